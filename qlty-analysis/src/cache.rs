@@ -1,6 +1,11 @@
 use anyhow::Result;
-use std::{collections::HashMap, fmt::Debug, fs, path::PathBuf};
-use tracing::{debug, error, trace};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::{collections::HashMap, fmt::Debug, fs, io::Read as _, path::PathBuf, time::Duration};
+use tracing::{debug, error, trace, warn};
+
+const HTTP_CACHE_TIMEOUT: Duration = Duration::from_secs(30);
+const HTTP_CACHE_CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
 
 #[derive(Debug, Clone, Default)]
 pub struct HashDigest {
@@ -173,6 +178,179 @@ impl Cache for FilesystemCache {
     }
 }
 
+/// A remote cache speaking the plain HTTP GET/PUT protocol used by Gradle and
+/// Bazel HTTP caches, where each entry is stored at `<base_url>/<key>`.
+///
+/// After the first connection failure the cache is skipped for the rest of
+/// the run, so an unreachable server costs at most one timeout.
+#[derive(Debug, Clone)]
+pub struct HttpCache {
+    pub base_url: String,
+    pub extension: String,
+    token: Option<String>,
+    agent: ureq::Agent,
+    unavailable: Arc<AtomicBool>,
+}
+
+impl HttpCache {
+    pub fn new(base_url: &str, extension: &str, token: Option<String>) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            extension: extension.to_string(),
+            token,
+            agent: ureq::AgentBuilder::new()
+                .timeout_connect(HTTP_CACHE_CONNECT_TIMEOUT)
+                .timeout(HTTP_CACHE_TIMEOUT)
+                .build(),
+            unavailable: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn is_unavailable(&self) -> bool {
+        self.unavailable.load(Ordering::Relaxed)
+    }
+
+    fn handle_error(&self, error: ureq::Error) -> anyhow::Error {
+        if let ureq::Error::Transport(_) = &error {
+            if !self.unavailable.swap(true, Ordering::Relaxed) {
+                warn!(
+                    "Remote cache at {} is unavailable, skipping it for this run",
+                    self.base_url
+                );
+            }
+        }
+
+        error.into()
+    }
+
+    fn url(&self, key: &dyn CacheKey) -> String {
+        format!("{}/{}.{}", self.base_url, key.hexdigest(), self.extension)
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        let request = self.agent.request(method, url);
+
+        match &self.token {
+            Some(token) => request.set("Authorization", &format!("Bearer {}", token)),
+            None => request,
+        }
+    }
+}
+
+impl Cache for HttpCache {
+    fn read(&self, key: &dyn CacheKey) -> Result<Option<Vec<u8>>> {
+        if self.is_unavailable() {
+            return Ok(None);
+        }
+
+        let url = self.url(key);
+        trace!("HttpCache read: {}", url);
+
+        match self.request("GET", &url).call() {
+            Ok(response) => {
+                let mut contents = vec![];
+                response.into_reader().read_to_end(&mut contents)?;
+                debug!("Remote cache hit: {:?}", &key.hexdigest());
+                Ok(Some(contents))
+            }
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(error) => Err(self.handle_error(error)),
+        }
+    }
+
+    fn write(&self, key: &dyn CacheKey, value: &[u8]) -> Result<()> {
+        if self.is_unavailable() {
+            return Ok(());
+        }
+
+        let url = self.url(key);
+        trace!("HttpCache write: {} ({} bytes)", url, value.len());
+
+        self.request("PUT", &url)
+            .set("Content-Type", "application/octet-stream")
+            .send_bytes(value)
+            .map_err(|error| self.handle_error(error))?;
+        Ok(())
+    }
+
+    fn path(&self, key: &dyn CacheKey) -> PathBuf {
+        PathBuf::from(self.url(key))
+    }
+
+    fn clear(&self) -> Result<()> {
+        // Remote caches are shared, so they are never cleared from here
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Cache> {
+        Box::new(self.clone())
+    }
+}
+
+/// Reads from a local cache first and falls back to a shared remote cache,
+/// copying remote hits into the local cache. Failures of the remote cache are
+/// logged and treated as misses so that an unavailable cache never fails a run.
+#[derive(Debug, Clone)]
+pub struct TieredCache {
+    pub local: Box<dyn Cache>,
+    pub remote: Box<dyn Cache>,
+    pub read_only: bool,
+}
+
+impl TieredCache {
+    pub fn new(local: Box<dyn Cache>, remote: Box<dyn Cache>, read_only: bool) -> Self {
+        Self {
+            local,
+            remote,
+            read_only,
+        }
+    }
+}
+
+impl Cache for TieredCache {
+    fn read(&self, key: &dyn CacheKey) -> Result<Option<Vec<u8>>> {
+        if let Some(contents) = self.local.read(key)? {
+            return Ok(Some(contents));
+        }
+
+        match self.remote.read(key) {
+            Ok(Some(contents)) => {
+                self.local.write(key, &contents)?;
+                Ok(Some(contents))
+            }
+            Ok(None) => Ok(None),
+            Err(error) => {
+                warn!("Failed to read from remote cache: {}", error);
+                Ok(None)
+            }
+        }
+    }
+
+    fn write(&self, key: &dyn CacheKey, value: &[u8]) -> Result<()> {
+        self.local.write(key, value)?;
+
+        if !self.read_only {
+            if let Err(error) = self.remote.write(key, value) {
+                warn!("Failed to write to remote cache: {}", error);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn path(&self, key: &dyn CacheKey) -> PathBuf {
+        self.local.path(key)
+    }
+
+    fn clear(&self) -> Result<()> {
+        self.local.clear()
+    }
+
+    fn clone_box(&self) -> Box<dyn Cache> {
+        Box::new(self.clone())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -216,4 +394,118 @@ mod test {
         cache.write(&digest, "Test 123".as_bytes()).unwrap();
         assert_eq!("Test 123".as_bytes(), cache.read(&digest).unwrap().unwrap());
     }
+
+    fn digest(value: &str) -> HashDigest {
+        let mut digest = HashDigest::new();
+        digest.add("foo", value);
+        digest.finalize();
+        digest
+    }
+
+    /// Serves a minimal in-memory GET/PUT cache and returns its base URL.
+    fn start_stub_server() -> String {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/cache", server.server_addr().to_ip().unwrap());
+
+        std::thread::spawn(move || {
+            let mut entries: HashMap<String, Vec<u8>> = HashMap::new();
+
+            for mut request in server.incoming_requests() {
+                let path = request.url().to_string();
+
+                let response = match request.method() {
+                    tiny_http::Method::Put => {
+                        let mut body = vec![];
+                        request.as_reader().read_to_end(&mut body).unwrap();
+                        entries.insert(path, body);
+                        tiny_http::Response::from_data(vec![]).with_status_code(201)
+                    }
+                    tiny_http::Method::Get => match entries.get(&path) {
+                        Some(body) => tiny_http::Response::from_data(body.clone()),
+                        None => tiny_http::Response::from_data(vec![]).with_status_code(404),
+                    },
+                    _ => tiny_http::Response::from_data(vec![]).with_status_code(405),
+                };
+
+                request.respond(response).unwrap();
+            }
+        });
+
+        url
+    }
+
+    #[test]
+    fn http_cache() {
+        let url = start_stub_server();
+        let cache = HttpCache::new(&url, "bytes", None);
+
+        assert_eq!(None, cache.read(&digest("missing")).unwrap());
+
+        cache.write(&digest("bar"), "Test 123".as_bytes()).unwrap();
+        assert_eq!(
+            "Test 123".as_bytes(),
+            cache.read(&digest("bar")).unwrap().unwrap()
+        );
+    }
+
+    #[test]
+    fn http_cache_skipped_after_connection_failure() {
+        let cache = HttpCache::new("http://127.0.0.1:1/cache", "bytes", None);
+
+        assert!(cache.read(&digest("bar")).is_err());
+        assert_eq!(None, cache.clone().read(&digest("bar")).unwrap());
+        assert!(cache.write(&digest("bar"), "Test 123".as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn tiered_cache_shares_remote_hits() {
+        let url = start_stub_server();
+
+        let machine_a = TieredCache::new(
+            Box::new(FilesystemCache::new(
+                tempfile::tempdir().unwrap().keep(),
+                "bytes",
+            )),
+            Box::new(HttpCache::new(&url, "bytes", None)),
+            false,
+        );
+        let local_b = FilesystemCache::new(tempfile::tempdir().unwrap().keep(), "bytes");
+        let machine_b = TieredCache::new(
+            Box::new(local_b.clone()),
+            Box::new(HttpCache::new(&url, "bytes", None)),
+            false,
+        );
+
+        machine_a
+            .write(&digest("bar"), "Test 123".as_bytes())
+            .unwrap();
+
+        assert_eq!(
+            "Test 123".as_bytes(),
+            machine_b.read(&digest("bar")).unwrap().unwrap()
+        );
+        assert_eq!(
+            "Test 123".as_bytes(),
+            local_b.read(&digest("bar")).unwrap().unwrap()
+        );
+    }
+
+    #[test]
+    fn tiered_cache_ignores_unavailable_remote() {
+        let cache = TieredCache::new(
+            Box::new(FilesystemCache::new(
+                tempfile::tempdir().unwrap().keep(),
+                "bytes",
+            )),
+            Box::new(HttpCache::new("http://127.0.0.1:1/cache", "bytes", None)),
+            false,
+        );
+
+        assert_eq!(None, cache.read(&digest("bar")).unwrap());
+        cache.write(&digest("bar"), "Test 123".as_bytes()).unwrap();
+        assert_eq!(
+            "Test 123".as_bytes(),
+            cache.read(&digest("bar")).unwrap().unwrap()
+        );
+    }
 }
@@ -1,5 +1,7 @@
+mod churn;
 mod diff;
 mod upstream;
 
+pub use churn::{Churn, FileChurn};
 pub use diff::{DiffLineFilter, DiffMode, GitDiff};
 pub use upstream::compute_upstream;
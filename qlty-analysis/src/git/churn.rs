@@ -0,0 +1,151 @@
+use anyhow::Result;
+use git2::{Patch, Repository, Sort};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// How often a file was changed in the history of the repository.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct FileChurn {
+    pub commits: usize,
+    pub lines_added: usize,
+    pub lines_deleted: usize,
+}
+
+/// Changes per file, by path relative to the root of the repository, across
+/// the non-merge commits reachable from HEAD.
+#[derive(Debug, Clone, Default)]
+pub struct Churn {
+    pub files: HashMap<PathBuf, FileChurn>,
+    pub commits: usize,
+}
+
+impl Churn {
+    /// Walks the history from HEAD, newest first, stopping at the first commit
+    /// older than `since` (in seconds since the epoch) or once `max_commits`
+    /// commits have been counted.
+    pub fn compute(
+        repository_root: &Path,
+        since: Option<i64>,
+        max_commits: Option<usize>,
+    ) -> Result<Self> {
+        let repository = Repository::open(repository_root)?;
+
+        let mut revwalk = repository.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+        revwalk.push_head()?;
+
+        let mut churn = Self::default();
+
+        for oid in revwalk {
+            if max_commits.is_some_and(|max_commits| churn.commits >= max_commits) {
+                break;
+            }
+
+            let commit = repository.find_commit(oid?)?;
+
+            if since.is_some_and(|since| commit.time().seconds() < since) {
+                break;
+            }
+
+            // Changes from merged branches are already counted in their own commits
+            if commit.parent_count() > 1 {
+                continue;
+            }
+
+            let parent_tree = match commit.parent_count() {
+                0 => None,
+                _ => Some(commit.parent(0)?.tree()?),
+            };
+
+            let diff =
+                repository.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+
+            for index in 0..diff.deltas().len() {
+                let Some(patch) = Patch::from_diff(&diff, index)? else {
+                    continue;
+                };
+
+                let delta = patch.delta();
+                let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+                    continue;
+                };
+
+                let (_, lines_added, lines_deleted) = patch.line_stats()?;
+
+                let file_churn = churn.files.entry(path.to_path_buf()).or_default();
+                file_churn.commits += 1;
+                file_churn.lines_added += lines_added;
+                file_churn.lines_deleted += lines_deleted;
+            }
+
+            churn.commits += 1;
+        }
+
+        debug!(
+            "Computed churn of {} files over {} commits",
+            churn.files.len(),
+            churn.commits
+        );
+
+        Ok(churn)
+    }
+
+    pub fn get(&self, path: &Path) -> FileChurn {
+        self.files.get(path).copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_test_utilities::git::sample_repo;
+    use std::fs;
+
+    fn commit_all(repo: &Repository, message: &str) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["."].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        let sig = repo.signature().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_compute() -> Result<()> {
+        let (td, repo) = sample_repo();
+
+        fs::write(td.path().join("greetings.rb"), "puts 'hi'\nputs 'there'\n").unwrap();
+        commit_all(&repo, "first");
+        fs::write(td.path().join("greetings.rb"), "puts 'hello'\n").unwrap();
+        commit_all(&repo, "second");
+
+        let churn = Churn::compute(td.path(), None, None)?;
+
+        assert_eq!(churn.commits, 3);
+        assert_eq!(churn.get(Path::new("greetings.rb")).commits, 3);
+        assert_eq!(churn.get(Path::new("lib/hello.rb")).commits, 1);
+        assert_eq!(churn.get(Path::new("missing.rb")), FileChurn::default());
+
+        let churn = Churn::compute(td.path(), None, Some(2))?;
+
+        assert_eq!(churn.commits, 2);
+        assert_eq!(
+            churn.get(Path::new("greetings.rb")),
+            FileChurn {
+                commits: 2,
+                lines_added: 3,
+                lines_deleted: 3,
+            }
+        );
+        assert_eq!(churn.get(Path::new("lib/hello.rb")).commits, 0);
+
+        Ok(())
+    }
+}
@@ -293,6 +293,10 @@ impl DiffLineTransformer {
     pub fn new(index: FileIndex) -> Self {
         Self { index }
     }
+
+    pub fn index(&self) -> &FileIndex {
+        &self.index
+    }
 }
 
 impl IssueTransformer for DiffLineTransformer {
@@ -0,0 +1,345 @@
+use anyhow::{Context, Result};
+use qlty_config::issue_transformer::IssueTransformer;
+use qlty_types::analysis::v1::Issue;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+use tracing::{debug, trace};
+
+const BASELINE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub fingerprint: String,
+    pub tool: String,
+    pub rule_key: String,
+    pub path: String,
+    #[serde(default = "default_count")]
+    pub count: usize,
+}
+
+fn default_count() -> usize {
+    1
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub issues: Vec<BaselineEntry>,
+}
+
+impl Default for Baseline {
+    fn default() -> Self {
+        Self {
+            version: BASELINE_VERSION,
+            issues: vec![],
+        }
+    }
+}
+
+impl Baseline {
+    pub fn read(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline file {}", path.display()))?;
+        let baseline: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse baseline file {}", path.display()))?;
+
+        Ok(Some(baseline))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json + "\n")
+            .with_context(|| format!("Failed to write baseline file {}", path.display()))
+    }
+
+    pub fn from_issues(issues: &[Issue]) -> Self {
+        let mut entries: BTreeMap<(String, String, String, String), BaselineEntry> =
+            BTreeMap::new();
+
+        for issue in issues {
+            let path = issue.path().unwrap_or_default();
+            let fingerprint = Self::fingerprint(issue);
+
+            entries
+                .entry((
+                    path.clone(),
+                    issue.tool.clone(),
+                    issue.rule_key.clone(),
+                    fingerprint.clone(),
+                ))
+                .and_modify(|entry| entry.count += 1)
+                .or_insert(BaselineEntry {
+                    fingerprint,
+                    tool: issue.tool.clone(),
+                    rule_key: issue.rule_key.clone(),
+                    path,
+                    count: 1,
+                });
+        }
+
+        Self {
+            version: BASELINE_VERSION,
+            issues: entries.into_values().collect(),
+        }
+    }
+
+    /// Returns a copy of this baseline with entries that no longer match any of
+    /// the given issues removed. New issues are never added.
+    pub fn prune(&self, issues: &[Issue]) -> Self {
+        let current = Self::from_issues(issues).counts();
+
+        let issues = self
+            .issues
+            .iter()
+            .filter_map(|entry| {
+                current
+                    .get(&entry.fingerprint)
+                    .map(|current_count| BaselineEntry {
+                        count: entry.count.min(*current_count),
+                        ..entry.clone()
+                    })
+            })
+            .collect();
+
+        Self {
+            version: BASELINE_VERSION,
+            issues,
+        }
+    }
+
+    pub fn issues_count(&self) -> usize {
+        self.issues.iter().map(|entry| entry.count).sum()
+    }
+
+    // Fingerprints intentionally ignore line numbers so that entries survive
+    // code being moved around within a file.
+    pub fn fingerprint(issue: &Issue) -> String {
+        let source = if issue.snippet.trim().is_empty() {
+            &issue.message
+        } else {
+            &issue.snippet
+        };
+
+        let mut hasher = Sha256::new();
+        hasher.update(issue.tool.as_bytes());
+        hasher.update([0]);
+        hasher.update(issue.rule_key.as_bytes());
+        hasher.update([0]);
+        hasher.update(issue.path().unwrap_or_default().as_bytes());
+        hasher.update([0]);
+        hasher.update(Self::normalize_snippet(source).as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    fn normalize_snippet(snippet: &str) -> String {
+        snippet
+            .lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn counts(&self) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+
+        for entry in &self.issues {
+            *counts.entry(entry.fingerprint.clone()).or_insert(0) += entry.count;
+        }
+
+        counts
+    }
+}
+
+#[derive(Debug)]
+pub struct BaselineFilter {
+    path: PathBuf,
+    counts: HashMap<String, usize>,
+    remaining: RwLock<HashMap<String, usize>>,
+}
+
+impl Clone for BaselineFilter {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            counts: self.counts.clone(),
+            remaining: RwLock::new(self.remaining.read().unwrap().clone()),
+        }
+    }
+}
+
+impl BaselineFilter {
+    pub fn new(path: PathBuf, baseline: &Baseline) -> Self {
+        let counts = baseline.counts();
+
+        Self {
+            path,
+            remaining: RwLock::new(counts.clone()),
+            counts,
+        }
+    }
+}
+
+impl IssueTransformer for BaselineFilter {
+    fn initialize(&self) {
+        debug!(
+            "Loaded {} baseline fingerprints from {}",
+            self.counts.len(),
+            self.path.display()
+        );
+        *self.remaining.write().unwrap() = self.counts.clone();
+    }
+
+    fn transform(&self, issue: Issue) -> Option<Issue> {
+        let fingerprint = Baseline::fingerprint(&issue);
+        let mut remaining = self.remaining.write().unwrap();
+
+        if let Some(count) = remaining.get_mut(&fingerprint) {
+            if *count > 0 {
+                *count -= 1;
+                trace!("Issue matched baseline fingerprint {}", fingerprint);
+                return None;
+            }
+        }
+
+        Some(issue)
+    }
+
+    fn hides_issues(&self) -> bool {
+        true
+    }
+
+    fn clone_box(&self) -> Box<dyn IssueTransformer> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::{Location, Range};
+
+    fn make_issue(rule_key: &str, path: &str, line: u32, snippet: &str) -> Issue {
+        Issue {
+            tool: "eslint".into(),
+            rule_key: rule_key.into(),
+            message: "Unexpected var".into(),
+            snippet: snippet.into(),
+            location: Some(Location {
+                path: path.into(),
+                range: Some(Range {
+                    start_line: line,
+                    end_line: line,
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_line_numbers_and_whitespace() {
+        let a = make_issue("no-var", "a.js", 3, "var x = 1;");
+        let b = make_issue("no-var", "a.js", 30, "    var   x = 1;  ");
+
+        assert_eq!(Baseline::fingerprint(&a), Baseline::fingerprint(&b));
+    }
+
+    #[test]
+    fn test_fingerprint_distinguishes_rule_path_and_snippet() {
+        let issue = make_issue("no-var", "a.js", 3, "var x = 1;");
+
+        let fingerprint = Baseline::fingerprint(&issue);
+        assert_ne!(
+            fingerprint,
+            Baseline::fingerprint(&make_issue("other", "a.js", 3, "var x = 1;"))
+        );
+        assert_ne!(
+            fingerprint,
+            Baseline::fingerprint(&make_issue("no-var", "b.js", 3, "var x = 1;"))
+        );
+        assert_ne!(
+            fingerprint,
+            Baseline::fingerprint(&make_issue("no-var", "a.js", 3, "var y = 1;"))
+        );
+    }
+
+    #[test]
+    fn test_from_issues_counts_duplicates() {
+        let issues = vec![
+            make_issue("no-var", "a.js", 1, "var x = 1;"),
+            make_issue("no-var", "a.js", 5, "var x = 1;"),
+            make_issue("no-var", "b.js", 1, "var x = 1;"),
+        ];
+
+        let baseline = Baseline::from_issues(&issues);
+        assert_eq!(baseline.issues.len(), 2);
+        assert_eq!(baseline.issues[0].path, "a.js");
+        assert_eq!(baseline.issues[0].count, 2);
+        assert_eq!(baseline.issues_count(), 3);
+    }
+
+    #[test]
+    fn test_filter_hides_only_baselined_occurrences() {
+        let baseline = Baseline::from_issues(&[make_issue("no-var", "a.js", 1, "var x = 1;")]);
+        let filter = BaselineFilter::new(PathBuf::from("baseline.json"), &baseline);
+        filter.initialize();
+
+        assert_eq!(
+            filter.transform(make_issue("no-var", "a.js", 10, "var x = 1;")),
+            None
+        );
+        assert!(filter
+            .transform(make_issue("no-var", "a.js", 11, "var x = 1;"))
+            .is_some());
+        assert!(filter
+            .transform(make_issue("no-var", "a.js", 12, "var z = 1;"))
+            .is_some());
+
+        filter.initialize();
+        assert_eq!(
+            filter.transform(make_issue("no-var", "a.js", 11, "var x = 1;")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_prune_removes_fixed_entries() {
+        let baseline = Baseline::from_issues(&[
+            make_issue("no-var", "a.js", 1, "var x = 1;"),
+            make_issue("no-var", "a.js", 2, "var x = 1;"),
+            make_issue("no-var", "b.js", 1, "var y = 1;"),
+        ]);
+
+        let pruned = baseline.prune(&[
+            make_issue("no-var", "a.js", 7, "var x = 1;"),
+            make_issue("no-var", "c.js", 1, "var new = 1;"),
+        ]);
+
+        assert_eq!(pruned.issues.len(), 1);
+        assert_eq!(pruned.issues[0].path, "a.js");
+        assert_eq!(pruned.issues[0].count, 1);
+    }
+
+    #[test]
+    fn test_read_write_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baseline.json");
+
+        assert_eq!(Baseline::read(&path).unwrap(), None);
+
+        let baseline = Baseline::from_issues(&[make_issue("no-var", "a.js", 1, "var x = 1;")]);
+        baseline.write(&path).unwrap();
+
+        assert_eq!(Baseline::read(&path).unwrap(), Some(baseline));
+    }
+}
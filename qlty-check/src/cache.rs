@@ -1,7 +1,7 @@
 use crate::planner::config_files::PluginConfigFile;
 use crate::planner::target::Target;
 use crate::tool::Tool;
-use anyhow::{Context, Result};
+use anyhow::Result;
 use git2::{Repository, Status};
 use itertools::Itertools;
 use prost::Message;
@@ -93,13 +93,14 @@ pub struct IssuesCacheHit {
 
 #[derive(Debug, Clone)]
 pub struct IssuesCacheKey {
-    repository_tree_sha: Option<String>,
-    dirty_paths: Vec<PathBuf>,
+    blob_ids: Arc<HashMap<PathBuf, String>>,
+    dirty_paths: Arc<Vec<PathBuf>>,
     pub digest: HashDigest,
 }
 
 #[derive(Debug)]
 struct InvocationCacheKey {
+    root: PathBuf,
     qlty_version: String,
     tool: Box<dyn Tool>,
     plugin: Arc<PluginDef>,
@@ -244,14 +245,20 @@ impl InvocationCacheKey {
         );
 
         digest.add("qlty_version", &self.qlty_version);
-        digest.add("tool", &self.tool.directory());
+        // Keys avoid absolute paths so results can be shared through a remote cache
+        digest.add(
+            "tool",
+            &format!("{}/{}", self.tool.name(), self.tool.directory_name()),
+        );
         digest.add("driver_name", &self.driver_name);
 
         for config in self.configs.clone().iter().sorted() {
-            digest.add(&config.path.to_string_lossy(), &config.contents);
+            let path = config.path.strip_prefix(&self.root).unwrap_or(&config.path);
+            digest.add(&path.to_string_lossy(), &config.contents);
         }
 
         for (path, contents) in self.affects_cache.iter().sorted() {
+            let path = path.strip_prefix(&self.root).unwrap_or(path);
             digest.add(&path.to_string_lossy(), contents);
         }
 
@@ -275,17 +282,23 @@ impl IssuesCacheKey {
             cache_busters.insert(path, contents);
         }
 
-        let mut repository_tree_sha: Option<String> = None;
+        let mut root = PathBuf::new();
+        let mut blob_ids = HashMap::new();
         let mut dirty_paths = Vec::new();
-        if let Ok(repository) = Workspace::new().and_then(|w| w.repo()) {
-            repository_tree_sha = Self::repository_sha(&repository).ok();
-            dirty_paths = Self::collect_dirty_paths(&repository);
+        if let Ok(workspace) = Workspace::new() {
+            root = workspace.root.clone();
+
+            if let Ok(repository) = workspace.repo() {
+                blob_ids = Self::collect_blob_ids(&repository).unwrap_or_default();
+                dirty_paths = Self::collect_dirty_paths(&repository);
+            }
         }
 
         Self {
-            repository_tree_sha,
-            dirty_paths,
+            blob_ids: Arc::new(blob_ids),
+            dirty_paths: Arc::new(dirty_paths),
             digest: InvocationCacheKey {
+                root,
                 qlty_version: QLTY_VERSION.to_string(),
                 tool: tool.clone(),
                 plugin: plugin.clone(),
@@ -303,7 +316,7 @@ impl IssuesCacheKey {
         self.digest
             .add("target_contents_size", &target.contents_size.to_string());
 
-        if !self.add_target_sha(target) {
+        if !self.add_target_blob(target) {
             self.digest.add(
                 "target_content_modified",
                 &target
@@ -318,28 +331,36 @@ impl IssuesCacheKey {
         self.digest.finalize();
     }
 
-    fn add_target_sha(&mut self, target: &Target) -> bool {
-        if let Some(sha) = &self.repository_tree_sha {
-            if !self
-                .dirty_paths
-                .iter()
-                .any(|path| target.path.starts_with(path))
-            {
-                self.digest.add("target_sha", sha);
-                return true;
-            }
+    // Clean files are keyed on their contents rather than the commit, so that
+    // unchanged files hit the cache on every commit and branch
+    fn add_target_blob(&mut self, target: &Target) -> bool {
+        if self
+            .dirty_paths
+            .iter()
+            .any(|path| target.path.starts_with(path))
+        {
+            return false;
+        }
+
+        if let Some(blob_id) = self.blob_ids.get(&target.path) {
+            self.digest.add("target_blob", blob_id);
+            return true;
         }
 
         false
     }
 
-    fn repository_sha(repo: &Repository) -> Result<String> {
+    fn collect_blob_ids(repo: &Repository) -> Result<HashMap<PathBuf, String>> {
         Ok(repo
-            .head()?
-            .resolve()?
-            .target()
-            .context("missing target")?
-            .to_string())
+            .index()?
+            .iter()
+            .map(|entry| {
+                (
+                    PathBuf::from(String::from_utf8_lossy(&entry.path).as_ref()),
+                    entry.id.to_string(),
+                )
+            })
+            .collect())
     }
 
     fn collect_dirty_paths(repo: &Repository) -> Vec<PathBuf> {
@@ -360,3 +381,63 @@ impl CacheKey for IssuesCacheKey {
         self.digest.hexdigest()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_analysis::WorkspaceEntryKind;
+    use std::time::{Duration, SystemTime};
+
+    fn cache_key(dirty_paths: Vec<PathBuf>) -> IssuesCacheKey {
+        IssuesCacheKey {
+            blob_ids: Arc::new(HashMap::from([(
+                PathBuf::from("src/main.rs"),
+                "8ab686eafeb1f44702738c8b0f24f2567c36da6d".to_string(),
+            )])),
+            dirty_paths: Arc::new(dirty_paths),
+            digest: HashDigest::new(),
+        }
+    }
+
+    fn target(path: &str, content_modified: SystemTime) -> Target {
+        Target {
+            path: PathBuf::from(path),
+            kind: WorkspaceEntryKind::File,
+            content_modified,
+            contents_size: 10,
+            language_name: None,
+        }
+    }
+
+    fn hexdigest(mut cache_key: IssuesCacheKey, target: &Target) -> String {
+        cache_key.finalize(target);
+        cache_key.hexdigest()
+    }
+
+    #[test]
+    fn test_clean_file_keyed_on_blob() {
+        let earlier = SystemTime::UNIX_EPOCH;
+        let later = earlier + Duration::from_secs(10);
+
+        assert_eq!(
+            hexdigest(cache_key(vec![]), &target("src/main.rs", earlier)),
+            hexdigest(cache_key(vec![]), &target("src/main.rs", later))
+        );
+    }
+
+    #[test]
+    fn test_dirty_or_untracked_file_keyed_on_modified_time() {
+        let earlier = SystemTime::UNIX_EPOCH;
+        let later = earlier + Duration::from_secs(10);
+        let dirty = || cache_key(vec![PathBuf::from("src/main.rs")]);
+
+        assert_ne!(
+            hexdigest(dirty(), &target("src/main.rs", earlier)),
+            hexdigest(dirty(), &target("src/main.rs", later))
+        );
+        assert_ne!(
+            hexdigest(cache_key(vec![]), &target("src/lib.rs", earlier)),
+            hexdigest(cache_key(vec![]), &target("src/lib.rs", later))
+        );
+    }
+}
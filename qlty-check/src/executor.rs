@@ -173,7 +173,7 @@ impl Executor {
         }));
 
         if self.plan.ai_enabled {
-            transformers.push(Box::new(Fixer::new(&self.plan, self.progress.clone())));
+            transformers.push(Box::new(Fixer::new(&self.plan, self.progress.clone())?));
         }
 
         if !self.plan.invocations.is_empty() {
@@ -34,6 +34,7 @@ use crate::parser::trufflehog::Trufflehog;
 use crate::parser::tsc::Tsc;
 use crate::parser::Parser;
 use crate::planner::InvocationPlan;
+use crate::profile::ResourceSampler;
 use crate::tool::command_builder::Command;
 use crate::ui::ProgressBar;
 use crate::ui::ProgressTask;
@@ -132,11 +133,20 @@ impl Driver {
         let invocation_label = plan.invocation_label();
         let running = Arc::new(AtomicBool::new(true));
         let running_clone = Arc::clone(&running);
+        let timed_out = Arc::new(AtomicBool::new(false));
+        let timed_out_clone = Arc::clone(&timed_out);
+
+        let sampler = if plan.settings.profile {
+            Some(ResourceSampler::start(pids.clone()))
+        } else {
+            None
+        };
 
         thread::spawn(move || {
             thread::sleep(Duration::from_secs(timeout));
             if running_clone.load(Ordering::SeqCst) {
                 error!("Killing {} process after {}s", invocation_label, timeout);
+                timed_out_clone.store(true, Ordering::SeqCst);
                 Self::terminate_processes(pids);
             }
         });
@@ -144,6 +154,7 @@ impl Driver {
         let output = handle.into_output()?;
         let duration = timer.elapsed().as_secs_f64();
         running.store(false, Ordering::SeqCst);
+        let resources = sampler.map(ResourceSampler::finish);
 
         info!(
             "{}: Completed {} in {:.3}s (exit {})",
@@ -153,7 +164,10 @@ impl Driver {
             output.status.code().unwrap_or(-1)
         );
 
-        InvocationResult::from_command_output(plan, rerun, &output, duration)
+        let mut result = InvocationResult::from_command_output(plan, rerun, &output, duration)?;
+        result.timed_out = timed_out.load(Ordering::SeqCst);
+        result.resources = resources;
+        Ok(result)
     }
 
     pub fn terminate_processes(pids: Vec<u32>) {
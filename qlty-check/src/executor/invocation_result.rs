@@ -2,6 +2,7 @@ use crate::{
     cache::{IssueCache, IssuesCacheKey},
     command::ExecResult,
     planner::InvocationPlan,
+    profile::ResourceUsage,
     results::FormattedFile,
 };
 use anyhow::{Context, Result};
@@ -26,6 +27,8 @@ pub struct InvocationResult {
     pub invocation: Invocation,
     pub file_results: Option<Vec<FileResult>>,
     pub formatted: Option<Vec<FormattedFile>>,
+    pub timed_out: bool,
+    pub resources: Option<ResourceUsage>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -143,6 +146,8 @@ impl InvocationResult {
             messages: Default::default(),
             file_results: Default::default(),
             formatted: Default::default(),
+            timed_out: Default::default(),
+            resources: Default::default(),
         };
 
         invocation.process_results()?;
@@ -0,0 +1,434 @@
+use crate::baseline::Baseline;
+use anyhow::{Context, Result};
+use git2::{Repository, StatusOptions};
+use qlty_types::analysis::v1::Issue;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+use tracing::{debug, warn};
+
+const HISTORY_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryIssue {
+    pub fingerprint: String,
+    pub tool: String,
+    pub rule_key: String,
+    pub path: String,
+    pub line: u32,
+    pub message: String,
+}
+
+impl HistoryIssue {
+    pub fn rule_id(&self) -> String {
+        format!("{}:{}", self.tool, self.rule_key)
+    }
+
+    /// Returns the first `depth` directories of the path, or "." for files at
+    /// the root of the repository.
+    pub fn directory(&self, depth: usize) -> String {
+        let components = Path::new(&self.path)
+            .parent()
+            .map(|parent| {
+                parent
+                    .components()
+                    .take(depth)
+                    .map(|component| component.as_os_str().to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        if components.is_empty() {
+            ".".to_string()
+        } else {
+            components.join("/")
+        }
+    }
+}
+
+impl From<&Issue> for HistoryIssue {
+    fn from(issue: &Issue) -> Self {
+        Self {
+            fingerprint: Baseline::fingerprint(issue),
+            tool: issue.tool.clone(),
+            rule_key: issue.rule_key.clone(),
+            path: issue.path().unwrap_or_default(),
+            line: issue.range().unwrap_or_default().start_line,
+            message: issue.message.clone(),
+        }
+    }
+}
+
+/// The issues found for a single commit.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub version: u32,
+    pub commit_sha: String,
+    pub committed_at: i64,
+    pub recorded_at: i64,
+    pub issues: Vec<HistoryIssue>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistoryDiff {
+    pub introduced: Vec<HistoryIssue>,
+    pub fixed: Vec<HistoryIssue>,
+}
+
+impl HistoryEntry {
+    pub fn new(commit_sha: &str, committed_at: i64, issues: &[Issue]) -> Self {
+        let mut issues = issues.iter().map(HistoryIssue::from).collect::<Vec<_>>();
+        issues.sort_by(|a, b| {
+            (&a.path, a.line, &a.fingerprint).cmp(&(&b.path, b.line, &b.fingerprint))
+        });
+
+        Self {
+            version: HISTORY_VERSION,
+            commit_sha: commit_sha.to_string(),
+            committed_at,
+            recorded_at: chrono::Utc::now().timestamp(),
+            issues,
+        }
+    }
+
+    pub fn counts_by_rule(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+
+        for issue in &self.issues {
+            *counts.entry(issue.rule_id()).or_default() += 1;
+        }
+
+        counts
+    }
+
+    pub fn counts_by_directory(&self, depth: usize) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+
+        for issue in &self.issues {
+            *counts.entry(issue.directory(depth)).or_default() += 1;
+        }
+
+        counts
+    }
+
+    /// Compares the issues of this entry with those of a later entry. Issues
+    /// are matched by fingerprint, so moving code around does not show up as
+    /// an issue being both fixed and introduced.
+    pub fn diff(&self, later: &HistoryEntry) -> HistoryDiff {
+        HistoryDiff {
+            introduced: Self::unmatched(&later.issues, &self.issues),
+            fixed: Self::unmatched(&self.issues, &later.issues),
+        }
+    }
+
+    fn unmatched(issues: &[HistoryIssue], others: &[HistoryIssue]) -> Vec<HistoryIssue> {
+        let mut remaining: HashMap<&str, usize> = HashMap::new();
+
+        for other in others {
+            *remaining.entry(other.fingerprint.as_str()).or_default() += 1;
+        }
+
+        issues
+            .iter()
+            .filter(
+                |issue| match remaining.get_mut(issue.fingerprint.as_str()) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        false
+                    }
+                    _ => true,
+                },
+            )
+            .cloned()
+            .collect()
+    }
+}
+
+/// The command whose issues are recorded. `qlty build` also reports smells,
+/// so each command keeps a history of its own to keep trends comparable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistorySource {
+    Check,
+    Build,
+}
+
+impl HistorySource {
+    fn directory_name(&self) -> &'static str {
+        match self {
+            HistorySource::Check => "check",
+            HistorySource::Build => "build",
+        }
+    }
+}
+
+/// A local store of issues keyed by commit SHA, kept in the results directory
+/// of the library.
+#[derive(Debug, Clone)]
+pub struct History {
+    dir: PathBuf,
+}
+
+impl History {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn for_source(history_dir: &Path, source: HistorySource) -> Self {
+        Self::new(history_dir.join(source.directory_name()))
+    }
+
+    /// Records the issues for the commit checked out in the repository,
+    /// replacing any earlier entry for the same commit. Nothing is recorded
+    /// when the working tree has changes, as the issues would not belong to
+    /// that commit.
+    pub fn record_head(
+        &self,
+        repository: &Repository,
+        issues: &[Issue],
+    ) -> Result<Option<HistoryEntry>> {
+        if Self::is_dirty(repository)? {
+            debug!("Not recording issue history for a working tree with changes");
+            return Ok(None);
+        }
+
+        let commit = repository.head()?.peel_to_commit()?;
+        let entry = HistoryEntry::new(&commit.id().to_string(), commit.time().seconds(), issues);
+        self.record(&entry)?;
+        Ok(Some(entry))
+    }
+
+    pub fn record(&self, entry: &HistoryEntry) -> Result<()> {
+        std::fs::create_dir_all(&self.dir).with_context(|| {
+            format!("Failed to create history directory {}", self.dir.display())
+        })?;
+
+        let path = self.entry_path(&entry.commit_sha);
+        debug!(
+            "Recording {} issues to {}",
+            entry.issues.len(),
+            path.display()
+        );
+
+        let json = serde_json::to_string(entry)?;
+        std::fs::write(&path, json)
+            .with_context(|| format!("Failed to write history file {}", path.display()))
+    }
+
+    pub fn get(&self, commit_sha: &str) -> Result<Option<HistoryEntry>> {
+        let path = self.entry_path(commit_sha);
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        Self::read(&path).map(Some)
+    }
+
+    /// Returns all recorded entries, oldest commit first.
+    pub fn entries(&self) -> Result<Vec<HistoryEntry>> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut entries = vec![];
+
+        for file in std::fs::read_dir(&self.dir)? {
+            let path = file?.path();
+
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                match Self::read(&path) {
+                    Ok(entry) => entries.push(entry),
+                    Err(err) => warn!("Skipping history file {}: {}", path.display(), err),
+                }
+            }
+        }
+
+        entries.sort_by_key(|entry| (entry.committed_at, entry.recorded_at));
+        Ok(entries)
+    }
+
+    fn is_dirty(repository: &Repository) -> Result<bool> {
+        let mut options = StatusOptions::new();
+        options.include_untracked(true).include_ignored(false);
+
+        Ok(!repository.statuses(Some(&mut options))?.is_empty())
+    }
+
+    fn read(path: &Path) -> Result<HistoryEntry> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read history file {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse history file {}", path.display()))
+    }
+
+    fn entry_path(&self, commit_sha: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", commit_sha))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::{Location, Range};
+    use tempfile::tempdir;
+
+    fn issue(rule_key: &str, path: &str, line: u32, snippet: &str) -> Issue {
+        Issue {
+            tool: "eslint".to_string(),
+            rule_key: rule_key.to_string(),
+            message: format!("Violation of {}", rule_key),
+            snippet: snippet.to_string(),
+            location: Some(Location {
+                path: path.to_string(),
+                range: Some(Range {
+                    start_line: line,
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_counts() {
+        let entry = HistoryEntry::new(
+            "abc",
+            1,
+            &[
+                issue("no-var", "src/app/a.js", 1, "var a"),
+                issue("no-var", "src/lib/b.js", 1, "var b"),
+                issue("eqeqeq", "index.js", 1, "a == b"),
+            ],
+        );
+
+        assert_eq!(
+            entry.counts_by_rule(),
+            BTreeMap::from([
+                ("eslint:eqeqeq".to_string(), 1),
+                ("eslint:no-var".to_string(), 2)
+            ])
+        );
+        assert_eq!(
+            entry.counts_by_directory(1),
+            BTreeMap::from([(".".to_string(), 1), ("src".to_string(), 2)])
+        );
+        assert_eq!(
+            entry.counts_by_directory(2),
+            BTreeMap::from([
+                (".".to_string(), 1),
+                ("src/app".to_string(), 1),
+                ("src/lib".to_string(), 1)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = HistoryEntry::new(
+            "abc",
+            1,
+            &[
+                issue("no-var", "a.js", 1, "var a"),
+                issue("no-var", "a.js", 2, "var a"),
+                issue("eqeqeq", "a.js", 3, "a == b"),
+            ],
+        );
+        let after = HistoryEntry::new(
+            "def",
+            2,
+            &[
+                issue("no-var", "a.js", 10, "var a"),
+                issue("no-console", "a.js", 20, "console.log(a)"),
+            ],
+        );
+
+        let diff = before.diff(&after);
+        let rules = |issues: &[HistoryIssue]| {
+            issues
+                .iter()
+                .map(|issue| (issue.rule_key.clone(), issue.line))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            rules(&diff.introduced),
+            vec![("no-console".to_string(), 20)]
+        );
+        assert_eq!(
+            rules(&diff.fixed),
+            vec![("no-var".to_string(), 2), ("eqeqeq".to_string(), 3)]
+        );
+    }
+
+    #[test]
+    fn test_record_and_entries() {
+        let dir = tempdir().unwrap();
+        let history = History::new(dir.path().join("history"));
+
+        assert!(history.entries().unwrap().is_empty());
+
+        let later = HistoryEntry::new("def", 20, &[issue("no-var", "a.js", 1, "var a")]);
+        let earlier = HistoryEntry::new("abc", 10, &[]);
+        history.record(&later).unwrap();
+        history.record(&earlier).unwrap();
+
+        let shas = history
+            .entries()
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.commit_sha)
+            .collect::<Vec<_>>();
+        assert_eq!(shas, vec!["abc", "def"]);
+
+        assert_eq!(history.get("def").unwrap(), Some(later));
+        assert_eq!(history.get("missing").unwrap(), None);
+    }
+
+    #[test]
+    fn test_sources_are_kept_apart() {
+        let dir = tempdir().unwrap();
+        let check = History::for_source(dir.path(), HistorySource::Check);
+        let build = History::for_source(dir.path(), HistorySource::Build);
+
+        check
+            .record(&HistoryEntry::new(
+                "abc",
+                10,
+                &[issue("no-var", "a.js", 1, "var a")],
+            ))
+            .unwrap();
+        build.record(&HistoryEntry::new("abc", 10, &[])).unwrap();
+
+        assert_eq!(check.get("abc").unwrap().unwrap().issues.len(), 1);
+        assert_eq!(build.get("abc").unwrap().unwrap().issues.len(), 0);
+    }
+
+    #[test]
+    fn test_record_head_skips_dirty_working_tree() {
+        let dir = tempdir().unwrap();
+        let repository = Repository::init(dir.path().join("repo")).unwrap();
+        std::fs::write(dir.path().join("repo/a.js"), "var a").unwrap();
+
+        let mut index = repository.index().unwrap();
+        index.add_path(Path::new("a.js")).unwrap();
+        index.write().unwrap();
+        let tree = repository.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("qlty", "qlty@example.com").unwrap();
+        repository
+            .commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[])
+            .unwrap();
+
+        let history = History::new(dir.path().join("history"));
+        let issues = [issue("no-var", "a.js", 1, "var a")];
+
+        assert!(history.record_head(&repository, &issues).unwrap().is_some());
+
+        std::fs::write(dir.path().join("repo/a.js"), "var b").unwrap();
+        assert!(history.record_head(&repository, &issues).unwrap().is_none());
+    }
+}
@@ -1,6 +1,8 @@
+pub mod baseline;
 pub mod cache;
 mod command;
 pub mod executor;
+pub mod history;
 mod issue_muter;
 pub mod llm;
 pub mod parser;
@@ -8,6 +10,7 @@ mod patch_builder;
 pub mod patcher;
 pub mod planner;
 mod processor;
+pub mod profile;
 pub mod report;
 pub mod results;
 mod settings;
@@ -15,6 +18,7 @@ pub mod source_reader;
 pub mod tool;
 pub mod ui;
 mod utils;
+pub mod watcher;
 
 pub use executor::{Executor, InvocationResult};
 pub use patch_builder::PATCH_CONTEXT_LENGTH;
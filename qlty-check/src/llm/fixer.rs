@@ -1,11 +1,12 @@
+use super::openai::OpenAiClient;
 use crate::planner::Plan;
 use crate::source_reader::SourceReader;
 use crate::ui::ProgressBar as _;
 use crate::{executor::staging_area::StagingArea, Progress};
 use anyhow::Result;
 use itertools::Itertools;
-use lazy_static::lazy_static;
 use qlty_cloud::Client;
+use qlty_config::config::{Ai, AiBackend};
 use qlty_config::issue_transformer::IssueTransformer;
 use qlty_types::analysis::v1::{Issue, Suggestion};
 use rayon::{ThreadPool, ThreadPoolBuilder};
@@ -15,18 +16,6 @@ use std::sync::{Arc, Mutex};
 use tracing::{debug, info, warn};
 use ureq::json;
 
-const MAX_FIXES: usize = 500;
-const MAX_FIXES_PER_FILE: usize = 30;
-const MAX_CONCURRENT_FIXES: usize = 10;
-const MAX_BATCH_SIZE: usize = 15;
-
-lazy_static! {
-    static ref API_THREAD_POOL: ThreadPool = ThreadPoolBuilder::new()
-        .num_threads(MAX_CONCURRENT_FIXES)
-        .build()
-        .unwrap();
-}
-
 #[derive(Clone, Debug)]
 pub struct Fixer {
     progress: Progress,
@@ -35,6 +24,9 @@ pub struct Fixer {
     attempts_per_file: Arc<Mutex<HashMap<String, AtomicUsize>>>,
     total_attempts: Arc<AtomicUsize>,
     auth_token: String,
+    settings: Ai,
+    openai: Option<OpenAiClient>,
+    thread_pool: Arc<ThreadPool>,
 }
 
 impl IssueTransformer for Fixer {
@@ -47,7 +39,7 @@ impl IssueTransformer for Fixer {
             .map(|(path, issues)| {
                 if let Some(path) = path {
                     issues
-                        .chunks(MAX_BATCH_SIZE)
+                        .chunks(self.batch_size(issues))
                         .flat_map(|chunk| self.fix_issue(path, chunk))
                         .collect_vec()
                 } else {
@@ -64,25 +56,48 @@ impl IssueTransformer for Fixer {
 }
 
 impl Fixer {
-    pub fn new(plan: &Plan, progress: Progress) -> Self {
+    pub fn new(plan: &Plan, progress: Progress) -> Result<Self> {
         // If auth_token is missing, use empty string as a fallback
         let auth_token = plan.auth_token.clone().unwrap_or_default();
+        let settings = plan.config.ai.clone();
+
+        let openai = match settings.backend {
+            AiBackend::Qlty => None,
+            AiBackend::Openai => Some(OpenAiClient::new(&settings)?),
+        };
 
-        Self {
+        let thread_pool = ThreadPoolBuilder::new()
+            .num_threads(settings.max_concurrent_fixes.max(1))
+            .build()?;
+
+        Ok(Self {
             progress,
             staging_area: plan.staging_area.clone(),
             r#unsafe: plan.allow_unsafe,
             attempts_per_file: Arc::new(Mutex::new(HashMap::new())),
             total_attempts: Arc::new(AtomicUsize::new(0)),
             auth_token,
+            settings,
+            openai,
+            thread_pool: Arc::new(thread_pool),
+        })
+    }
+
+    // Fixes from the openai backend are split from a single rewrite of the
+    // file, so all of a file's issues go into one request to keep them apart
+    fn batch_size(&self, issues: &[Issue]) -> usize {
+        if self.openai.is_some() {
+            issues.len().max(1)
+        } else {
+            self.settings.max_batch_size.max(1)
         }
     }
 
     fn reached_max_fixes(&self, path: &String, issues: &[Issue]) -> bool {
-        if self.total_attempts.load(Ordering::Relaxed) + issues.len() >= MAX_FIXES {
+        if self.total_attempts.load(Ordering::Relaxed) + issues.len() >= self.settings.max_fixes {
             debug!(
                 "Skipping all issue due to max attempts of {} reached",
-                MAX_FIXES
+                self.settings.max_fixes
             );
             return true;
         }
@@ -91,7 +106,7 @@ impl Fixer {
         let file_attempts = attempts_per_file
             .entry(path.clone())
             .or_insert(AtomicUsize::new(0));
-        if file_attempts.load(Ordering::Relaxed) >= MAX_FIXES_PER_FILE {
+        if file_attempts.load(Ordering::Relaxed) >= self.settings.max_fixes_per_file {
             warn!(
                 "Skipping more issues in file with too many attempts: {}",
                 path
@@ -160,10 +175,35 @@ impl Fixer {
     }
 
     fn try_fix(&self, path: &String, issues: &[Issue]) -> Result<Vec<Issue>> {
-        let client = Client::new(None, Some(self.auth_token.clone()));
         let content = self.staging_area.read(path.clone().into())?;
+
+        let suggestion_groups = match &self.openai {
+            Some(client) => self.request_local_fixes(client, path, &content, issues),
+            None => self.request_cloud_fixes(path, &content, issues)?,
+        };
+
+        let issues = issues
+            .iter()
+            .zip(suggestion_groups)
+            .map(|(issue, suggestions)| {
+                let mut issue = issue.clone();
+                issue.suggestions = suggestions;
+                issue
+            })
+            .collect_vec();
+
+        Ok(issues)
+    }
+
+    fn request_cloud_fixes(
+        &self,
+        path: &String,
+        content: &str,
+        issues: &[Issue],
+    ) -> Result<Vec<Vec<Suggestion>>> {
+        let client = Client::new(None, Some(self.auth_token.clone()));
         let request = client.post("/fixes/batch")?;
-        let response = API_THREAD_POOL.scope(|_| {
+        let response = self.thread_pool.install(|| {
             request.send_json(json!({
                 "issues": issues,
                 "files": [{ "path": path, "content": content }],
@@ -180,17 +220,21 @@ impl Fixer {
 
         let suggestion_groups: Vec<Vec<Suggestion>> = response.into_json()?;
         debug!("Suggestions: {:?}", suggestion_groups);
+        Ok(suggestion_groups)
+    }
 
-        let issues = issues
-            .iter()
-            .zip(suggestion_groups)
-            .map(|(issue, suggestions)| {
-                let mut issue = issue.clone();
-                issue.suggestions = suggestions;
-                issue
+    fn request_local_fixes(
+        &self,
+        client: &OpenAiClient,
+        path: &str,
+        content: &str,
+        issues: &[Issue],
+    ) -> Vec<Vec<Suggestion>> {
+        self.thread_pool
+            .install(|| client.suggest(path, content, issues, self.r#unsafe))
+            .unwrap_or_else(|error| {
+                warn!("Failed to generate AI fix: {:?}", error);
+                vec![vec![]; issues.len()]
             })
-            .collect_vec();
-
-        Ok(issues)
     }
 }
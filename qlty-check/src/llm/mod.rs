@@ -1,3 +1,4 @@
 mod fixer;
+mod openai;
 
 pub use fixer::Fixer;
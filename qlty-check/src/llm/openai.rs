@@ -0,0 +1,327 @@
+use anyhow::{Context as _, Result};
+use indoc::indoc;
+use itertools::Itertools;
+use qlty_config::config::Ai;
+use qlty_types::analysis::v1::{Issue, Suggestion, SuggestionSource};
+use serde_json::Value;
+use similar::{DiffTag, TextDiff};
+use std::ops::Range;
+use std::time::Duration;
+use tracing::debug;
+use ureq::json;
+
+const SYSTEM_PROMPT: &str = "You are an expert software engineer fixing issues reported by static analysis tools. Reply with the complete fixed file in a single fenced code block and nothing else.";
+
+const SAFE_INSTRUCTIONS: &str =
+    "Only make changes which are guaranteed to preserve the behavior of the code.";
+
+// Matches the context of the patches created by diffy, so that the patches
+// built from one response never touch each other's lines
+const PATCH_CONTEXT_LINES: usize = 3;
+
+const DEFAULT_PROMPT: &str = indoc! {"
+    Fix the following issues reported in ${path}:
+
+    ${issues}
+
+    This is the complete contents of ${path}:
+
+    ```
+    ${source}
+    ```
+"};
+
+/// Generates fixes with an OpenAI-compatible chat completions endpoint, such
+/// as one served locally by llama.cpp or vLLM.
+#[derive(Debug, Clone)]
+pub struct OpenAiClient {
+    url: String,
+    model: String,
+    api_key: Option<String>,
+    prompt: String,
+    agent: ureq::Agent,
+}
+
+impl OpenAiClient {
+    pub fn new(config: &Ai) -> Result<Self> {
+        let url = config
+            .url
+            .as_ref()
+            .context("The openai AI backend requires an ai url in qlty.toml")?;
+        let model = config
+            .model
+            .as_ref()
+            .context("The openai AI backend requires an ai model in qlty.toml")?;
+
+        Ok(Self {
+            url: url.trim_end_matches('/').to_string(),
+            model: model.clone(),
+            api_key: std::env::var(&config.api_key_env).ok(),
+            prompt: config
+                .prompt
+                .clone()
+                .unwrap_or_else(|| DEFAULT_PROMPT.to_string()),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(config.timeout))
+                .build(),
+        })
+    }
+
+    /// Asks for a single fixed version of the file covering all of the issues,
+    /// and returns the suggestions for each issue in the same order.
+    pub fn suggest(
+        &self,
+        path: &str,
+        source: &str,
+        issues: &[Issue],
+        allow_unsafe: bool,
+    ) -> Result<Vec<Vec<Suggestion>>> {
+        let mut prompt = self.render_prompt(path, source, issues);
+
+        if !allow_unsafe {
+            prompt.push('\n');
+            prompt.push_str(SAFE_INSTRUCTIONS);
+        }
+
+        let mut request = self.agent.post(&format!("{}/chat/completions", self.url));
+
+        if let Some(api_key) = &self.api_key {
+            request = request.set("Authorization", &format!("Bearer {}", api_key));
+        }
+
+        let response: Value = request
+            .send_json(json!({
+                "model": self.model,
+                "temperature": 0,
+                "messages": [
+                    { "role": "system", "content": SYSTEM_PROMPT },
+                    { "role": "user", "content": prompt },
+                ],
+            }))?
+            .into_json()?;
+        debug!("Response [chat/completions]: {:?}", &response);
+
+        let content = response["choices"][0]["message"]["content"]
+            .as_str()
+            .context("Chat completion response is missing the message content")?;
+
+        Ok(build_suggestions(source, content, issues))
+    }
+
+    fn render_prompt(&self, path: &str, source: &str, issues: &[Issue]) -> String {
+        let issues = issues
+            .iter()
+            .map(|issue| {
+                format!(
+                    "- Line {}: {} ({} {})",
+                    issue.range().unwrap_or_default().start_line,
+                    issue.message,
+                    issue.tool,
+                    issue.rule_key
+                )
+            })
+            .join("\n");
+
+        self.prompt
+            .replace("${issues}", &issues)
+            .replace("${path}", path)
+            .replace("${source}", source)
+    }
+}
+
+/// Splits the changes made by the model among the issues, giving each issue a
+/// patch with only the changes closest to it. Changes are kept far enough
+/// apart that the patches of one file apply one after another.
+///
+/// The model rewrites the whole file without any checks, so its suggestions
+/// are always unsafe.
+fn build_suggestions(source: &str, content: &str, issues: &[Issue]) -> Vec<Vec<Suggestion>> {
+    let mut fixed = extract_code_block(content);
+
+    // Models tend to drop the trailing newline of the file
+    if source.ends_with('\n') && !fixed.ends_with('\n') {
+        fixed.push('\n');
+    }
+
+    if fixed.trim().is_empty() || issues.is_empty() {
+        return vec![vec![]; issues.len()];
+    }
+
+    let mut changes_by_issue: Vec<Vec<(Range<usize>, Range<usize>)>> = vec![vec![]; issues.len()];
+
+    let diff = TextDiff::from_lines(source, &fixed);
+    let old_lines = diff.old_slices();
+    let new_lines = diff.new_slices();
+
+    for group in diff.grouped_ops(PATCH_CONTEXT_LINES) {
+        let changed = group
+            .iter()
+            .filter(|op| op.tag() != DiffTag::Equal)
+            .collect_vec();
+
+        if let (Some(first), Some(last)) = (changed.first(), changed.last()) {
+            let old = first.old_range().start..last.old_range().end;
+            let new = first.new_range().start..last.new_range().end;
+            changes_by_issue[closest_issue(issues, &old)].push((old, new));
+        }
+    }
+
+    changes_by_issue
+        .into_iter()
+        .map(|changes| {
+            if changes.is_empty() {
+                return vec![];
+            }
+
+            let mut partial = String::new();
+            let mut position = 0;
+
+            for (old, new) in changes {
+                partial.push_str(&old_lines[position..old.start].concat());
+                partial.push_str(&new_lines[new].concat());
+                position = old.end;
+            }
+
+            partial.push_str(&old_lines[position..].concat());
+
+            vec![Suggestion {
+                description: "AI generated fix".to_string(),
+                patch: diffy::create_patch(source, &partial).to_string(),
+                source: SuggestionSource::Llm.into(),
+                r#unsafe: true,
+                ..Default::default()
+            }]
+        })
+        .collect()
+}
+
+/// Returns the index of the issue overlapping, or else nearest to, the
+/// zero-based range of changed lines.
+fn closest_issue(issues: &[Issue], lines: &Range<usize>) -> usize {
+    let first = lines.start as u32 + 1;
+    let last = (lines.end as u32).max(first);
+
+    issues
+        .iter()
+        .position_min_by_key(|issue| match issue.range() {
+            Some(range) => {
+                let start = range.start_line;
+                let end = range.end_line.max(start);
+                start.saturating_sub(last).max(first.saturating_sub(end))
+            }
+            None => u32::MAX,
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the contents of the first fenced code block in the response, or
+/// the whole response if there is none.
+fn extract_code_block(content: &str) -> String {
+    let mut lines = content.lines().skip_while(|line| !line.starts_with("```"));
+
+    if lines.next().is_none() {
+        return content.to_string();
+    }
+
+    let mut code = lines
+        .take_while(|line| !line.starts_with("```"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    code.push('\n');
+    code
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_extract_code_block() {
+        assert_eq!(
+            extract_code_block("Here you go:\n```rust\nfn main() {}\n```\nDone."),
+            "fn main() {}\n"
+        );
+        assert_eq!(extract_code_block("fn main() {}\n"), "fn main() {}\n");
+    }
+
+    fn issue(line: u32) -> Issue {
+        Issue {
+            tool: "eslint".to_string(),
+            rule_key: "no-var".to_string(),
+            message: "Unexpected var".to_string(),
+            location: Some(qlty_types::analysis::v1::Location {
+                path: "index.js".to_string(),
+                range: Some(qlty_types::analysis::v1::Range {
+                    start_line: line,
+                    end_line: line,
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn apply(source: &str, suggestion: &Suggestion) -> String {
+        diffy::apply(source, &diffy::Patch::from_str(&suggestion.patch).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_build_suggestions() {
+        let source = "var a = 1;\n1\n2\n3\n4\n5\n6\n7\n8\n9\nvar b = 2;\n";
+        let fixed = "let a = 1;\n1\n2\n3\n4\n5\n6\n7\n8\n9\nlet b = 2;\nb++;\n";
+        let issues = [issue(11), issue(1), issue(5)];
+
+        let suggestions = build_suggestions(source, &format!("```js\n{}```", fixed), &issues);
+
+        assert_eq!(suggestions.len(), 3);
+        assert!(suggestions[2].is_empty());
+        assert_eq!(
+            apply(source, &suggestions[0][0]),
+            "var a = 1;\n1\n2\n3\n4\n5\n6\n7\n8\n9\nlet b = 2;\nb++;\n"
+        );
+        assert_eq!(
+            apply(source, &suggestions[1][0]),
+            "let a = 1;\n1\n2\n3\n4\n5\n6\n7\n8\n9\nvar b = 2;\n"
+        );
+
+        // Patches of one response apply one after another, like the Patcher does
+        let patched = apply(&apply(source, &suggestions[0][0]), &suggestions[1][0]);
+        assert_eq!(patched, fixed);
+
+        for suggestion in suggestions.iter().flatten() {
+            assert_eq!(suggestion.source(), SuggestionSource::Llm);
+            assert!(suggestion.r#unsafe);
+        }
+    }
+
+    #[test]
+    fn test_build_suggestions_without_changes() {
+        let source = "var a = 1;\n";
+
+        assert_eq!(
+            build_suggestions(source, "```\nvar a = 1;\n```", &[issue(1)]),
+            vec![vec![]]
+        );
+        assert_eq!(
+            build_suggestions(source, "", &[issue(1), issue(1)]),
+            vec![vec![], vec![]]
+        );
+    }
+
+    #[test]
+    fn test_render_prompt() {
+        let client = OpenAiClient::new(&Ai {
+            url: Some("http://localhost:8080/v1/".to_string()),
+            model: Some("qwen2.5-coder".to_string()),
+            prompt: Some("${path}\n${issues}\n${source}".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(client.url, "http://localhost:8080/v1");
+        assert_eq!(
+            client.render_prompt("index.js", "var a = 1;", &[issue(1), issue(3)]),
+            "index.js\n- Line 1: Unexpected var (eslint no-var)\n- Line 3: Unexpected var (eslint no-var)\nvar a = 1;"
+        );
+    }
+}
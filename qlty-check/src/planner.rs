@@ -1,6 +1,7 @@
 use self::level_filter::LevelFilter;
 use self::plugin::PluginPlanner;
 use self::plugin_mode_transformer::PluginModeTransformer;
+use crate::baseline::{Baseline, BaselineFilter};
 use crate::cache::{IssueCache, IssuesCacheHit};
 use crate::executor::staging_area::{Mode, StagingArea};
 use crate::issue_muter::IssueMuter;
@@ -8,17 +9,17 @@ use crate::patch_builder::PatchBuilder;
 use crate::planner::config_files::plugin_configs;
 use crate::planner::config_files::PluginConfigFile;
 use crate::Settings;
-use anyhow::{bail, Error, Result};
+use anyhow::{bail, Context as _, Error, Result};
 use check_filters::CheckFilters;
 use console::style;
 use document_url_generator::DocumentUrlGenerator;
 use itertools::Itertools;
 use plugin_tab_column_width_transformer::PluginTabColumnWidthTransformer;
-use qlty_analysis::cache::{Cache, FilesystemCache, NullCache};
+use qlty_analysis::cache::{Cache, FilesystemCache, HttpCache, NullCache, TieredCache};
 use qlty_analysis::git::{compute_upstream, DiffLineFilter};
 use qlty_analysis::workspace_entries::TargetMode;
 use qlty_config::config::issue_transformer::IssueTransformer;
-use qlty_config::config::{DriverType, Match, PluginDef, Set, Triage};
+use qlty_config::config::{CacheBackend, DriverType, Match, PluginDef, Set, Triage};
 use qlty_config::{warn_once, QltyConfig, Workspace};
 use qlty_types::analysis::v1::ExecutionVerb;
 use qlty_types::{category_from_str, level_from_str};
@@ -76,14 +77,15 @@ pub struct Planner {
 impl Planner {
     pub fn new(verb: ExecutionVerb, settings: &Settings) -> Result<Self> {
         let workspace = Workspace::for_root(&settings.root)?;
-        let cache = Self::build_cache(&workspace, settings)?;
+        let config = workspace.config()?;
+        let cache = Self::build_cache(&workspace, &config, settings)?;
         let issue_cache = IssueCache::new(cache.clone());
 
         Ok(Self {
             verb,
             settings: settings.clone(),
             workspace: workspace.clone(),
-            config: workspace.config()?,
+            config,
             staging_area: StagingArea::generate(Mode::Source, workspace.root.clone(), None),
             issue_cache,
             target_mode: None,
@@ -106,7 +108,7 @@ impl Planner {
         self.compute_enabled_plugins()?;
         self.compute_staging_area()?;
         self.compute_invocations()?;
-        self.compute_transformers();
+        self.compute_transformers()?;
         let plan = self.build_plan();
         info!(
             "Planned {} invocations ({} cache hits) in {:.2}s",
@@ -231,7 +233,7 @@ impl Planner {
         Ok(())
     }
 
-    fn compute_transformers(&mut self) {
+    fn compute_transformers(&mut self) -> Result<()> {
         if let Ok(diff_line_filter) = self
             .workspace_entry_finder_builder
             .as_mut()
@@ -288,11 +290,22 @@ impl Planner {
         self.transformers
             .push(Box::new(IssueMuter::new(self.staging_area.clone())));
 
+        if self.settings.baseline {
+            let baseline_path = self.workspace.library()?.baseline_path();
+
+            if let Some(baseline) = Baseline::read(&baseline_path)? {
+                self.transformers
+                    .push(Box::new(BaselineFilter::new(baseline_path, &baseline)));
+            }
+        }
+
         // keep triage last
         let triages = self.build_triages();
         for issue_triage in &triages {
             self.transformers.push(Box::new(issue_triage.clone()));
         }
+
+        Ok(())
     }
 
     fn build_triages(&self) -> Vec<Triage> {
@@ -378,18 +391,55 @@ impl Planner {
         }
     }
 
-    fn build_cache(workspace: &Workspace, settings: &Settings) -> Result<Box<dyn Cache>> {
-        if settings.cache {
-            let library = workspace.library()?;
-            library.create()?;
-
-            Ok(Box::new(FilesystemCache::new(
-                library.results_dir().join("issues"),
-                "protos",
-            )))
-        } else {
-            Ok(Box::new(NullCache::new()))
+    fn build_cache(
+        workspace: &Workspace,
+        config: &QltyConfig,
+        settings: &Settings,
+    ) -> Result<Box<dyn Cache>> {
+        if !settings.cache {
+            return Ok(Box::new(NullCache::new()));
         }
+
+        let library = workspace.library()?;
+        library.create()?;
+
+        let local = Box::new(FilesystemCache::new(
+            library.results_dir().join("issues"),
+            "protos",
+        ));
+
+        let remote: Box<dyn Cache> = match config.cache.backend {
+            CacheBackend::Local => return Ok(local),
+            CacheBackend::Http => {
+                let url = config
+                    .cache
+                    .url
+                    .as_ref()
+                    .context("The http cache backend requires a cache url in qlty.toml")?;
+
+                Box::new(HttpCache::new(
+                    url,
+                    "protos",
+                    std::env::var("QLTY_CACHE_TOKEN").ok(),
+                ))
+            }
+            CacheBackend::Directory => {
+                let directory = config.cache.directory.as_ref().context(
+                    "The directory cache backend requires a cache directory in qlty.toml",
+                )?;
+
+                Box::new(FilesystemCache::new(
+                    workspace.root.join(directory).join("issues"),
+                    "protos",
+                ))
+            }
+        };
+
+        Ok(Box::new(TieredCache::new(
+            local,
+            remote,
+            config.cache.read_only,
+        )))
     }
 
     fn jobs(&self) -> usize {
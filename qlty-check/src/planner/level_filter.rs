@@ -15,6 +15,10 @@ impl IssueTransformer for LevelFilter {
         }
     }
 
+    fn hides_issues(&self) -> bool {
+        true
+    }
+
     fn clone_box(&self) -> Box<dyn IssueTransformer> {
         Box::new(self.clone())
     }
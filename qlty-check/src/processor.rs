@@ -61,6 +61,32 @@ impl Processor {
         })
     }
 
+    /// Returns the issues found, including those hidden from the output by
+    /// transformers such as the level or baseline filters.
+    pub fn unfiltered_issues(&self) -> Vec<Issue> {
+        for transformer in self.plan.transformers.iter() {
+            transformer.initialize();
+        }
+
+        let mut issues = self
+            .results
+            .issues
+            .iter()
+            .filter_map(|issue| {
+                self.plan
+                    .transformers
+                    .iter()
+                    .filter(|transformer| !transformer.hides_issues())
+                    .try_fold(issue.clone(), |issue, transformer| {
+                        transformer.transform(issue)
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        issues.sort();
+        issues
+    }
+
     fn compute_counts(&mut self) {
         self.counts.total_issues = self.issues.len();
 
@@ -0,0 +1,500 @@
+use crate::executor::InvocationResult;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    collections::{BTreeMap, HashSet},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tracing::{debug, warn};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_STORED_PROFILES: usize = 10;
+
+/// Resources used by the process tree of an invocation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    pub cpu_secs: f64,
+    pub peak_rss_bytes: u64,
+}
+
+/// Samples the CPU and memory usage of a process tree on a background thread
+/// until it is finished. CPU time is estimated from the sampled usage, so very
+/// short-lived processes may be under-counted.
+#[derive(Debug)]
+pub struct ResourceSampler {
+    running: Arc<AtomicBool>,
+    handle: JoinHandle<ResourceUsage>,
+}
+
+impl ResourceSampler {
+    pub fn start(pids: Vec<u32>) -> Self {
+        let running = Arc::new(AtomicBool::new(true));
+        let running_clone = Arc::clone(&running);
+
+        let handle = thread::spawn(move || {
+            let mut system = System::new();
+            let mut usage = ResourceUsage::default();
+            let mut last_sample = Instant::now();
+
+            while running_clone.load(Ordering::SeqCst) {
+                thread::sleep(SAMPLE_INTERVAL);
+
+                system.refresh_processes_specifics(
+                    ProcessesToUpdate::All,
+                    ProcessRefreshKind::new().with_cpu().with_memory(),
+                );
+
+                let elapsed = last_sample.elapsed().as_secs_f64();
+                last_sample = Instant::now();
+
+                let tree = process_tree(&system, &pids);
+                let (cpu_percent, rss_bytes) = tree
+                    .iter()
+                    .filter_map(|pid| system.process(*pid))
+                    .fold((0.0, 0), |(cpu, rss), process| {
+                        (cpu + process.cpu_usage() as f64, rss + process.memory())
+                    });
+
+                usage.cpu_secs += cpu_percent / 100.0 * elapsed;
+                usage.peak_rss_bytes = usage.peak_rss_bytes.max(rss_bytes);
+            }
+
+            usage
+        });
+
+        Self { running, handle }
+    }
+
+    pub fn finish(self) -> ResourceUsage {
+        self.running.store(false, Ordering::SeqCst);
+        self.handle.join().unwrap_or_default()
+    }
+}
+
+fn process_tree(system: &System, roots: &[u32]) -> HashSet<Pid> {
+    let mut tree = roots
+        .iter()
+        .map(|pid| Pid::from_u32(*pid))
+        .collect::<HashSet<_>>();
+
+    loop {
+        let children = system
+            .processes()
+            .iter()
+            .filter(|(pid, process)| {
+                !tree.contains(pid) && process.parent().is_some_and(|p| tree.contains(&p))
+            })
+            .map(|(pid, _)| *pid)
+            .collect::<Vec<_>>();
+
+        if children.is_empty() {
+            return tree;
+        }
+
+        tree.extend(children);
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InvocationProfile {
+    pub id: String,
+    pub label: String,
+    pub plugin_name: String,
+    pub driver_name: String,
+    /// Seconds between the start of the first invocation and this one
+    pub start_secs: f64,
+    pub wall_secs: f64,
+    pub cpu_secs: Option<f64>,
+    pub peak_rss_bytes: Option<u64>,
+    pub batch_size: usize,
+    pub exit_code: Option<i64>,
+    pub timed_out: bool,
+    pub targets: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlowFile {
+    pub path: String,
+    pub timeouts: usize,
+    pub profiles: usize,
+}
+
+/// Timing and resource usage of every invocation in a run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub recorded_at: i64,
+    pub invocations: Vec<InvocationProfile>,
+}
+
+impl Profile {
+    pub fn new(results: &[InvocationResult]) -> Self {
+        let started_at = |result: &InvocationResult| {
+            result
+                .invocation
+                .started_at
+                .as_ref()
+                .map(|t| t.seconds as f64 + t.nanos as f64 / 1e9)
+                .unwrap_or_default()
+        };
+
+        let first_start = results
+            .iter()
+            .map(started_at)
+            .reduce(f64::min)
+            .unwrap_or_default();
+
+        let mut invocations = results
+            .iter()
+            .map(|result| InvocationProfile {
+                id: result.invocation.id.clone(),
+                label: result.plan.invocation_label(),
+                plugin_name: result.plan.plugin_name.clone(),
+                driver_name: result.plan.driver_name.clone(),
+                start_secs: started_at(result) - first_start,
+                wall_secs: result.invocation.duration_secs as f64,
+                cpu_secs: result.resources.map(|usage| usage.cpu_secs),
+                peak_rss_bytes: result.resources.map(|usage| usage.peak_rss_bytes),
+                batch_size: result.plan.targets.len(),
+                exit_code: result.invocation.exit_code,
+                timed_out: result.timed_out,
+                targets: result
+                    .plan
+                    .targets
+                    .iter()
+                    .map(|target| target.path_string())
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+
+        invocations.sort_by(|a, b| a.start_secs.total_cmp(&b.start_secs));
+
+        Self {
+            recorded_at: chrono::Utc::now().timestamp(),
+            invocations,
+        }
+    }
+
+    /// Returns the invocations ordered from slowest to fastest.
+    pub fn ranked(&self) -> Vec<&InvocationProfile> {
+        let mut ranked = self.invocations.iter().collect::<Vec<_>>();
+        ranked.sort_by(|a, b| b.wall_secs.total_cmp(&a.wall_secs));
+        ranked
+    }
+
+    /// Returns the total wall time spent in each plugin, slowest first.
+    pub fn totals_by_plugin(&self) -> Vec<(String, f64)> {
+        let mut totals: BTreeMap<String, f64> = BTreeMap::new();
+
+        for invocation in &self.invocations {
+            *totals.entry(invocation.plugin_name.clone()).or_default() += invocation.wall_secs;
+        }
+
+        let mut totals = totals.into_iter().collect::<Vec<_>>();
+        totals.sort_by(|a, b| b.1.total_cmp(&a.1));
+        totals
+    }
+
+    /// Writes the invocations in the Chrome trace event format, which can be
+    /// loaded in chrome://tracing or Perfetto. Invocations are spread across
+    /// lanes so that concurrent ones do not overlap.
+    pub fn write_chrome_trace(&self, writer: &mut dyn Write) -> Result<()> {
+        let mut lanes: Vec<f64> = vec![];
+
+        let events = self
+            .invocations
+            .iter()
+            .map(|invocation| {
+                let end = invocation.start_secs + invocation.wall_secs;
+                let lane = match lanes
+                    .iter()
+                    .position(|lane_end| *lane_end <= invocation.start_secs)
+                {
+                    Some(lane) => {
+                        lanes[lane] = end;
+                        lane
+                    }
+                    None => {
+                        lanes.push(end);
+                        lanes.len() - 1
+                    }
+                };
+
+                json!({
+                    "name": invocation.label,
+                    "cat": invocation.plugin_name,
+                    "ph": "X",
+                    "ts": (invocation.start_secs * 1e6).round() as u64,
+                    "dur": (invocation.wall_secs * 1e6).round() as u64,
+                    "pid": 1,
+                    "tid": lane + 1,
+                    "args": {
+                        "id": invocation.id,
+                        "batch_size": invocation.batch_size,
+                        "exit_code": invocation.exit_code,
+                        "timed_out": invocation.timed_out,
+                        "cpu_secs": invocation.cpu_secs,
+                        "peak_rss_bytes": invocation.peak_rss_bytes,
+                    },
+                })
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::to_writer_pretty(&mut *writer, &json!({ "traceEvents": events }))?;
+        writer.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+/// Keeps the profiles of recent runs so that files which repeatedly time out
+/// can be identified.
+#[derive(Debug, Clone)]
+pub struct ProfileStore {
+    dir: PathBuf,
+}
+
+impl ProfileStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Saves the profile, pruning all but the most recent profiles.
+    pub fn record(&self, profile: &Profile) -> Result<()> {
+        std::fs::create_dir_all(&self.dir).with_context(|| {
+            format!("Failed to create profiles directory {}", self.dir.display())
+        })?;
+
+        let path = self.dir.join(format!(
+            "{}-{}.json",
+            profile.recorded_at,
+            std::process::id()
+        ));
+        debug!("Recording profile to {}", path.display());
+        std::fs::write(&path, serde_json::to_string(profile)?)
+            .with_context(|| format!("Failed to write profile {}", path.display()))?;
+
+        let paths = self.paths()?;
+
+        for path in &paths[..paths.len().saturating_sub(MAX_STORED_PROFILES)] {
+            std::fs::remove_file(path).ok();
+        }
+
+        Ok(())
+    }
+
+    pub fn profiles(&self) -> Result<Vec<Profile>> {
+        let mut profiles = vec![];
+
+        for path in self.paths()? {
+            match Self::read(&path) {
+                Ok(profile) => profiles.push(profile),
+                Err(err) => warn!("Skipping profile {}: {}", path.display(), err),
+            }
+        }
+
+        Ok(profiles)
+    }
+
+    /// Returns the files which were part of a timed out invocation in more
+    /// than one of the recorded profiles, most frequent first.
+    pub fn repeated_timeouts(&self) -> Result<Vec<SlowFile>> {
+        Ok(repeated_timeouts(&self.profiles()?))
+    }
+
+    fn paths(&self) -> Result<Vec<PathBuf>> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut paths = vec![];
+
+        for file in std::fs::read_dir(&self.dir)? {
+            let path = file?.path();
+
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                paths.push(path);
+            }
+        }
+
+        // File names start with the timestamp, so this orders oldest first
+        paths.sort();
+        Ok(paths)
+    }
+
+    fn read(path: &Path) -> Result<Profile> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read profile {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse profile {}", path.display()))
+    }
+}
+
+fn repeated_timeouts(profiles: &[Profile]) -> Vec<SlowFile> {
+    let mut timeouts: BTreeMap<&str, usize> = BTreeMap::new();
+
+    for profile in profiles {
+        let paths = profile
+            .invocations
+            .iter()
+            .filter(|invocation| invocation.timed_out)
+            .flat_map(|invocation| invocation.targets.iter().map(String::as_str))
+            .collect::<HashSet<_>>();
+
+        for path in paths {
+            *timeouts.entry(path).or_default() += 1;
+        }
+    }
+
+    let mut slow_files = timeouts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(path, count)| SlowFile {
+            path: path.to_string(),
+            timeouts: count,
+            profiles: profiles.len(),
+        })
+        .collect::<Vec<_>>();
+
+    slow_files.sort_by_key(|file| std::cmp::Reverse(file.timeouts));
+    slow_files
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn invocation(label: &str, start_secs: f64, wall_secs: f64) -> InvocationProfile {
+        InvocationProfile {
+            id: label.to_string(),
+            label: label.to_string(),
+            plugin_name: label.split('/').next().unwrap().to_string(),
+            start_secs,
+            wall_secs,
+            batch_size: 1,
+            exit_code: Some(0),
+            targets: vec![format!("{}.rs", label.replace('/', "_"))],
+            ..Default::default()
+        }
+    }
+
+    fn timed_out(targets: &[&str]) -> InvocationProfile {
+        InvocationProfile {
+            label: "eslint/lint".to_string(),
+            plugin_name: "eslint".to_string(),
+            timed_out: true,
+            targets: targets.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ranked_and_totals() {
+        let profile = Profile {
+            recorded_at: 0,
+            invocations: vec![
+                invocation("eslint/lint", 0.0, 2.0),
+                invocation("ruff/lint", 0.5, 5.0),
+                invocation("eslint/lint", 2.0, 4.0),
+            ],
+        };
+
+        let ranked = profile
+            .ranked()
+            .iter()
+            .map(|invocation| invocation.wall_secs)
+            .collect::<Vec<_>>();
+        assert_eq!(ranked, vec![5.0, 4.0, 2.0]);
+
+        assert_eq!(
+            profile.totals_by_plugin(),
+            vec![("eslint".to_string(), 6.0), ("ruff".to_string(), 5.0)]
+        );
+    }
+
+    #[test]
+    fn test_chrome_trace() {
+        let profile = Profile {
+            recorded_at: 0,
+            invocations: vec![
+                invocation("eslint/lint", 0.0, 2.0),
+                invocation("ruff/lint", 0.5, 1.0),
+                invocation("eslint/lint", 2.0, 0.25),
+            ],
+        };
+
+        let mut output = vec![];
+        profile.write_chrome_trace(&mut output).unwrap();
+        let trace: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        let events = trace["traceEvents"].as_array().unwrap();
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0]["ph"], "X");
+        assert_eq!(events[0]["dur"], 2_000_000);
+        assert_eq!(events[1]["ts"], 500_000);
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| event["tid"].as_u64().unwrap())
+                .collect::<Vec<_>>(),
+            vec![1, 2, 1]
+        );
+    }
+
+    #[test]
+    fn test_repeated_timeouts() {
+        let profiles = vec![
+            Profile {
+                recorded_at: 1,
+                invocations: vec![timed_out(&["big.js", "small.js"])],
+            },
+            Profile {
+                recorded_at: 2,
+                invocations: vec![timed_out(&["big.js"]), timed_out(&["big.js", "other.js"])],
+            },
+            Profile {
+                recorded_at: 3,
+                invocations: vec![invocation("eslint/lint", 0.0, 1.0)],
+            },
+        ];
+
+        assert_eq!(
+            repeated_timeouts(&profiles),
+            vec![SlowFile {
+                path: "big.js".to_string(),
+                timeouts: 2,
+                profiles: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_store_prunes_old_profiles() {
+        let dir = tempdir().unwrap();
+        let store = ProfileStore::new(dir.path().join("profiles"));
+
+        for recorded_at in 0..(MAX_STORED_PROFILES as i64 + 2) {
+            store
+                .record(&Profile {
+                    recorded_at: 1_000 + recorded_at,
+                    invocations: vec![],
+                })
+                .unwrap();
+        }
+
+        let profiles = store.profiles().unwrap();
+        assert_eq!(profiles.len(), MAX_STORED_PROFILES);
+        assert_eq!(profiles[0].recorded_at, 1_002);
+    }
+}
@@ -27,6 +27,8 @@ pub struct Settings {
     pub emit_existing_issues: bool,
     pub auth_token: Option<String>,
     pub install_only: bool,
+    pub baseline: bool,
+    pub profile: bool,
 }
 
 impl Default for Settings {
@@ -55,6 +57,8 @@ impl Default for Settings {
             emit_existing_issues: false,
             auth_token: None,
             install_only: false,
+            baseline: true,
+            profile: false,
         }
     }
 }
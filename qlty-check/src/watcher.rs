@@ -0,0 +1,183 @@
+use crate::planner::PluginWorkspaceEntryFinderBuilder;
+use anyhow::Result;
+use qlty_analysis::workspace_entries::TargetMode;
+use qlty_config::{QltyConfig, Workspace};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+use tracing::debug;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+type Snapshot = HashMap<PathBuf, (SystemTime, u64)>;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Changes {
+    pub modified: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+}
+
+impl Changes {
+    pub fn is_empty(&self) -> bool {
+        self.modified.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Polls the workspace for modified files, honoring the same `exclude` and
+/// ignore rules used when planning invocations.
+#[derive(Debug, Clone)]
+pub struct Watcher {
+    root: PathBuf,
+    config: QltyConfig,
+    interval: Duration,
+    snapshot: Snapshot,
+}
+
+impl Watcher {
+    pub fn new(workspace: &Workspace) -> Result<Self> {
+        let mut watcher = Self {
+            root: workspace.root.clone(),
+            config: workspace.config()?,
+            interval: DEFAULT_POLL_INTERVAL,
+            snapshot: HashMap::new(),
+        };
+
+        watcher.snapshot = watcher.take_snapshot()?;
+        Ok(watcher)
+    }
+
+    /// Blocks until at least one file is modified or removed.
+    pub fn wait_for_changes(&mut self) -> Result<Changes> {
+        loop {
+            std::thread::sleep(self.interval);
+            let changes = self.poll()?;
+
+            if !changes.is_empty() {
+                debug!(
+                    "Detected {} modified and {} removed files",
+                    changes.modified.len(),
+                    changes.removed.len()
+                );
+                return Ok(changes);
+            }
+        }
+    }
+
+    /// Returns the changes since the last poll and records the current state.
+    pub fn poll(&mut self) -> Result<Changes> {
+        let snapshot = self.take_snapshot()?;
+        let changes = Self::diff(&self.snapshot, &snapshot);
+        self.snapshot = snapshot;
+        Ok(changes)
+    }
+
+    /// Records the current state of files which were written on purpose, such
+    /// as by formatters or fixes, so that they are not reported as changes.
+    /// Other files keep the state from the last poll, so edits made in the
+    /// meantime are still reported.
+    pub fn acknowledge(&mut self, paths: &[PathBuf]) -> Result<()> {
+        if paths.is_empty() {
+            return Ok(());
+        }
+
+        let snapshot = self.take_snapshot()?;
+
+        for path in paths {
+            match snapshot.get(path) {
+                Some(state) => self.snapshot.insert(path.clone(), *state),
+                None => self.snapshot.remove(path),
+            };
+        }
+
+        Ok(())
+    }
+
+    fn take_snapshot(&self) -> Result<Snapshot> {
+        let mut builder = PluginWorkspaceEntryFinderBuilder {
+            mode: TargetMode::All,
+            root: self.root.clone(),
+            file_types: self.config.file_types.clone(),
+            excludes: self.config.exclude.clone(),
+            exclude_patterns: self.config.exclude_patterns.clone(),
+            ..Default::default()
+        };
+        builder.compute()?;
+
+        let file_types = self.config.file_types.keys().cloned().collect::<Vec<_>>();
+        let mut finder = builder.build(&file_types, None)?;
+
+        Ok(finder
+            .workspace_entries("qlty")?
+            .into_iter()
+            .map(|entry| {
+                (
+                    entry.path.clone(),
+                    (entry.content_modified, entry.contents_size),
+                )
+            })
+            .collect())
+    }
+
+    fn diff(previous: &Snapshot, current: &Snapshot) -> Changes {
+        let mut modified = current
+            .iter()
+            .filter(|(path, state)| previous.get(*path) != Some(*state))
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        modified.sort();
+
+        let mut removed = previous
+            .keys()
+            .filter(|path| !current.contains_key(*path))
+            .cloned()
+            .collect::<Vec<_>>();
+        removed.sort();
+
+        Changes { modified, removed }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let earlier = SystemTime::UNIX_EPOCH;
+        let later = earlier + Duration::from_secs(10);
+
+        let previous = Snapshot::from([
+            (PathBuf::from("unchanged.rs"), (earlier, 10)),
+            (PathBuf::from("touched.rs"), (earlier, 10)),
+            (PathBuf::from("resized.rs"), (earlier, 10)),
+            (PathBuf::from("deleted.rs"), (earlier, 10)),
+        ]);
+
+        let current = Snapshot::from([
+            (PathBuf::from("unchanged.rs"), (earlier, 10)),
+            (PathBuf::from("touched.rs"), (later, 10)),
+            (PathBuf::from("resized.rs"), (earlier, 12)),
+            (PathBuf::from("added.rs"), (later, 1)),
+        ]);
+
+        assert_eq!(
+            Watcher::diff(&previous, &current),
+            Changes {
+                modified: vec![
+                    PathBuf::from("added.rs"),
+                    PathBuf::from("resized.rs"),
+                    PathBuf::from("touched.rs"),
+                ],
+                removed: vec![PathBuf::from("deleted.rs")],
+            }
+        );
+    }
+
+    #[test]
+    fn test_diff_without_changes() {
+        let snapshot = Snapshot::from([(PathBuf::from("a.rs"), (SystemTime::UNIX_EPOCH, 1))]);
+        assert!(Watcher::diff(&snapshot, &snapshot).is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::commands::*;
-use crate::commands::{auth, cache, config, plugins, sources};
+use crate::commands::{auth, cache, config, history, plugins, sources};
 use crate::{CommandError, CommandSuccess};
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -58,6 +58,9 @@ pub enum Commands {
     /// Open the docs website in the browser
     Docs(Docs),
 
+    /// Group duplicated code into clone classes and report duplication per directory
+    Duplication(Duplication),
+
     /// Auto-format files by rewriting them
     Fmt(Fmt),
 
@@ -65,12 +68,21 @@ pub enum Commands {
     #[command(hide = true)]
     Githooks(githooks::Arguments),
 
+    /// Show issue trends recorded by local builds
+    History(history::Arguments),
+
+    /// Rank files and functions by complexity, churn and coverage
+    Hotspots(Hotspots),
+
     /// Set up Qlty in the current repository
     Init(Init),
 
     /// Install linters and their dependencies
     Install(Install),
 
+    /// Run a Language Server Protocol server over stdio
+    Lsp(Lsp),
+
     /// Compute code quality metrics
     Metrics(Metrics),
 
@@ -129,10 +141,14 @@ impl Arguments {
             Commands::Deinit(command) => command.execute(self),
             Commands::Discord(command) => command.execute(self),
             Commands::Docs(command) => command.execute(self),
+            Commands::Duplication(command) => command.execute(self),
             Commands::Fmt(command) => command.execute(self),
             Commands::Githooks(command) => command.execute(self),
+            Commands::History(command) => command.execute(self),
+            Commands::Hotspots(command) => command.execute(self),
             Commands::Install(command) => command.execute(self),
             Commands::Init(command) => command.execute(self),
+            Commands::Lsp(command) => command.execute(self),
             Commands::Metrics(command) => command.execute(self),
             Commands::Panic(command) => command.execute(self),
             Commands::Parse(command) => command.execute(self),
@@ -9,10 +9,14 @@ mod dashboard;
 mod deinit;
 mod discord;
 mod docs;
+mod duplication;
 mod fmt;
 pub mod githooks;
+pub mod history;
+mod hotspots;
 mod init;
 mod install;
+mod lsp;
 mod metrics;
 mod panic;
 mod parse;
@@ -27,7 +31,8 @@ mod version;
 
 pub use {
     build::Build, check::Check, completions::Completions, dashboard::Dashboard, deinit::Deinit,
-    discord::Discord, docs::Docs, fmt::Fmt, init::Init, install::Install, metrics::Metrics,
-    panic::Panic, parse::Parse, patch::Patch, smells::Smells, telemetry::Telemetry,
-    upgrade::Upgrade, validate::Validate, version::Version,
+    discord::Discord, docs::Docs, duplication::Duplication, fmt::Fmt, hotspots::Hotspots,
+    init::Init, install::Install, lsp::Lsp, metrics::Metrics, panic::Panic, parse::Parse,
+    patch::Patch, smells::Smells, telemetry::Telemetry, upgrade::Upgrade, validate::Validate,
+    version::Version,
 };
@@ -10,10 +10,15 @@ use qlty_analysis::{
     workspace_entries::{TargetMode, WorkspaceEntryFinderBuilder},
     Report,
 };
+use qlty_check::history::{History, HistorySource};
 use qlty_config::{QltyConfig, Workspace};
 use qlty_types::analysis::v1::{AnalysisResult, ExecutionVerb, Metadata};
 use rayon::prelude::*;
-use std::{env, path::PathBuf, sync::Arc};
+use std::{
+    env,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use time::OffsetDateTime;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
@@ -99,7 +104,7 @@ impl Build {
                     "Checking structure of {} files... ",
                     workspace_entry_files.len()
                 );
-                self.run_structure(&config, &workspace_entry_files)
+                self.run_structure(&config, &workspace.root, &workspace_entry_files)
             }
             ReportFunctions::Duplication => {
                 info!("Looking for duplication across all files... ");
@@ -139,6 +144,10 @@ impl Build {
         debug!("Finishing analysis...");
         report.finish();
 
+        if self.upstream.is_none() {
+            self.record_history(&workspace, &report);
+        }
+
         info!("Reporting {} issues...", report.issues.len());
         info!("Reporting {} stats...", report.stats.len());
 
@@ -184,8 +193,24 @@ impl Build {
         }
     }
 
-    fn run_structure(&self, config: &QltyConfig, files: &[Arc<File>]) -> Result<Report> {
-        let planner = qlty_smells::structure::Planner::new(config, files.to_vec())?;
+    fn record_history(&self, workspace: &Workspace, report: &Report) {
+        let result = workspace.library().and_then(|library| {
+            History::for_source(&library.history_dir(), HistorySource::Build)
+                .record_head(&workspace.repo()?, &report.issues)
+        });
+
+        if let Err(err) = result {
+            warn!("Failed to record issue history: {}", err);
+        }
+    }
+
+    fn run_structure(
+        &self,
+        config: &QltyConfig,
+        root: &Path,
+        files: &[Arc<File>],
+    ) -> Result<Report> {
+        let planner = qlty_smells::structure::Planner::new(config, root, files.to_vec())?;
         let plan = planner.compute()?;
         let mut executor = qlty_smells::structure::Executor::new(&plan);
         executor.execute();
@@ -278,6 +303,7 @@ impl Build {
             r#unsafe: self.ai, // When AI is enabled, we also enable unsafe fixes
             skip_errored_plugins: self.skip_errored_plugins,
             emit_existing_issues: true,
+            baseline: false,
             ..Default::default()
         };
 
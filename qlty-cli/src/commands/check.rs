@@ -1,20 +1,31 @@
-use crate::format::SarifFormatter;
+use crate::format::OutputFormat;
+use crate::ui::print_profile;
+use crate::ui::review_fixes;
 use crate::ui::ApplyMode;
 use crate::ui::ErrorsFormatter;
 use crate::ui::Steps;
 use crate::ui::TextFormatter;
+use crate::watch::WatchSession;
 use crate::{Arguments, CommandError, CommandSuccess, Trigger};
 use anyhow::bail;
+use anyhow::Context as _;
 use anyhow::Result;
 use clap::Args;
 use console::{style, Emoji};
+use qlty_check::baseline::Baseline;
+use qlty_check::history::{History, HistorySource};
 use qlty_check::planner::Plan;
+use qlty_check::profile::{Profile, ProfileStore};
+use qlty_check::results::FixedResult;
 use qlty_check::{planner::Planner, CheckFilter, Executor, Processor, Report, Settings};
+use qlty_config::config::AiBackend;
 use qlty_config::Workspace;
 use qlty_formats::{Formatter, JsonFormatter};
 use qlty_types::analysis::v1::ExecutionVerb;
 use qlty_types::analysis::v1::Level;
+use std::collections::HashSet;
 use std::io::BufRead as _;
+use std::io::IsTerminal as _;
 use std::io::{self, Read};
 use std::path::PathBuf;
 use std::thread;
@@ -37,15 +48,31 @@ enum GitHookStdin {
     Content(String),
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FixMode {
+    /// Apply all auto-fix suggestions
+    All,
+    /// Review each auto-fix suggestion before applying it
+    Interactive,
+}
+
 #[derive(Args, Clone, Debug)]
 pub struct Check {
     /// Check all files, not just changed
     #[arg(short, long, conflicts_with = "upstream")]
     pub all: bool,
 
-    /// Apply all auto-fix suggestions
-    #[arg(long, conflicts_with = "no_fix")]
-    pub fix: bool,
+    /// Apply all auto-fix suggestions, or review them one at a time with `--fix=interactive`
+    #[arg(
+        long,
+        value_enum,
+        value_name = "MODE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "all",
+        conflicts_with = "no_fix"
+    )]
+    pub fix: Option<FixMode>,
 
     /// Do not apply auto-fix suggestions
     #[arg(long, conflicts_with = "fix")]
@@ -119,13 +146,17 @@ pub struct Check {
     fail_level: Level,
 
     /// JSON output
-    #[arg(long, hide = true, conflicts_with = "sarif")]
+    #[arg(long, hide = true, conflicts_with_all = ["sarif", "format"])]
     json: bool,
 
     /// SARIF output
-    #[arg(long, conflicts_with = "json")]
+    #[arg(long, conflicts_with_all = ["json", "format"])]
     sarif: bool,
 
+    /// Output format (sarif, junit, checkstyle or codeclimate)
+    #[arg(long, value_enum, conflicts_with_all = ["json", "sarif"])]
+    format: Option<OutputFormat>,
+
     /// Allow individual plugins to be skipped if they fail or crash
     #[arg(hide = true, long, conflicts_with = "fail_level")]
     skip_errored_plugins: bool,
@@ -143,6 +174,39 @@ pub struct Check {
     /// Skip fetching sources before running checks
     #[arg(long)]
     skip_source_fetch: bool,
+
+    /// Show issues recorded in the baseline file
+    #[arg(long, conflicts_with_all = ["write_baseline", "update_baseline"])]
+    no_baseline: bool,
+
+    /// Record all current issues in the baseline file
+    #[arg(
+        long,
+        conflicts_with_all = ["update_baseline", "fix", "upstream", "sample", "filter", "level", "skip_errored_plugins"]
+    )]
+    write_baseline: bool,
+
+    /// Remove fixed issues from the baseline file
+    #[arg(
+        long,
+        conflicts_with_all = ["fix", "upstream", "sample", "filter", "level", "skip_errored_plugins"]
+    )]
+    update_baseline: bool,
+
+    /// Re-run checks on files as they change
+    #[arg(
+        long,
+        conflicts_with_all = ["json", "sarif", "format", "install_only", "write_baseline", "update_baseline"]
+    )]
+    watch: bool,
+
+    /// Print the time and resources used by each plugin invocation
+    #[arg(long)]
+    profile: bool,
+
+    /// Write a Chrome trace of the plugin invocations to a file (implies --profile)
+    #[arg(long, value_name = "FILE")]
+    profile_trace: Option<PathBuf>,
 }
 
 impl Check {
@@ -166,8 +230,20 @@ impl Check {
 
         let settings = self.build_settings(&workspace, git_hook_stdin)?;
 
+        if self.watch {
+            WatchSession::new(
+                ExecutionVerb::Check,
+                &workspace,
+                &settings,
+                self.apply_mode(),
+            )
+            .run()?;
+            return CommandSuccess::ok();
+        }
+
         let mut counter = 0;
         let mut dirty = true;
+        let mut reviewed = false;
 
         while dirty {
             if counter > 0 {
@@ -201,12 +277,40 @@ impl Check {
             let mut processor = Processor::new(&plan, results);
             let report = processor.compute()?;
 
+            if self.write_baseline || self.update_baseline {
+                return self.save_baseline(&workspace, &report);
+            }
+
+            if counter == 1 {
+                self.record_history(&workspace, &settings, &processor);
+            }
+
+            if settings.profile {
+                self.write_profile(&workspace, &report)?;
+            }
+
             if !report.fixed.is_empty() {
                 if self.verbose >= 1 {
                     steps.start(FORMATTING, "Formatting...");
                 }
 
-                self.format_after_fix(&settings, &report)?;
+                self.format_after_fix(&settings, &report.fixed)?;
+            }
+
+            if self.fix == Some(FixMode::Interactive) && !reviewed {
+                reviewed = true;
+
+                let fixed = review_fixes(
+                    &mut std::io::stderr(),
+                    &report.issues,
+                    &plan.staging_area,
+                    settings.r#unsafe,
+                )?;
+
+                if !fixed.is_empty() {
+                    self.format_after_fix(&settings, &fixed)?;
+                    continue;
+                }
             }
 
             dirty = self.write_stdout(&report, &plan, &settings)?;
@@ -253,12 +357,15 @@ impl Check {
         });
     }
 
-    fn format_after_fix(&self, settings: &Settings, report: &Report) -> Result<Report> {
-        debug!("Format after fix: {:?}", report.fixed);
+    fn format_after_fix(
+        &self,
+        settings: &Settings,
+        fixed: &HashSet<FixedResult>,
+    ) -> Result<Report> {
+        debug!("Format after fix: {:?}", fixed);
         let mut settings = settings.clone();
         settings.filters = vec![];
-        settings.paths = report
-            .fixed
+        settings.paths = fixed
             .iter()
             .map(|f| settings.root.join(f.location.path.clone()))
             .collect();
@@ -270,6 +377,15 @@ impl Check {
     }
 
     fn validate_options(&self) -> Result<(), CommandError> {
+        if self.fix == Some(FixMode::Interactive) && !io::stdin().is_terminal() {
+            let message = format!(
+                "the argument '{}' requires an interactive terminal",
+                style("--fix=interactive").yellow()
+            );
+
+            return Err(CommandError::InvalidOptions { message });
+        }
+
         if self.all && !self.paths.is_empty() {
             let message = format!(
                 "the argument '{}' cannot be used with specified {}",
@@ -300,6 +416,15 @@ impl Check {
             return Err(CommandError::InvalidOptions { message });
         }
 
+        if (self.write_baseline || self.update_baseline) && !self.paths.is_empty() {
+            let message = format!(
+                "the baseline can only be written for all files, not specified {}",
+                style("[PATHS]").yellow()
+            );
+
+            return Err(CommandError::InvalidOptions { message });
+        }
+
         for path in &self.paths {
             if !path.exists() {
                 let message = format!("path '{}' does not exist", path.display());
@@ -319,8 +444,8 @@ impl Check {
         settings.root = Workspace::assert_within_git_directory()?;
         settings.verbose = self.verbose as usize;
         settings.sample = self.sample;
-        settings.all = self.all;
-        settings.fix = self.fix;
+        settings.all = self.all || self.write_baseline || self.update_baseline;
+        settings.fix = self.fix == Some(FixMode::All);
         settings.ai = self.ai;
         settings.r#unsafe = self.r#unsafe;
         settings.jobs = self.jobs;
@@ -338,9 +463,12 @@ impl Check {
         settings.paths = self.paths.clone();
         settings.trigger = self.trigger.into();
         settings.skip_errored_plugins = self.skip_errored_plugins;
+        settings.baseline = !(self.no_baseline || self.write_baseline || self.update_baseline);
+        settings.profile = self.profile || self.profile_trace.is_some();
 
-        // Get auth token if AI is enabled
-        if settings.ai {
+        // Get auth token if AI fixes are generated by Qlty Cloud, never when
+        // they are generated by a self-hosted model
+        if settings.ai && workspace.config()?.ai.backend == AiBackend::Qlty {
             settings.auth_token = match crate::auth::load_or_retrieve_auth_token() {
                 Ok(token) => Some(token),
                 Err(err) => {
@@ -396,6 +524,85 @@ impl Check {
         }
     }
 
+    fn save_baseline(
+        &self,
+        workspace: &Workspace,
+        report: &Report,
+    ) -> Result<CommandSuccess, CommandError> {
+        let path = workspace.library()?.baseline_path();
+
+        // Issues from a plugin that failed are missing from the report, so
+        // saving it would drop their baseline entries as if they were fixed
+        if report.has_errors() {
+            let flag = if self.update_baseline {
+                "--update-baseline"
+            } else {
+                "--write-baseline"
+            };
+            let message = format!(
+                "not saving the baseline because some plugins failed, fix the errors and run {} again",
+                style(flag).yellow()
+            );
+
+            return Err(CommandError::InvalidOptions { message });
+        }
+
+        let baseline = if self.update_baseline {
+            match Baseline::read(&path)? {
+                Some(existing) => {
+                    let pruned = existing.prune(&report.issues);
+
+                    eprintln!(
+                        "Removed {} fixed issues from the baseline",
+                        existing.issues_count() - pruned.issues_count()
+                    );
+
+                    pruned
+                }
+                None => {
+                    let message = format!(
+                        "no baseline file found at {}, run with {} first",
+                        path.display(),
+                        style("--write-baseline").yellow()
+                    );
+
+                    return Err(CommandError::InvalidOptions { message });
+                }
+            }
+        } else {
+            Baseline::from_issues(&report.issues)
+        };
+
+        baseline.write(&path)?;
+
+        eprintln!(
+            "{} Wrote {} issues to {}",
+            style("✔").green(),
+            baseline.issues_count(),
+            path.display()
+        );
+
+        CommandSuccess::ok()
+    }
+
+    /// Records the issues found when checking the whole repository, so that
+    /// `qlty history` can report on them later. Issues hidden by the baseline
+    /// or `--level` are recorded too, so that trends don't depend on them.
+    fn record_history(&self, workspace: &Workspace, settings: &Settings, processor: &Processor) {
+        if !settings.all || !settings.filters.is_empty() {
+            return;
+        }
+
+        let result = workspace.library().and_then(|library| {
+            History::for_source(&library.history_dir(), HistorySource::Check)
+                .record_head(&workspace.repo()?, &processor.unfiltered_issues())
+        });
+
+        if let Err(err) = result {
+            warn!("Failed to record issue history: {}", err);
+        }
+    }
+
     fn read_pre_push_stdin() -> Result<Option<String>> {
         let mut buffer = String::new();
         io::stdin().read_to_string(&mut buffer)?;
@@ -407,31 +614,67 @@ impl Check {
         }
     }
 
+    fn output_format(&self) -> Option<OutputFormat> {
+        if self.sarif {
+            Some(OutputFormat::Sarif)
+        } else {
+            self.format
+        }
+    }
+
     fn write_stdout(&self, report: &Report, plan: &Plan, settings: &Settings) -> Result<bool> {
         if self.json {
             let formatter = JsonFormatter::new(report.issues.clone());
             formatter.write_to(&mut std::io::stdout())?;
             Ok(false)
-        } else if self.sarif {
-            let formatter = SarifFormatter::boxed(report.messages.clone(), report.issues.clone());
+        } else if let Some(format) = self.output_format() {
+            let formatter = format.formatter(report.messages.clone(), report.issues.clone());
             formatter.write_to(&mut std::io::stdout())?;
             Ok(false)
         } else {
-            let apply_mode = if self.fix {
-                ApplyMode::All
-            } else if self.no_fix {
-                ApplyMode::None
-            } else {
-                ApplyMode::Ask
-            };
-
-            let mut formatter =
-                TextFormatter::new(report, &plan.workspace, settings, self.summary, apply_mode);
+            let mut formatter = TextFormatter::new(
+                report,
+                &plan.workspace,
+                settings,
+                self.summary,
+                self.apply_mode(),
+            );
 
             formatter.write_to(&mut std::io::stdout())
         }
     }
 
+    fn apply_mode(&self) -> ApplyMode {
+        if self.fix == Some(FixMode::All) {
+            ApplyMode::All
+        } else if self.no_fix || self.watch || self.fix == Some(FixMode::Interactive) {
+            ApplyMode::None
+        } else {
+            ApplyMode::Ask
+        }
+    }
+
+    fn write_profile(&self, workspace: &Workspace, report: &Report) -> Result<()> {
+        let profile = Profile::new(&report.invocations);
+        let store = ProfileStore::new(workspace.library()?.profiles_dir());
+        store.record(&profile)?;
+
+        print_profile(
+            &mut std::io::stderr(),
+            &profile,
+            &store.repeated_timeouts()?,
+        )?;
+
+        if let Some(path) = &self.profile_trace {
+            let mut file = std::fs::File::create(path)
+                .with_context(|| format!("Failed to create {}", path.display()))?;
+            profile.write_chrome_trace(&mut file)?;
+            eprintln!("Wrote trace to {}", path.display());
+        }
+
+        Ok(())
+    }
+
     fn write_stderr(&self, report: &Report) -> Result<()> {
         if self.print_errors {
             let formatter = ErrorsFormatter::new(report);
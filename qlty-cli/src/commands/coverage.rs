@@ -1,10 +1,17 @@
 mod complete;
+mod diff;
+mod functions;
+mod merge;
 mod publish;
 mod transform;
 mod utils;
 pub use complete::Complete;
+pub use diff::Diff;
+pub use functions::Functions;
+pub use merge::Merge;
 pub use publish::Publish;
 pub use transform::Transform;
+pub use utils::read_file_coverages;
 
 use anyhow::Result;
 use clap::{Args, Subcommand};
@@ -28,6 +35,15 @@ pub enum Commands {
 
     /// Mark coverage as complete on Qlty Cloud
     Complete(Complete),
+
+    /// Report coverage of the lines changed compared to an upstream branch
+    Diff(Diff),
+
+    /// Merge several coverage reports into a single report
+    Merge(Merge),
+
+    /// List the least covered functions
+    Functions(Functions),
 }
 
 impl Arguments {
@@ -36,6 +52,9 @@ impl Arguments {
             Commands::Transform(command) => command.execute(args),
             Commands::Publish(command) => command.execute(args),
             Commands::Complete(command) => command.execute(args),
+            Commands::Diff(command) => command.execute(args),
+            Commands::Merge(command) => command.execute(args),
+            Commands::Functions(command) => command.execute(args),
         }
     }
 }
@@ -0,0 +1,197 @@
+use crate::{CommandError, CommandSuccess};
+use anyhow::Result;
+use clap::Args;
+use cli_table::{
+    format::{Border, HorizontalLine, Justify, Separator, VerticalLine},
+    print_stdout, Cell, Style, Table,
+};
+use console::style;
+use qlty_analysis::git::{compute_upstream, DiffMode, GitDiff};
+use qlty_config::Workspace;
+use qlty_coverage::{diff::DiffCoverage, formats::Formats, transform::Settings};
+use qlty_types::tests::v1::FileCoverage;
+
+use super::utils::{load_config, read_file_coverages};
+
+#[derive(Debug, Args)]
+pub struct Diff {
+    #[arg(long, value_enum)]
+    /// The format of the coverage reports. If not specified, the format will be inferred from the file extension or contents.
+    pub report_format: Option<Formats>,
+
+    #[arg(long)]
+    /// The prefix to add to file paths in the coverage reports.
+    pub add_prefix: Option<String>,
+
+    #[arg(long)]
+    /// The prefix to remove from absolute paths in the coverage reports. Defaults to the root of the git repository.
+    pub strip_prefix: Option<String>,
+
+    #[arg(long)]
+    /// The upstream branch or commit to compare against. Defaults to the remote's default branch.
+    pub upstream: Option<String>,
+
+    #[arg(long, value_name = "PERCENT")]
+    /// Exit with a failure if the coverage of the changed lines is below this percentage (0-100).
+    pub fail_under: Option<f64>,
+
+    #[arg(long)]
+    /// JSON output
+    pub json: bool,
+
+    /// The paths to the coverage reports, which may include their format (e.g., 'lcov:./coverage/lcov.info').
+    #[arg(required = true)]
+    pub paths: Vec<String>,
+}
+
+impl Diff {
+    pub fn execute(&self, _args: &crate::Arguments) -> Result<CommandSuccess, CommandError> {
+        if let Some(threshold) = self.fail_under {
+            if !(0.0..=100.0).contains(&threshold) {
+                return Err(CommandError::InvalidOptions {
+                    message: "--fail-under must be between 0 and 100".to_string(),
+                });
+            }
+        }
+
+        let workspace = Workspace::new()?;
+        let upstream = compute_upstream(&workspace, &self.upstream).ok_or_else(|| {
+            CommandError::InvalidOptions {
+                message: "Unable to determine the upstream branch. Please specify --upstream."
+                    .to_string(),
+            }
+        })?;
+
+        let file_coverages = self.read_reports()?;
+        let git_diff = GitDiff::compute(
+            DiffMode::UpstreamToWorkdir(upstream.clone()),
+            &workspace.root,
+        )?;
+        let diff_coverage = DiffCoverage::compute(&file_coverages, git_diff.line_filter.index());
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&diff_coverage)?);
+        } else {
+            self.print(&diff_coverage, &upstream)?;
+        }
+
+        let fail = self
+            .fail_under
+            .is_some_and(|threshold| diff_coverage.is_below(threshold));
+
+        if fail && !self.json {
+            eprintln!(
+                "{} Diff coverage of {:.2}% is below the threshold of {:.2}%",
+                style("✖").red().bold(),
+                diff_coverage.coverage_percentage().unwrap_or_default(),
+                self.fail_under.unwrap_or_default()
+            );
+        }
+
+        Ok(CommandSuccess {
+            fail,
+            ..Default::default()
+        })
+    }
+
+    fn read_reports(&self) -> Result<Vec<FileCoverage>> {
+        let settings = Settings {
+            report_format: self.report_format,
+            add_prefix: self.add_prefix.clone(),
+            strip_prefix: self.strip_prefix.clone(),
+            path: String::new(),
+            ignores: vec![],
+            pragmas: false,
+            ignore_marker: None,
+        };
+
+        read_file_coverages(&load_config(false), &settings, &self.paths)
+    }
+
+    fn print(&self, diff_coverage: &DiffCoverage, upstream: &str) -> Result<()> {
+        let Some(total_percentage) = diff_coverage.coverage_percentage() else {
+            eprintln!("No coverable lines changed compared to {}", upstream);
+            return Ok(());
+        };
+
+        let mut rows = diff_coverage
+            .files
+            .iter()
+            .map(|file| {
+                vec![
+                    file.path.clone().cell(),
+                    file.covered_lines.cell().justify(Justify::Right),
+                    file.uncovered_lines.cell().justify(Justify::Right),
+                    format!("{:.2}", file.coverage_percentage().unwrap_or_default())
+                        .cell()
+                        .justify(Justify::Right),
+                    format_ranges(&file.uncovered_ranges).cell(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        rows.push(vec![
+            "TOTAL".cell().bold(true),
+            diff_coverage
+                .covered_lines
+                .cell()
+                .bold(true)
+                .justify(Justify::Right),
+            diff_coverage
+                .uncovered_lines
+                .cell()
+                .bold(true)
+                .justify(Justify::Right),
+            format!("{:.2}", total_percentage)
+                .cell()
+                .bold(true)
+                .justify(Justify::Right),
+            "".cell(),
+        ]);
+
+        let table = rows
+            .table()
+            .title(vec![
+                "name".cell(),
+                "covered".cell().justify(Justify::Right),
+                "missed".cell().justify(Justify::Right),
+                "%".cell().justify(Justify::Right),
+                "missed lines".cell(),
+            ])
+            .border(Border::builder().build())
+            .separator(
+                Separator::builder()
+                    .title(Some(HorizontalLine::default()))
+                    .column(Some(VerticalLine::default()))
+                    .build(),
+            );
+
+        print_stdout(table)?;
+        Ok(())
+    }
+}
+
+fn format_ranges(ranges: &[(u32, u32)]) -> String {
+    ranges
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{}-{}", start, end)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_ranges() {
+        assert_eq!(format_ranges(&[]), "");
+        assert_eq!(format_ranges(&[(2, 4), (7, 7)]), "2-4, 7");
+    }
+}
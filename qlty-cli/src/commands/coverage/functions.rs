@@ -0,0 +1,247 @@
+use crate::{CommandError, CommandSuccess};
+use anyhow::Result;
+use clap::Args;
+use cli_table::{
+    format::{Border, HorizontalLine, Justify, Separator, VerticalLine},
+    print_stdout, Cell, Table,
+};
+use qlty_analysis::workspace_entries::{TargetMode, WorkspaceEntryFinderBuilder};
+use qlty_config::Workspace;
+use qlty_coverage::{eprintln_unless, formats::Formats, transform::Settings};
+use qlty_smells::metrics;
+use qlty_types::analysis::v1::Stats;
+use std::path::{Path, PathBuf};
+
+use super::utils::{load_config, read_file_coverages};
+
+const DEFAULT_LIMIT: usize = 20;
+
+#[derive(Debug, Args)]
+pub struct Functions {
+    #[arg(long, value_enum)]
+    /// The format of the coverage reports. If not specified, the format will be inferred from the file extension or contents of each report.
+    pub report_format: Option<Formats>,
+
+    #[arg(long)]
+    /// The prefix to add to file paths in the coverage reports.
+    pub add_prefix: Option<String>,
+
+    #[arg(long)]
+    /// The prefix to remove from absolute paths in the coverage reports. Defaults to the root of the git repository.
+    pub strip_prefix: Option<String>,
+
+    #[arg(long)]
+    /// Maximum number of functions to print. Defaults to 20.
+    pub limit: Option<usize>,
+
+    #[arg(long)]
+    /// JSON output
+    pub json: bool,
+
+    #[clap(long, short)]
+    /// Suppresses most of the standard output messages.
+    pub quiet: bool,
+
+    /// The paths to the coverage reports, which may include their format (e.g., 'lcov:./coverage/lcov.info').
+    #[arg(required = true)]
+    pub paths: Vec<String>,
+}
+
+impl Functions {
+    pub fn execute(&self, _args: &crate::Arguments) -> Result<CommandSuccess, CommandError> {
+        let workspace = Workspace::new()?;
+        let config = load_config(false);
+
+        let file_coverages = read_file_coverages(
+            &config,
+            &Settings {
+                report_format: self.report_format,
+                add_prefix: self.add_prefix.clone(),
+                strip_prefix: self.strip_prefix.clone(),
+                path: String::new(),
+                ignores: vec![],
+                pragmas: false,
+                ignore_marker: None,
+            },
+            &self.paths,
+        )?;
+        let source_paths = file_coverages
+            .iter()
+            .map(|file_coverage| workspace.root.join(&file_coverage.path))
+            .filter(|path| path.is_file())
+            .collect::<Vec<_>>();
+
+        eprintln_unless!(
+            self.quiet,
+            "Analyzing functions in {} covered files...",
+            source_paths.len()
+        );
+
+        let target_mode = TargetMode::Paths(source_paths.len());
+        let files = WorkspaceEntryFinderBuilder {
+            mode: target_mode.clone(),
+            paths: source_paths,
+            config: config.clone(),
+            ..Default::default()
+        }
+        .build()?
+        .files_for_qlty()?;
+
+        let settings = metrics::Settings {
+            functions: true,
+            exclude_tests: false,
+            target_mode,
+            coverage: file_coverages
+                .into_iter()
+                .map(|file_coverage| (workspace.root.join(&file_coverage.path), file_coverage.hits))
+                .collect(),
+        };
+
+        let plan = metrics::Planner::new(&config, &settings, files).compute()?;
+        let results = metrics::Executor::new(&plan).execute();
+
+        let mut stats = least_covered(results.stats);
+        stats.truncate(self.limit.unwrap_or(DEFAULT_LIMIT));
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&stats)?);
+        } else {
+            print(&stats, &workspace.root)?;
+        }
+
+        CommandSuccess::ok()
+    }
+}
+
+/// Functions with executable lines, ordered from the lowest coverage. Ties
+/// are broken by the riskiest function first, as measured by its CRAP score.
+fn least_covered(stats: Vec<Stats>) -> Vec<Stats> {
+    let mut stats = stats
+        .into_iter()
+        .filter(|stats| stats.coverage_percent().is_some())
+        .collect::<Vec<_>>();
+
+    stats.sort_by(|a, b| {
+        a.coverage_percent()
+            .partial_cmp(&b.coverage_percent())
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| {
+                b.crap()
+                    .partial_cmp(&a.crap())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .then_with(|| a.path.cmp(&b.path))
+            .then_with(|| a.fully_qualified_name.cmp(&b.fully_qualified_name))
+    });
+
+    stats
+}
+
+fn print(stats: &[Stats], root: &Path) -> Result<()> {
+    if stats.is_empty() {
+        eprintln!("No covered functions found");
+        return Ok(());
+    }
+
+    let rows = stats
+        .iter()
+        .map(|stats| {
+            let path = PathBuf::from(&stats.path);
+
+            vec![
+                stats.fully_qualified_name.clone().cell(),
+                path.strip_prefix(root)
+                    .unwrap_or(&path)
+                    .display()
+                    .to_string()
+                    .cell(),
+                stats
+                    .covered_lines
+                    .unwrap_or_default()
+                    .cell()
+                    .justify(Justify::Right),
+                stats
+                    .uncovered_lines
+                    .unwrap_or_default()
+                    .cell()
+                    .justify(Justify::Right),
+                format!("{:.2}", stats.coverage_percent().unwrap_or_default())
+                    .cell()
+                    .justify(Justify::Right),
+                stats
+                    .cyclomatic
+                    .unwrap_or_default()
+                    .cell()
+                    .justify(Justify::Right),
+                format!("{:.1}", stats.crap().unwrap_or_default())
+                    .cell()
+                    .justify(Justify::Right),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let table = rows
+        .table()
+        .title(vec![
+            "function".cell(),
+            "path".cell(),
+            "covered".cell().justify(Justify::Right),
+            "missed".cell().justify(Justify::Right),
+            "%".cell().justify(Justify::Right),
+            "cyclo".cell().justify(Justify::Right),
+            "CRAP".cell().justify(Justify::Right),
+        ])
+        .border(Border::builder().build())
+        .separator(
+            Separator::builder()
+                .title(Some(HorizontalLine::default()))
+                .column(Some(VerticalLine::default()))
+                .build(),
+        );
+
+    print_stdout(table)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn function(name: &str, covered: u32, uncovered: u32, cyclomatic: u32) -> Stats {
+        Stats {
+            fully_qualified_name: name.to_string(),
+            cyclomatic: Some(cyclomatic),
+            covered_lines: Some(covered),
+            uncovered_lines: Some(uncovered),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_least_covered() {
+        let stats = least_covered(vec![
+            function("covered", 4, 0, 1),
+            function("simple_untested", 0, 4, 1),
+            function("no_lines", 0, 0, 1),
+            function("half", 2, 2, 3),
+            function("complex_untested", 0, 4, 5),
+            Stats {
+                fully_qualified_name: "no_coverage".to_string(),
+                cyclomatic: Some(1),
+                ..Default::default()
+            },
+        ]);
+
+        let names = stats
+            .iter()
+            .map(|stats| stats.fully_qualified_name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            vec!["complex_untested", "simple_untested", "half", "covered"]
+        );
+        assert_eq!(stats[0].crap(), Some(30.0));
+        assert_eq!(stats[2].crap(), Some(4.125));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{CommandError, CommandSuccess};
+use anyhow::Result;
+use clap::Args;
+use qlty_coverage::{
+    eprintln_unless,
+    formats::Formats,
+    formatter::{formatter_for, OutputFormats},
+    merge::merge_file_coverages,
+    transform::{Planner, Processor, Settings},
+};
+use std::path::PathBuf;
+
+use super::utils::{load_config, print_initial_messages, workspace_root};
+
+#[derive(Debug, Args)]
+pub struct Merge {
+    #[arg(long, value_enum)]
+    /// The format of the coverage reports. If not specified, the format will be inferred from the file extension or contents of each report.
+    pub report_format: Option<Formats>,
+
+    #[arg(long)]
+    /// The prefix to add to file paths in the coverage reports.
+    pub add_prefix: Option<String>,
+
+    #[arg(long)]
+    /// The prefix to remove from absolute paths in the coverage reports. Defaults to the root of the git repository.
+    pub strip_prefix: Option<String>,
+
+    #[arg(long, value_enum, default_value_t)]
+    /// The format of the merged coverage report.
+    pub output_format: OutputFormats,
+
+    #[arg(long)]
+    /// The output file name for the merged coverage report. Defaults to 'coverage.jsonl', 'lcov.info', 'cobertura.xml' or 'coverage.html' depending on the output format.
+    pub output: Option<String>,
+
+    #[clap(long, short)]
+    /// Suppresses most of the standard output messages.
+    pub quiet: bool,
+
+    /// The paths to the coverage reports, which may include their format (e.g., 'lcov:./coverage/lcov.info').
+    #[arg(required = true)]
+    pub paths: Vec<String>,
+}
+
+impl Merge {
+    pub fn execute(&self, _args: &crate::Arguments) -> Result<CommandSuccess, CommandError> {
+        print_initial_messages(self.quiet);
+
+        let config = load_config(false);
+        let ignores = config.coverage.ignores.clone().unwrap_or_default();
+        let mut file_coverages = vec![];
+
+        for path in &self.paths {
+            eprintln_unless!(self.quiet, "Reading coverage report {}", path);
+
+            let settings = Settings {
+                report_format: self.report_format,
+                add_prefix: self.add_prefix.clone(),
+                strip_prefix: self.strip_prefix.clone(),
+                path: path.clone(),
+                ignores: ignores.clone(),
+                pragmas: config.coverage.pragmas,
+                ignore_marker: config.coverage.ignore_marker.clone(),
+            };
+
+            let plan = Planner::new(&settings).compute()?;
+            file_coverages.extend(Processor::new(&plan).compute()?);
+        }
+
+        let file_coverages = merge_file_coverages(file_coverages);
+        let files_count = file_coverages.len();
+
+        formatter_for(self.output_format, file_coverages, &workspace_root())
+            .write_to_file(&PathBuf::from(self.output()))?;
+
+        eprintln_unless!(
+            self.quiet,
+            "Merged {} reports covering {} files into {}",
+            self.paths.len(),
+            files_count,
+            self.output()
+        );
+
+        CommandSuccess::ok()
+    }
+
+    fn output(&self) -> String {
+        self.output
+            .clone()
+            .unwrap_or_else(|| self.output_format.default_output().to_string())
+    }
+}
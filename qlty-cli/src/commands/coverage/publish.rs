@@ -11,7 +11,7 @@ use num_format::{Locale, ToFormattedString as _};
 use qlty_config::QltyConfig;
 use qlty_coverage::formats::Formats;
 use qlty_coverage::print::{print_report_as_json, print_report_as_text};
-use qlty_coverage::publish::{Plan, Planner, Processor, Reader, Report, Settings, Upload};
+use qlty_coverage::publish::{Plan, Planner, Processor, Reader, Report, Settings, Target, Upload};
 use qlty_coverage::token::load_auth_token;
 use qlty_coverage::validate::{ValidationStatus, Validator};
 use qlty_coverage::{ExclusionStrategy, JavaSrcDirFinder};
@@ -160,6 +160,18 @@ pub struct Publish {
     #[arg(long)]
     pub skip_source_fetch: bool,
 
+    #[arg(long, conflicts_with = "target_url")]
+    /// Publish the export bundle to a local directory instead of the Qlty Cloud.
+    pub target_dir: Option<PathBuf>,
+
+    #[arg(long)]
+    /// Publish the export bundle with a PUT request to this URL instead of the Qlty Cloud.
+    pub target_url: Option<String>,
+
+    #[arg(long, value_name = "HEADER", requires = "target_url")]
+    /// Additional 'Name: value' header to send with the request to --target-url. Can be repeated.
+    pub target_header: Vec<String>,
+
     // Paths to coverage reports
     pub paths: Vec<String>,
 }
@@ -184,7 +196,9 @@ impl Publish {
         print_settings(&settings);
         self.validate_options()?;
 
-        let token = if self.dry_run {
+        let target = self.target()?;
+
+        let token = if self.dry_run || target.is_some() {
             load_auth_token(&self.token, self.project.as_deref()).unwrap_or_default()
         } else {
             load_auth_token(&self.token, self.project.as_deref())?
@@ -233,6 +247,15 @@ impl Publish {
             return CommandSuccess::ok();
         }
 
+        if let Some(target) = target {
+            self.print_section_header(" PUBLISHING... ");
+            let timer = Instant::now();
+            target.publish(&export)?;
+            let bytes = export.total_size_bytes()?;
+            self.print_publish_complete(bytes, timer.elapsed().as_secs_f32(), &target);
+            return CommandSuccess::ok();
+        }
+
         self.print_section_header(" AUTHENTICATION ");
         print_authentication_info(&token, self.quiet);
 
@@ -249,6 +272,27 @@ impl Publish {
         CommandSuccess::ok()
     }
 
+    fn target(&self) -> Result<Option<Target>> {
+        if let Some(directory) = &self.target_dir {
+            return Ok(Some(Target::Directory(directory.clone())));
+        }
+
+        if let Some(url) = &self.target_url {
+            let headers = self
+                .target_header
+                .iter()
+                .map(|header| Target::parse_header(header))
+                .collect::<Result<Vec<_>>>()?;
+
+            return Ok(Some(Target::Http {
+                url: url.clone(),
+                headers,
+            }));
+        }
+
+        Ok(None)
+    }
+
     fn print_upload(&self, upload: &Upload) {
         if self.quiet {
             return;
@@ -630,6 +674,20 @@ impl Publish {
         eprintln!();
     }
 
+    fn print_publish_complete(&self, bytes: u64, elapsed_seconds: f32, target: &Target) {
+        if self.quiet {
+            return;
+        }
+
+        eprintln!(
+            "    Published {} to {} in {:.2}s!",
+            HumanBytes(bytes),
+            target.location(),
+            elapsed_seconds
+        );
+        eprintln!();
+    }
+
     fn validate_options(&self) -> Result<(), CommandError> {
         if let Some(total_parts) = self.total_parts_count {
             if total_parts == 0 {
@@ -4,14 +4,14 @@ use clap::Args;
 use qlty_coverage::{
     eprintln_unless,
     formats::Formats,
+    formatter::{formatter_for, OutputFormats},
     print::{print_file_coverages_as_json, print_file_coverages_as_text},
     transform::{Planner, Processor, Settings},
 };
-use qlty_formats::{Formatter, JsonEachRowFormatter};
 use qlty_types::tests::v1::FileCoverage;
 use std::path::PathBuf;
 
-use super::utils::print_initial_messages;
+use super::utils::{load_config, print_initial_messages, workspace_root};
 
 #[derive(Debug, Args)]
 pub struct Transform {
@@ -32,8 +32,12 @@ pub struct Transform {
     /// This is usually the directory in which the tests were run. Defaults to the root of the git repository.
     pub strip_prefix: Option<String>,
 
+    #[arg(long, value_enum, default_value_t)]
+    /// The format of the transformed coverage report, which allows converting between coverage formats.
+    pub output_format: OutputFormats,
+
     #[arg(long)]
-    /// The output file name for the transformed coverage report. If not specified, the report will be saved to 'coverage.jsonl'.
+    /// The output file name for the transformed coverage report. If not specified, the report will be saved to 'coverage.jsonl', 'lcov.info', 'cobertura.xml' or 'coverage.html' depending on the output format.
     pub output: Option<String>,
 
     #[arg(long)]
@@ -60,11 +64,15 @@ impl Transform {
             eprintln!("Transforming coverage report {}", self.path);
         }
 
+        let config = load_config(false);
         let settings = Settings {
             report_format: self.report_format,
             add_prefix: self.add_prefix.clone(),
             strip_prefix: self.strip_prefix.clone(),
             path: self.path.clone(),
+            ignores: vec![],
+            pragmas: config.coverage.pragmas,
+            ignore_marker: config.coverage.ignore_marker.clone(),
         };
 
         let planner = Planner::new(&settings);
@@ -81,7 +89,8 @@ impl Transform {
 
             eprintln_unless!(
                 self.quiet,
-                "Exported qlty coverage report to {}",
+                "Exported {} coverage report to {}",
+                self.output_format,
                 self.output()
             );
         }
@@ -92,12 +101,17 @@ impl Transform {
     fn output(&self) -> String {
         self.output
             .clone()
-            .unwrap_or_else(|| "coverage.jsonl".to_string())
+            .unwrap_or_else(|| self.output_format.default_output().to_string())
     }
 
     fn export(&self, file_coverages: &Vec<FileCoverage>) -> Result<()> {
         let output_path = PathBuf::from(self.output());
-        JsonEachRowFormatter::new(file_coverages.clone()).write_to_file(&output_path)?;
+        formatter_for(
+            self.output_format,
+            file_coverages.clone(),
+            &workspace_root(),
+        )
+        .write_to_file(&output_path)?;
         Ok(())
     }
 
@@ -2,8 +2,10 @@ use anyhow::{bail, Result};
 use chrono::{DateTime, Utc};
 use console::style;
 use qlty_config::{version::LONG_VERSION, DeprecatedDefaultSourceError, QltyConfig, Workspace};
+use qlty_coverage::merge::merge_file_coverages;
 use qlty_coverage::publish::Settings;
-use qlty_types::tests::v1::{CoverageMetadata, ReferenceType};
+use qlty_coverage::transform;
+use qlty_types::tests::v1::{CoverageMetadata, FileCoverage, ReferenceType};
 use regex::Regex;
 use std::path::PathBuf;
 use tracing::{info, warn};
@@ -19,6 +21,39 @@ pub fn load_config(skip_source_fetch: bool) -> QltyConfig {
     load_config_for(&workspace, skip_source_fetch)
 }
 
+/// Reads and merges coverage reports with the given format and prefixes,
+/// applying `coverage.ignores` and `coverage.pragmas` like `coverage merge`.
+pub fn read_file_coverages(
+    config: &QltyConfig,
+    settings: &transform::Settings,
+    paths: &[String],
+) -> Result<Vec<FileCoverage>> {
+    let mut file_coverages = vec![];
+
+    for path in paths {
+        let settings = transform::Settings {
+            path: path.clone(),
+            ignores: config.coverage.ignores.clone().unwrap_or_default(),
+            pragmas: config.coverage.pragmas,
+            ignore_marker: config.coverage.ignore_marker.clone(),
+            ..settings.clone()
+        };
+
+        let plan = transform::Planner::new(&settings).compute()?;
+        file_coverages.extend(transform::Processor::new(&plan).compute()?);
+    }
+
+    Ok(merge_file_coverages(file_coverages))
+}
+
+/// The root of the git repository, or the current directory outside of one,
+/// which is what the paths in transformed coverage are relative to.
+pub fn workspace_root() -> PathBuf {
+    Workspace::new()
+        .map(|workspace| workspace.root)
+        .unwrap_or_else(|_| PathBuf::from("."))
+}
+
 fn load_config_for(workspace: &Workspace, skip_source_fetch: bool) -> QltyConfig {
     if !matches!(workspace.config_exists(), Ok(true)) {
         return QltyConfig::default();
@@ -341,4 +376,35 @@ tag = "v99.99.99"
         assert_eq!(config.config_version, None);
         assert!(config.plugin.is_empty());
     }
+
+    #[test]
+    fn test_read_file_coverages_applies_ignores() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let report = temp_dir.path().join("lcov.info");
+        fs::write(
+            &report,
+            "SF:src/app.rs\nDA:1,1\nend_of_record\nSF:vendor/lib.rs\nDA:1,0\nend_of_record\n",
+        )
+        .unwrap();
+
+        let mut config = QltyConfig::default();
+        config.coverage.ignores = Some(vec!["vendor/**".to_string()]);
+
+        let settings = transform::Settings {
+            path: String::new(),
+            report_format: None,
+            add_prefix: None,
+            strip_prefix: Some(temp_dir.path().to_string_lossy().to_string()),
+            ignores: vec![],
+            pragmas: false,
+            ignore_marker: None,
+        };
+
+        let file_coverages =
+            read_file_coverages(&config, &settings, &[report.to_string_lossy().to_string()])
+                .unwrap();
+
+        assert_eq!(file_coverages.len(), 1);
+        assert_eq!(file_coverages[0].path, "src/app.rs");
+    }
 }
@@ -0,0 +1,245 @@
+use crate::ui::Steps;
+use crate::{Arguments, CommandError, CommandSuccess};
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use cli_table::{
+    format::{Border, HorizontalLine, Justify, Separator, VerticalLine},
+    print_stdout, Cell, Table,
+};
+use console::Emoji;
+use qlty_analysis::workspace_entries::{TargetMode, WorkspaceEntryFinderBuilder};
+use qlty_config::Workspace;
+use qlty_smells::duplication::{
+    clone_classes, duplication_by_directory, CloneClass, DirectoryDuplication, Executor,
+    HtmlReport, Planner, Settings,
+};
+use serde::Serialize;
+use std::path::PathBuf;
+
+static LOOKING_GLASS: Emoji<'_, '_> = Emoji("🔍  ", "");
+static THINKING: Emoji<'_, '_> = Emoji("🤔  ", "");
+static SPARKLES: Emoji<'_, '_> = Emoji("✨  ", "");
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, ValueEnum)]
+pub enum DuplicationFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+#[derive(Args, Debug)]
+pub struct Duplication {
+    /// Write an HTML page showing each clone class side by side
+    #[arg(long, value_name = "FILE")]
+    pub html: Option<PathBuf>,
+
+    /// Maximum clone classes and directories to print
+    #[arg(long, default_value = "20")]
+    pub limit: usize,
+
+    /// Include tests
+    #[arg(long)]
+    pub include_tests: bool,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    pub format: DuplicationFormat,
+
+    /// Only show results
+    #[arg(long)]
+    pub quiet: bool,
+
+    /// Only report clones found in these files
+    pub paths: Vec<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+struct DuplicationReport<'a> {
+    classes: &'a [CloneClass],
+    directories: &'a [DirectoryDuplication],
+}
+
+impl Duplication {
+    pub fn execute(&self, _args: &Arguments) -> Result<CommandSuccess, CommandError> {
+        let workspace = Workspace::new()?;
+        let config = workspace.load_config(false)?;
+
+        let mut steps = Steps::new(self.quiet || self.format != DuplicationFormat::Table, 3);
+        steps.start(LOOKING_GLASS, "Analyzing all targets...");
+
+        let files = WorkspaceEntryFinderBuilder {
+            mode: TargetMode::All,
+            paths: vec![],
+            config: config.clone(),
+            exclude_tests: !self.include_tests,
+            ..Default::default()
+        }
+        .build()?
+        .files_for_qlty()?;
+
+        let settings = Settings {
+            paths: self.paths.clone(),
+            include_tests: self.include_tests,
+        };
+
+        steps.start(
+            THINKING,
+            format!("Looking for duplication across {} files... ", files.len()),
+        );
+        let plan = Planner::new(&config, &settings, files)?.compute()?;
+        let mut executor = Executor::new(&plan);
+        executor.execute();
+
+        let mut report = executor.report();
+        report.relativeize_paths(&workspace.root);
+
+        let classes = clone_classes(&report);
+        let directories = duplication_by_directory(&classes);
+
+        steps.start(SPARKLES, "Reporting... ");
+
+        if let Some(html) = &self.html {
+            let mut file = std::fs::File::create(html)
+                .with_context(|| format!("Unable to create {}", html.display()))?;
+            HtmlReport::new(classes.clone()).write_to(&mut file)?;
+        }
+
+        match self.format {
+            DuplicationFormat::Table => {
+                print_classes(&classes[..classes.len().min(self.limit)])?;
+                print_directories(&directories[..directories.len().min(self.limit)])?;
+
+                if let Some(html) = &self.html {
+                    println!();
+                    println!("Wrote {}", html.display());
+                }
+            }
+            DuplicationFormat::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(&DuplicationReport {
+                    classes: &classes,
+                    directories: &directories,
+                })?
+            ),
+        }
+
+        CommandSuccess::ok()
+    }
+}
+
+fn print_classes(classes: &[CloneClass]) -> Result<()> {
+    let rows = classes
+        .iter()
+        .map(|class| {
+            let first = &class.occurrences[0];
+
+            vec![
+                describe(class).cell(),
+                class.languages().join(", ").cell(),
+                class.occurrences.len().cell().justify(Justify::Right),
+                first.lines_count().cell().justify(Justify::Right),
+                class.duplicated_lines().cell().justify(Justify::Right),
+                format!("{}:{}", first.path, first.start_line).cell(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let table = rows
+        .table()
+        .title(vec![
+            "clone".cell(),
+            "languages".cell(),
+            "count".cell().justify(Justify::Right),
+            "lines".cell().justify(Justify::Right),
+            "dup lines".cell().justify(Justify::Right),
+            "first location".cell(),
+        ])
+        .border(Border::builder().build())
+        .separator(
+            Separator::builder()
+                .title(Some(HorizontalLine::default()))
+                .column(Some(VerticalLine::default()))
+                .build(),
+        );
+
+    println!();
+    print_stdout(table)?;
+    Ok(())
+}
+
+fn print_directories(directories: &[DirectoryDuplication]) -> Result<()> {
+    let rows = directories
+        .iter()
+        .map(|directory| {
+            vec![
+                directory.directory.clone().cell(),
+                directory.files.cell().justify(Justify::Right),
+                directory.duplicated_lines.cell().justify(Justify::Right),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let table = rows
+        .table()
+        .title(vec![
+            "directory".cell(),
+            "files".cell().justify(Justify::Right),
+            "dup lines".cell().justify(Justify::Right),
+        ])
+        .border(Border::builder().build())
+        .separator(
+            Separator::builder()
+                .title(Some(HorizontalLine::default()))
+                .column(Some(VerticalLine::default()))
+                .build(),
+        );
+
+    println!();
+    print_stdout(table)?;
+    Ok(())
+}
+
+fn describe(class: &CloneClass) -> String {
+    match class.similarity {
+        Some(similarity) => format!("near-miss {:.0}% :{}", similarity * 100.0, class.node_kind),
+        None if class.rule_key == "identical-code" => format!("identical :{}", class.node_kind),
+        None => format!("similar :{}", class.node_kind),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qlty_smells::duplication::CloneOccurrence;
+
+    fn class(rule_key: &str, similarity: Option<f64>) -> CloneClass {
+        CloneClass {
+            rule_key: rule_key.to_string(),
+            node_kind: "method".to_string(),
+            mass: 30,
+            similarity,
+            occurrences: vec![CloneOccurrence {
+                path: "lib/a.rb".to_string(),
+                language: "ruby".to_string(),
+                start_line: 1,
+                end_line: 5,
+                differing_lines: "".to_string(),
+                snippet_with_context: "".to_string(),
+                context_start_line: 1,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            describe(&class("identical-code", None)),
+            "identical :method"
+        );
+        assert_eq!(describe(&class("similar-code", None)), "similar :method");
+        assert_eq!(
+            describe(&class("near-miss-code", Some(0.854))),
+            "near-miss 85% :method"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::ui::{ApplyMode, TextFormatter};
+use crate::watch::WatchSession;
 use crate::{Arguments, CommandError, CommandSuccess, Trigger};
 use anyhow::Result;
 use clap::Args;
@@ -58,6 +59,10 @@ pub struct Fmt {
     /// Skip fetching sources before formatting
     #[arg(long)]
     skip_source_fetch: bool,
+
+    /// Re-run formatters on files as they change
+    #[arg(long, conflicts_with_all = ["index", "index_file"])]
+    watch: bool,
 }
 
 impl Fmt {
@@ -68,6 +73,12 @@ impl Fmt {
         workspace.prepare_sources(self.skip_source_fetch)?;
 
         let settings = self.build_settings()?;
+
+        if self.watch {
+            WatchSession::new(ExecutionVerb::Fmt, &workspace, &settings, ApplyMode::None).run()?;
+            return CommandSuccess::ok();
+        }
+
         let plan = Planner::new(ExecutionVerb::Fmt, &settings)?.compute()?;
         let executor = Executor::new(&plan);
         let results = executor.install_and_invoke()?;
@@ -0,0 +1,53 @@
+use crate::{CommandError, CommandSuccess};
+use anyhow::Result;
+use clap::{Args, Subcommand, ValueEnum};
+use qlty_check::history::HistorySource;
+
+mod diff;
+mod trends;
+
+pub use diff::Diff;
+pub use trends::Trends;
+
+#[derive(Debug, Args)]
+pub struct Arguments {
+    #[command(subcommand)]
+    pub command: Commands,
+}
+
+/// The command whose recorded issues are shown
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, ValueEnum)]
+pub enum Source {
+    /// Issues recorded by `qlty check --all`
+    #[default]
+    Check,
+    /// Issues and smells recorded by `qlty build`
+    Build,
+}
+
+impl From<Source> for HistorySource {
+    fn from(source: Source) -> Self {
+        match source {
+            Source::Check => HistorySource::Check,
+            Source::Build => HistorySource::Build,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Commands {
+    /// Show issue counts for recent commits
+    Trends(Trends),
+
+    /// List issues introduced or fixed between two commits
+    Diff(Diff),
+}
+
+impl Arguments {
+    pub fn execute(&self, args: &crate::Arguments) -> Result<CommandSuccess, CommandError> {
+        match &self.command {
+            Commands::Diff(command) => command.execute(args),
+            Commands::Trends(command) => command.execute(args),
+        }
+    }
+}
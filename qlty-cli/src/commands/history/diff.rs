@@ -0,0 +1,84 @@
+use super::Source;
+use crate::{Arguments, CommandError, CommandSuccess};
+use anyhow::{Context, Result};
+use clap::Args;
+use console::style;
+use qlty_check::history::{History, HistoryEntry, HistoryIssue};
+use qlty_config::Workspace;
+
+#[derive(Args, Debug)]
+pub struct Diff {
+    /// Commit to compare from
+    pub from: String,
+
+    /// Commit to compare to
+    #[arg(default_value = "HEAD")]
+    pub to: String,
+
+    /// Command whose recorded issues to compare
+    #[arg(long, value_enum, default_value_t)]
+    pub source: Source,
+}
+
+impl Diff {
+    pub fn execute(&self, _args: &Arguments) -> Result<CommandSuccess, CommandError> {
+        let workspace = Workspace::require_initialized()?;
+        let history = History::for_source(&workspace.library()?.history_dir(), self.source.into());
+
+        let from = self.load(&workspace, &history, &self.from)?;
+        let to = self.load(&workspace, &history, &self.to)?;
+        let diff = from.diff(&to);
+
+        print_issues("Introduced", &diff.introduced);
+        println!();
+        print_issues("Fixed", &diff.fixed);
+
+        CommandSuccess::ok()
+    }
+
+    fn load(
+        &self,
+        workspace: &Workspace,
+        history: &History,
+        reference: &str,
+    ) -> Result<HistoryEntry, CommandError> {
+        let commit_sha = workspace
+            .repo()?
+            .revparse_single(reference)
+            .and_then(|object| object.peel_to_commit())
+            .with_context(|| format!("Unable to find commit {}", reference))?
+            .id()
+            .to_string();
+
+        match history.get(&commit_sha)? {
+            Some(entry) => Ok(entry),
+            None => Err(CommandError::InvalidOptions {
+                message: format!(
+                    "no history recorded for {} ({}), check out that commit and run {}",
+                    reference,
+                    &commit_sha[..7],
+                    style("qlty check --all").yellow()
+                ),
+            }),
+        }
+    }
+}
+
+fn print_issues(title: &str, issues: &[HistoryIssue]) {
+    println!(
+        "{}",
+        style(format!(" {}: {} ", title.to_uppercase(), issues.len()))
+            .bold()
+            .reverse()
+    );
+    println!();
+
+    for issue in issues {
+        println!(
+            "{} {} {}",
+            style(format!("{}:{}", issue.path, issue.line)).underlined(),
+            style(issue.rule_id()).dim(),
+            issue.message
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use super::Source;
+use crate::{Arguments, CommandError, CommandSuccess};
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use cli_table::{
+    format::{Border, HorizontalLine, Justify, Separator, VerticalLine},
+    print_stdout, Cell, CellStruct, Table,
+};
+use qlty_check::history::{History, HistoryEntry};
+use qlty_config::Workspace;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    Rule,
+    Directory,
+}
+
+#[derive(Args, Debug)]
+pub struct Trends {
+    /// Group issue counts by rule or by directory
+    #[arg(long, value_enum, default_value = "rule")]
+    by: GroupBy,
+
+    /// Number of directory levels to group by
+    #[arg(long, default_value = "1")]
+    depth: usize,
+
+    /// Number of most recent commits to show
+    #[arg(long, default_value = "5")]
+    limit: usize,
+
+    /// Command whose recorded issues to show
+    #[arg(long, value_enum, default_value_t)]
+    source: Source,
+}
+
+impl Trends {
+    pub fn execute(&self, _args: &Arguments) -> Result<CommandSuccess, CommandError> {
+        let workspace = Workspace::require_initialized()?;
+        let history = History::for_source(&workspace.library()?.history_dir(), self.source.into());
+
+        let entries = history.entries()?;
+        let entries = &entries[entries.len().saturating_sub(self.limit)..];
+
+        if entries.is_empty() {
+            let command = match self.source {
+                Source::Check => "qlty check --all",
+                Source::Build => "qlty build",
+            };
+
+            eprintln!(
+                "No history recorded yet. Run `{}` on a clean working tree to record issues for the current commit.",
+                command
+            );
+            return CommandSuccess::ok();
+        }
+
+        let counts = entries
+            .iter()
+            .map(|entry| self.counts(entry))
+            .collect::<Vec<_>>();
+
+        let latest = counts.last().unwrap();
+        let mut keys = counts
+            .iter()
+            .flat_map(|counts| counts.keys().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        keys.sort_by_key(|key| std::cmp::Reverse(latest.get(key).copied().unwrap_or(0)));
+
+        let mut rows = keys
+            .iter()
+            .map(|key| {
+                let mut row = vec![key.clone().cell().justify(Justify::Left)];
+                row.extend(
+                    counts
+                        .iter()
+                        .map(|counts| count_cell(counts.get(key).copied().unwrap_or(0))),
+                );
+                row
+            })
+            .collect::<Vec<_>>();
+
+        let mut total = vec!["Total".cell().justify(Justify::Left)];
+        total.extend(entries.iter().map(|entry| count_cell(entry.issues.len())));
+        rows.push(total);
+
+        let mut title = vec![match self.by {
+            GroupBy::Rule => "Rule".cell(),
+            GroupBy::Directory => "Directory".cell(),
+        }];
+        title.extend(entries.iter().map(|entry| {
+            entry.commit_sha[..entry.commit_sha.len().min(7)]
+                .cell()
+                .justify(Justify::Right)
+        }));
+
+        let table = rows
+            .table()
+            .title(title)
+            .border(Border::builder().build())
+            .separator(
+                Separator::builder()
+                    .title(Some(HorizontalLine::default()))
+                    .column(Some(VerticalLine::default()))
+                    .build(),
+            );
+        print_stdout(table)?;
+
+        CommandSuccess::ok()
+    }
+
+    fn counts(&self, entry: &HistoryEntry) -> BTreeMap<String, usize> {
+        match self.by {
+            GroupBy::Rule => entry.counts_by_rule(),
+            GroupBy::Directory => entry.counts_by_directory(self.depth),
+        }
+    }
+}
+
+fn count_cell(count: usize) -> CellStruct {
+    count.to_string().cell().justify(Justify::Right)
+}
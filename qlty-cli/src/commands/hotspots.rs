@@ -0,0 +1,404 @@
+use crate::ui::Steps;
+use crate::{Arguments, CommandError, CommandSuccess};
+use anyhow::Result;
+use chrono::Utc;
+use clap::{Args, ValueEnum};
+use cli_table::{
+    format::{Border, HorizontalLine, Justify, Separator, VerticalLine},
+    print_stdout, Cell, Table,
+};
+use console::Emoji;
+use qlty_analysis::git::Churn;
+use qlty_analysis::workspace_entries::{TargetMode, WorkspaceEntryFinderBuilder};
+use qlty_config::Workspace;
+use qlty_smells::metrics::{Executor, Planner, Settings};
+use qlty_types::analysis::v1::Stats;
+use serde::Serialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::metrics::read_coverage;
+
+static LOOKING_GLASS: Emoji<'_, '_> = Emoji("🔍  ", "");
+static SPARKLES: Emoji<'_, '_> = Emoji("✨  ", "");
+static THINKING: Emoji<'_, '_> = Emoji("🤔  ", "");
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, ValueEnum)]
+pub enum HotspotsFormat {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Args, Debug)]
+pub struct Hotspots {
+    /// Rank functions instead of files
+    #[arg(long)]
+    pub functions: bool,
+
+    /// Coverage report to weigh risk by untested code (e.g., 'lcov:./coverage/lcov.info')
+    #[arg(long, value_name = "REPORT")]
+    pub coverage: Vec<String>,
+
+    /// Number of days of git history to count changes over
+    #[arg(long, default_value = "365")]
+    pub days: u32,
+
+    /// Maximum rows to print
+    #[arg(long, default_value = "20")]
+    pub limit: usize,
+
+    /// Exclude tests
+    #[arg(long)]
+    pub exclude_tests: bool,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    pub format: HotspotsFormat,
+
+    /// Only show results
+    #[arg(long)]
+    pub quiet: bool,
+
+    /// Files to analyze
+    pub paths: Vec<PathBuf>,
+}
+
+/// A file or function ranked by how risky it is to change. The risk is the
+/// CRAP score when coverage is known, or else the cyclomatic complexity,
+/// multiplied by the number of commits that changed the file. When a coverage
+/// report is given, code missing from it counts as untested.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Hotspot {
+    pub name: String,
+    pub path: String,
+    pub commits: usize,
+    pub cyclomatic: u32,
+    pub complexity: u32,
+    pub coverage: Option<f64>,
+    pub crap: Option<f64>,
+    pub score: f64,
+}
+
+impl Hotspot {
+    fn new(stats: &Stats, path: String, commits: usize, with_coverage: bool) -> Self {
+        let cyclomatic = stats.cyclomatic.unwrap_or_default();
+        let (coverage, crap) = if with_coverage && stats.covered_lines.is_none() {
+            // The CRAP score at 0% coverage
+            let complexity = cyclomatic as f64;
+            (Some(0.0), Some(complexity.powi(2) + complexity))
+        } else {
+            (stats.coverage_percent(), stats.crap())
+        };
+        let risk = crap.unwrap_or(cyclomatic as f64);
+
+        Self {
+            name: stats.fully_qualified_name.clone(),
+            path,
+            commits,
+            cyclomatic,
+            complexity: stats.complexity.unwrap_or_default(),
+            coverage,
+            crap,
+            score: risk * commits as f64,
+        }
+    }
+}
+
+impl Hotspots {
+    pub fn execute(&self, _args: &Arguments) -> Result<CommandSuccess, CommandError> {
+        let workspace = Workspace::new()?;
+        let config = workspace.load_config(false)?;
+
+        let mut steps = Steps::new(self.quiet || self.format != HotspotsFormat::Table, 3);
+        steps.start(THINKING, "Planning... ");
+
+        let target_mode = if self.paths.is_empty() {
+            TargetMode::All
+        } else {
+            TargetMode::Paths(self.paths.len())
+        };
+
+        let files = WorkspaceEntryFinderBuilder {
+            mode: target_mode.clone(),
+            paths: self.paths.clone(),
+            config: config.clone(),
+            ..Default::default()
+        }
+        .build()?
+        .files_for_qlty()?;
+
+        let settings = Settings {
+            functions: self.functions,
+            exclude_tests: self.exclude_tests,
+            target_mode,
+            coverage: read_coverage(&workspace, &config, &self.coverage)?,
+        };
+
+        let plan = Planner::new(&config, &settings, files).compute()?;
+
+        steps.start(
+            LOOKING_GLASS,
+            format!("Analyzing {}...", plan.description()),
+        );
+        let results = Executor::new(&plan).execute();
+
+        steps.start(
+            SPARKLES,
+            format!("Counting changes over the last {} days...", self.days),
+        );
+        let since = Utc::now().timestamp() - self.days as i64 * SECONDS_PER_DAY;
+        let churn = Churn::compute(&workspace.root, Some(since), None)?;
+
+        let mut hotspots = rank(
+            &results.stats,
+            &churn,
+            &workspace.root,
+            !self.coverage.is_empty(),
+        );
+        hotspots.truncate(self.limit);
+
+        match self.format {
+            HotspotsFormat::Table => print_table(&hotspots)?,
+            HotspotsFormat::Json => println!("{}", serde_json::to_string_pretty(&hotspots)?),
+            HotspotsFormat::Csv => write_csv(&mut std::io::stdout(), &hotspots)?,
+        }
+
+        CommandSuccess::ok()
+    }
+}
+
+/// Ranks by descending score, leaving out anything that did not change.
+fn rank(stats: &[Stats], churn: &Churn, root: &Path, with_coverage: bool) -> Vec<Hotspot> {
+    let mut hotspots = stats
+        .iter()
+        .filter_map(|stats| {
+            let path = PathBuf::from(&stats.path);
+            let path = path.strip_prefix(root).unwrap_or(&path);
+            let commits = churn.get(path).commits;
+
+            if commits == 0 {
+                None
+            } else {
+                Some(Hotspot::new(
+                    stats,
+                    path.to_string_lossy().to_string(),
+                    commits,
+                    with_coverage,
+                ))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    hotspots.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.path.cmp(&b.path))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    hotspots
+}
+
+fn print_table(hotspots: &[Hotspot]) -> Result<()> {
+    let rows = hotspots
+        .iter()
+        .map(|hotspot| {
+            vec![
+                hotspot.name.clone().cell(),
+                hotspot.path.clone().cell(),
+                hotspot.commits.cell().justify(Justify::Right),
+                hotspot.cyclomatic.cell().justify(Justify::Right),
+                hotspot.complexity.cell().justify(Justify::Right),
+                format_optional(hotspot.coverage)
+                    .cell()
+                    .justify(Justify::Right),
+                format_optional(hotspot.crap).cell().justify(Justify::Right),
+                format!("{:.1}", hotspot.score)
+                    .cell()
+                    .justify(Justify::Right),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let table = rows
+        .table()
+        .title(vec![
+            "name".cell(),
+            "path".cell(),
+            "commits".cell().justify(Justify::Right),
+            "cyclo".cell().justify(Justify::Right),
+            "complex".cell().justify(Justify::Right),
+            "cov %".cell().justify(Justify::Right),
+            "CRAP".cell().justify(Justify::Right),
+            "score".cell().justify(Justify::Right),
+        ])
+        .border(Border::builder().build())
+        .separator(
+            Separator::builder()
+                .title(Some(HorizontalLine::default()))
+                .column(Some(VerticalLine::default()))
+                .build(),
+        );
+
+    println!();
+    print_stdout(table)?;
+    Ok(())
+}
+
+fn write_csv(writer: &mut dyn Write, hotspots: &[Hotspot]) -> Result<()> {
+    writeln!(
+        writer,
+        "name,path,commits,cyclomatic,complexity,coverage,crap,score"
+    )?;
+
+    for hotspot in hotspots {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{},{:.2}",
+            csv_field(&hotspot.name),
+            csv_field(&hotspot.path),
+            hotspot.commits,
+            hotspot.cyclomatic,
+            hotspot.complexity,
+            hotspot
+                .coverage
+                .map(|coverage| format!("{:.2}", coverage))
+                .unwrap_or_default(),
+            hotspot
+                .crap
+                .map(|crap| format!("{:.2}", crap))
+                .unwrap_or_default(),
+            hotspot.score
+        )?;
+    }
+
+    Ok(())
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn format_optional(value: Option<f64>) -> String {
+    value
+        .map(|value| format!("{:.1}", value))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qlty_analysis::git::FileChurn;
+
+    fn stats(path: &str, name: &str, cyclomatic: u32, covered: Option<u32>) -> Stats {
+        Stats {
+            path: path.to_string(),
+            fully_qualified_name: name.to_string(),
+            cyclomatic: Some(cyclomatic),
+            complexity: Some(cyclomatic * 2),
+            covered_lines: covered,
+            uncovered_lines: covered.map(|covered| 4 - covered),
+            ..Default::default()
+        }
+    }
+
+    fn churn(files: &[(&str, usize)]) -> Churn {
+        Churn {
+            files: files
+                .iter()
+                .map(|(path, commits)| {
+                    (
+                        PathBuf::from(path),
+                        FileChurn {
+                            commits: *commits,
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect(),
+            commits: 10,
+        }
+    }
+
+    #[test]
+    fn test_rank() {
+        let hotspots = rank(
+            &[
+                stats("/repo/src/a.rs", "src/a.rs", 4, None),
+                stats("/repo/src/b.rs", "src/b.rs", 2, Some(0)),
+                stats("/repo/src/c.rs", "src/c.rs", 10, Some(4)),
+                stats("/repo/src/unchanged.rs", "src/unchanged.rs", 50, None),
+            ],
+            &churn(&[("src/a.rs", 3), ("src/b.rs", 2), ("src/c.rs", 1)]),
+            Path::new("/repo"),
+            false,
+        );
+
+        let ranked = hotspots
+            .iter()
+            .map(|hotspot| (hotspot.path.as_str(), hotspot.score))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            ranked,
+            vec![("src/a.rs", 12.0), ("src/b.rs", 12.0), ("src/c.rs", 10.0)]
+        );
+        assert_eq!(hotspots[1].crap, Some(6.0));
+        assert_eq!(hotspots[2].coverage, Some(100.0));
+    }
+
+    #[test]
+    fn test_rank_missing_coverage() {
+        let hotspots = rank(
+            &[
+                stats("/repo/src/tested.rs", "src/tested.rs", 4, Some(4)),
+                stats("/repo/src/untested.rs", "src/untested.rs", 4, None),
+            ],
+            &churn(&[("src/tested.rs", 1), ("src/untested.rs", 1)]),
+            Path::new("/repo"),
+            true,
+        );
+
+        let ranked = hotspots
+            .iter()
+            .map(|hotspot| (hotspot.path.as_str(), hotspot.score))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            ranked,
+            vec![("src/untested.rs", 20.0), ("src/tested.rs", 4.0)]
+        );
+        assert_eq!(hotspots[0].coverage, Some(0.0));
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let hotspot = Hotspot {
+            name: "Parser#parse, \"fast\"".to_string(),
+            path: "src/parser.rb".to_string(),
+            commits: 3,
+            cyclomatic: 2,
+            complexity: 4,
+            coverage: Some(50.0),
+            crap: Some(2.5),
+            score: 7.5,
+        };
+
+        let mut output = vec![];
+        write_csv(&mut output, &[hotspot]).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "name,path,commits,cyclomatic,complexity,coverage,crap,score\n\"Parser#parse, \"\"fast\"\"\",src/parser.rb,3,2,4,50.00,2.50,7.50\n"
+        );
+    }
+}
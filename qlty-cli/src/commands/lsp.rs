@@ -0,0 +1,38 @@
+use crate::lsp::{QltyBackend, Server};
+use crate::{Arguments, CommandError, CommandSuccess};
+use anyhow::Result;
+use clap::Args;
+use qlty_config::Workspace;
+
+#[derive(Args, Debug)]
+pub struct Lsp {
+    /// Offer unsafe fixes as code actions
+    #[arg(long)]
+    pub r#unsafe: bool,
+
+    /// Skip fetching sources before starting the server
+    #[arg(long)]
+    skip_source_fetch: bool,
+}
+
+impl Lsp {
+    pub fn execute(&self, _args: &Arguments) -> Result<CommandSuccess, CommandError> {
+        let workspace = Workspace::require_initialized()?;
+        workspace.prepare_sources(self.skip_source_fetch)?;
+
+        let root = Workspace::assert_within_git_directory()?;
+        let stdin = std::io::stdin();
+        let stdout = std::io::stdout();
+
+        Server::new(
+            root.clone(),
+            QltyBackend::new(root),
+            stdin.lock(),
+            stdout.lock(),
+        )
+        .allow_unsafe(self.r#unsafe)
+        .run()?;
+
+        CommandSuccess::ok()
+    }
+}
@@ -11,11 +11,16 @@ use console::Emoji;
 use qlty_analysis::git::compute_upstream;
 use qlty_analysis::workspace_entries::{TargetMode, WorkspaceEntryFinderBuilder};
 use qlty_analysis::Report;
-use qlty_config::Workspace;
+use qlty_config::{QltyConfig, Workspace};
+use qlty_coverage::transform;
 use qlty_smells::metrics::{Executor, MetricsMode, Plan, Planner, Processor, Settings};
 use qlty_types::analysis::v1::Stats;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use super::coverage::read_file_coverages;
+
 static EYES: Emoji<'_, '_> = Emoji("👀  ", "");
 static LOOKING_GLASS: Emoji<'_, '_> = Emoji("🔍  ", "");
 static SPARKLES: Emoji<'_, '_> = Emoji("✨  ", "");
@@ -37,6 +42,10 @@ pub struct Metrics {
     #[arg(long, conflicts_with = "dirs")]
     pub functions: bool,
 
+    /// Coverage report to show coverage and CRAP scores of functions (e.g., 'lcov:./coverage/lcov.info')
+    #[arg(long, value_name = "REPORT", requires = "functions")]
+    pub coverage: Vec<String>,
+
     /// Directory depth to print, this flag will also set to print per-directory stats
     #[arg(long, conflicts_with = "functions")]
     pub max_depth: Option<usize>,
@@ -79,6 +88,10 @@ pub enum Sort {
     Loc,
     Complexity,
     Lcom,
+    Volume,
+    Difficulty,
+    Effort,
+    Maintainability,
 }
 
 impl Metrics {
@@ -104,6 +117,7 @@ impl Metrics {
             functions: self.functions,
             exclude_tests: self.exclude_tests,
             target_mode: target_mode.clone(),
+            coverage: read_coverage(&workspace, &config, &self.coverage)?,
         };
 
         let planner = Planner::new(&config, &settings, files);
@@ -210,12 +224,36 @@ impl Metrics {
     fn print_text(&self, mode: MetricsMode, report: &Report) -> Result<()> {
         match mode {
             MetricsMode::Files => print_tabular_report(self, report),
-            MetricsMode::Functions => print_functions_report(report),
+            MetricsMode::Functions => print_functions_report(report, !self.coverage.is_empty()),
         }
     }
 }
 
-fn print_functions_report(report: &Report) -> Result<()> {
+/// Reads coverage reports into line hits by the absolute path of each file.
+pub fn read_coverage(
+    workspace: &Workspace,
+    config: &QltyConfig,
+    reports: &[String],
+) -> Result<HashMap<PathBuf, Vec<i64>>> {
+    let settings = transform::Settings {
+        path: String::new(),
+        report_format: None,
+        add_prefix: None,
+        strip_prefix: None,
+        ignores: vec![],
+        pragmas: false,
+        ignore_marker: None,
+    };
+
+    // Coverage paths are relative to the root of the repository, while
+    // source files are analyzed by their absolute path
+    Ok(read_file_coverages(config, &settings, reports)?
+        .into_iter()
+        .map(|file_coverage| (workspace.root.join(&file_coverage.path), file_coverage.hits))
+        .collect())
+}
+
+fn print_functions_report(report: &Report, coverage: bool) -> Result<()> {
     let cwd = std::env::current_dir()?;
 
     for (path, function_stats) in report.function_stats_by_path() {
@@ -225,26 +263,48 @@ fn print_functions_report(report: &Report) -> Result<()> {
             let mut rows = vec![];
 
             for stats in function_stats {
-                rows.push(vec![
-                    style(stats.name).cyan().cell(),
+                let mut row = vec![
+                    style(&stats.name).cyan().cell(),
                     stats.fields.unwrap().cell().justify(Justify::Right),
                     stats.cyclomatic.unwrap().cell().justify(Justify::Right),
                     stats.complexity.unwrap().cell().justify(Justify::Right),
                     stats.lines.unwrap().cell().justify(Justify::Right),
                     stats.code_lines.unwrap().cell().justify(Justify::Right),
-                ]);
+                    format_optional(stats.maintainability_index)
+                        .cell()
+                        .justify(Justify::Right),
+                ];
+
+                if coverage {
+                    row.push(
+                        format_optional(stats.coverage_percent())
+                            .cell()
+                            .justify(Justify::Right),
+                    );
+                    row.push(format_optional(stats.crap()).cell().justify(Justify::Right));
+                }
+
+                rows.push(row);
+            }
+
+            let mut title = vec![
+                "function".cell(),
+                "fields".cell().justify(Justify::Right),
+                "cyclo".cell().justify(Justify::Right),
+                "cognitive".cell().justify(Justify::Right),
+                "lines".cell().justify(Justify::Right),
+                "loc".cell().justify(Justify::Right),
+                "MI".cell().justify(Justify::Right),
+            ];
+
+            if coverage {
+                title.push("cov %".cell().justify(Justify::Right));
+                title.push("CRAP".cell().justify(Justify::Right));
             }
 
             let table = rows
                 .table()
-                .title(vec![
-                    "function".cell(),
-                    "fields".cell().justify(Justify::Right),
-                    "cyclo".cell().justify(Justify::Right),
-                    "cognitive".cell().justify(Justify::Right),
-                    "lines".cell().justify(Justify::Right),
-                    "loc".cell().justify(Justify::Right),
-                ])
+                .title(title)
                 .border(Border::builder().build())
                 .separator(
                     Separator::builder()
@@ -291,6 +351,15 @@ pub fn print_tabular_report(arguments: &Metrics, report: &Report) -> Result<()>
         Some(Sort::Loc) => stats_b.code_lines.cmp(&stats_a.code_lines),
         Some(Sort::Complexity) => stats_b.complexity.cmp(&stats_a.complexity),
         Some(Sort::Lcom) => stats_b.lcom4.cmp(&stats_a.lcom4),
+        Some(Sort::Volume) => compare_optional(stats_b.halstead_volume, stats_a.halstead_volume),
+        Some(Sort::Difficulty) => {
+            compare_optional(stats_b.halstead_difficulty, stats_a.halstead_difficulty)
+        }
+        Some(Sort::Effort) => compare_optional(stats_b.halstead_effort, stats_a.halstead_effort),
+        // Least maintainable first
+        Some(Sort::Maintainability) => {
+            compare_optional(stats_a.maintainability_index, stats_b.maintainability_index)
+        }
         None => stats_a
             .fully_qualified_name
             .cmp(&stats_b.fully_qualified_name),
@@ -316,6 +385,18 @@ pub fn print_tabular_report(arguments: &Metrics, report: &Report) -> Result<()>
                 stats.lcom4.unwrap().cell().justify(Justify::Right),
                 stats.lines.unwrap().cell().justify(Justify::Right),
                 stats.code_lines.unwrap().cell().justify(Justify::Right),
+                format_rounded(stats.halstead_volume)
+                    .cell()
+                    .justify(Justify::Right),
+                format_optional(stats.halstead_difficulty)
+                    .cell()
+                    .justify(Justify::Right),
+                format_rounded(stats.halstead_effort)
+                    .cell()
+                    .justify(Justify::Right),
+                format_optional(stats.maintainability_index)
+                    .cell()
+                    .justify(Justify::Right),
             ]
         })
         .collect();
@@ -369,6 +450,18 @@ pub fn print_tabular_report(arguments: &Metrics, report: &Report) -> Result<()>
                 .unwrap_or_default()
                 .cell()
                 .justify(Justify::Right),
+            format_rounded(total.halstead_volume)
+                .cell()
+                .justify(Justify::Right),
+            format_optional(total.halstead_difficulty)
+                .cell()
+                .justify(Justify::Right),
+            format_rounded(total.halstead_effort)
+                .cell()
+                .justify(Justify::Right),
+            format_optional(total.maintainability_index)
+                .cell()
+                .justify(Justify::Right),
         ]);
     }
 
@@ -384,6 +477,10 @@ pub fn print_tabular_report(arguments: &Metrics, report: &Report) -> Result<()>
             "LCOM".cell().justify(Justify::Right),
             "lines".cell().justify(Justify::Right),
             "LOC".cell().justify(Justify::Right),
+            "volume".cell().justify(Justify::Right),
+            "diff".cell().justify(Justify::Right),
+            "effort".cell().justify(Justify::Right),
+            "MI".cell().justify(Justify::Right),
         ])
         .border(Border::builder().build())
         .separator(
@@ -399,6 +496,22 @@ pub fn print_tabular_report(arguments: &Metrics, report: &Report) -> Result<()>
     Ok(())
 }
 
+fn format_optional(value: Option<f64>) -> String {
+    value
+        .map(|value| format!("{:.1}", value))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn format_rounded(value: Option<f64>) -> String {
+    value
+        .map(|value| format!("{:.0}", value))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn compare_optional(a: Option<f64>, b: Option<f64>) -> Ordering {
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
+
 fn print_path(path: &Path, root: &PathBuf) {
     let path = path.strip_prefix(root).unwrap_or(path);
     println!("{}", style(path.display()).magenta().bold());
@@ -1,4 +1,4 @@
-use crate::format::SarifFormatter;
+use crate::format::OutputFormat;
 use crate::ui::Highlighter;
 use crate::ui::Steps;
 use crate::{Arguments, CommandError, CommandSuccess};
@@ -13,7 +13,10 @@ use qlty_analysis::workspace_entries::WorkspaceEntryFinderBuilder;
 use qlty_analysis::Report;
 use qlty_config::{QltyConfig, Workspace};
 use qlty_types::analysis::v1::Issue;
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 static EYES: Emoji<'_, '_> = Emoji("👀  ", "");
 static LOOKING_GLASS: Emoji<'_, '_> = Emoji("🔍  ", "");
@@ -47,13 +50,17 @@ pub struct Smells {
     pub quiet: bool,
 
     /// JSON output
-    #[arg(long, hide = true, conflicts_with = "sarif")]
+    #[arg(long, hide = true, conflicts_with_all = ["sarif", "format"])]
     json: bool,
 
     /// SARIF output
-    #[arg(long, conflicts_with = "json")]
+    #[arg(long, conflicts_with_all = ["json", "format"])]
     sarif: bool,
 
+    /// Output format (sarif, junit, checkstyle or codeclimate)
+    #[arg(long, value_enum, conflicts_with_all = ["json", "sarif"])]
+    format: Option<OutputFormat>,
+
     /// Files to analyze
     pub paths: Vec<PathBuf>,
 }
@@ -89,7 +96,7 @@ impl Smells {
             EYES,
             format!("Checking structure of {} files... ", files.len()),
         );
-        let mut report = self.run_structure(&config, &files)?;
+        let mut report = self.run_structure(&config, &workspace.root, &files)?;
 
         if !self.no_duplication {
             steps.start(
@@ -149,8 +156,13 @@ impl Smells {
         Ok(())
     }
 
-    fn run_structure(&self, config: &QltyConfig, files: &[Arc<File>]) -> Result<Report> {
-        let planner = qlty_smells::structure::Planner::new(config, files.to_vec())?;
+    fn run_structure(
+        &self,
+        config: &QltyConfig,
+        root: &Path,
+        files: &[Arc<File>],
+    ) -> Result<Report> {
+        let planner = qlty_smells::structure::Planner::new(config, root, files.to_vec())?;
         let plan = planner.compute()?;
 
         let mut executor = qlty_smells::structure::Executor::new(&plan);
@@ -210,11 +222,19 @@ impl Smells {
         Ok(executor.report())
     }
 
+    fn output_format(&self) -> Option<OutputFormat> {
+        if self.sarif {
+            Some(OutputFormat::Sarif)
+        } else {
+            self.format
+        }
+    }
+
     fn write_stdout(&self, workspace: &Workspace, report: &Report) -> Result<()> {
         if self.json {
             self.write_stdout_json(&report.issues)
-        } else if self.sarif {
-            let formatter = SarifFormatter::boxed(report.messages.clone(), report.issues.clone());
+        } else if let Some(format) = self.output_format() {
+            let formatter = format.formatter(report.messages.clone(), report.issues.clone());
             formatter.write_to(&mut std::io::stdout())?;
             Ok(())
         } else {
@@ -0,0 +1,126 @@
+use super::xml::escape;
+use anyhow::Result;
+use qlty_formats::Formatter;
+use qlty_types::analysis::v1::{Issue, Level};
+use std::collections::BTreeMap;
+use std::io::Write;
+
+const CHECKSTYLE_VERSION: &str = "4.3";
+
+/// Writes issues as a Checkstyle XML report, grouped by file.
+#[derive(Debug)]
+pub struct CheckstyleFormatter {
+    pub issues: Vec<Issue>,
+}
+
+impl CheckstyleFormatter {
+    pub fn new(issues: Vec<Issue>) -> Self {
+        Self { issues }
+    }
+
+    pub fn boxed(issues: Vec<Issue>) -> Box<dyn Formatter> {
+        Box::new(Self::new(issues))
+    }
+
+    fn files(&self) -> BTreeMap<String, Vec<&Issue>> {
+        let mut files: BTreeMap<String, Vec<&Issue>> = BTreeMap::new();
+
+        // Checkstyle has no way to represent issues which are not in a file
+        for issue in &self.issues {
+            if let Some(path) = issue.path() {
+                files.entry(path).or_default().push(issue);
+            }
+        }
+
+        for issues in files.values_mut() {
+            issues.sort_by_key(|issue| {
+                let range = issue.range().unwrap_or_default();
+                (range.start_line, range.start_column, issue.rule_key.clone())
+            });
+        }
+
+        files
+    }
+}
+
+impl Formatter for CheckstyleFormatter {
+    fn write_to(&self, writer: &mut dyn Write) -> Result<()> {
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(writer, r#"<checkstyle version="{}">"#, CHECKSTYLE_VERSION)?;
+
+        for (path, issues) in self.files() {
+            writeln!(writer, r#"  <file name="{}">"#, escape(&path))?;
+
+            for issue in issues {
+                let range = issue.range().unwrap_or_default();
+
+                writeln!(
+                    writer,
+                    r#"    <error line="{}" column="{}" severity="{}" message="{}" source="{}"/>"#,
+                    range.start_line,
+                    range.start_column,
+                    severity(issue.level()),
+                    escape(&issue.message),
+                    escape(&format!("{}:{}", issue.tool, issue.rule_key))
+                )?;
+            }
+
+            writeln!(writer, "  </file>")?;
+        }
+
+        writeln!(writer, "</checkstyle>")?;
+        Ok(())
+    }
+}
+
+fn severity(level: Level) -> &'static str {
+    match level {
+        Level::High => "error",
+        Level::Medium => "warning",
+        Level::Low | Level::Fmt | Level::Note | Level::Unspecified => "info",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::{Location, Range};
+
+    fn issue(rule_key: &str, level: Level, path: &str, line: u32) -> Issue {
+        Issue {
+            tool: "ruff".to_string(),
+            rule_key: rule_key.to_string(),
+            message: format!("Found \"{}\" & more", rule_key),
+            level: level.into(),
+            location: Some(Location {
+                path: path.to_string(),
+                range: Some(Range {
+                    start_line: line,
+                    start_column: 1,
+                    end_line: line,
+                    end_column: 4,
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_checkstyle_formatter() {
+        let formatter = CheckstyleFormatter::boxed(vec![
+            issue("E501", Level::Low, "app/main.py", 12),
+            issue("F401", Level::High, "app/main.py", 1),
+            issue("B006", Level::Medium, "app/models.py", 7),
+            Issue {
+                tool: "trufflehog".to_string(),
+                rule_key: "secret".to_string(),
+                message: "Not tied to a file".to_string(),
+                ..Default::default()
+            },
+        ]);
+
+        let output = String::from_utf8(formatter.read().unwrap()).unwrap();
+        insta::assert_snapshot!(output);
+    }
+}
@@ -0,0 +1,180 @@
+use anyhow::Result;
+use qlty_check::baseline::Baseline;
+use qlty_formats::Formatter;
+use qlty_types::analysis::v1::{Category, Issue, Level};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::Write;
+
+/// Writes issues in the CodeClimate JSON format, which GitLab renders as a
+/// Code Quality report on merge requests.
+#[derive(Debug)]
+pub struct CodeClimateFormatter {
+    pub issues: Vec<Issue>,
+}
+
+impl CodeClimateFormatter {
+    pub fn new(issues: Vec<Issue>) -> Self {
+        Self { issues }
+    }
+
+    pub fn boxed(issues: Vec<Issue>) -> Box<dyn Formatter> {
+        Box::new(Self::new(issues))
+    }
+
+    fn serialize_issue(&self, issue: &Issue, fingerprint: String) -> Value {
+        let range = issue.range().unwrap_or_default();
+
+        let mut result = json!({
+            "type": "issue",
+            "check_name": issue.rule_id(),
+            "description": issue.message,
+            "categories": [category(issue.category())],
+            "severity": severity(issue.level()),
+            "fingerprint": fingerprint,
+            "location": {
+                "path": issue.path().unwrap_or_default(),
+                "lines": {
+                    "begin": range.start_line,
+                    "end": range.end_line.max(range.start_line),
+                },
+            },
+        });
+
+        if !issue.documentation_url.is_empty() {
+            result["content"] = json!({ "body": issue.documentation_url });
+        }
+
+        result
+    }
+}
+
+impl Formatter for CodeClimateFormatter {
+    fn write_to(&self, writer: &mut dyn Write) -> Result<()> {
+        let mut issues = self.issues.iter().collect::<Vec<_>>();
+        issues.sort();
+
+        let results = issues
+            .iter()
+            .zip(fingerprints(&issues))
+            .map(|(issue, fingerprint)| self.serialize_issue(issue, fingerprint))
+            .collect::<Vec<_>>();
+
+        let json = serde_json::to_string_pretty(&results)?;
+        writer.write_all(json.as_bytes())?;
+        writer.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+/// Fingerprints ignore line numbers so issues can be matched between the
+/// source and target branches of a merge request. Repeated occurrences of the
+/// same issue in a file are told apart by their order of appearance.
+fn fingerprints(issues: &[&Issue]) -> Vec<String> {
+    let mut occurrences: HashMap<String, usize> = HashMap::new();
+
+    issues
+        .iter()
+        .map(|issue| {
+            let fingerprint = Baseline::fingerprint(issue);
+            let occurrence = occurrences.entry(fingerprint.clone()).or_default();
+            *occurrence += 1;
+
+            format!(
+                "{:x}",
+                md5::compute(format!("{}:{}", fingerprint, occurrence))
+            )
+        })
+        .collect()
+}
+
+fn severity(level: Level) -> &'static str {
+    match level {
+        Level::High => "critical",
+        Level::Medium => "major",
+        Level::Low => "minor",
+        Level::Fmt | Level::Note | Level::Unspecified => "info",
+    }
+}
+
+fn category(category: Category) -> &'static str {
+    match category {
+        Category::Bug | Category::TypeCheck | Category::AntiPattern => "Bug Risk",
+        Category::Vulnerability
+        | Category::SecurityHotspot
+        | Category::Secret
+        | Category::DependencyAlert => "Security",
+        Category::Structure => "Complexity",
+        Category::Duplication => "Duplication",
+        Category::Performance => "Performance",
+        Category::Documentation | Category::DeadCode => "Clarity",
+        Category::Accessibility => "Compatibility",
+        Category::Style | Category::Lint | Category::Unspecified => "Style",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::{Location, Range};
+
+    fn issue(line: u32) -> Issue {
+        Issue {
+            tool: "eslint".to_string(),
+            rule_key: "no-console".to_string(),
+            message: "Unexpected console statement".to_string(),
+            level: Level::Medium.into(),
+            category: Category::Lint.into(),
+            snippet: "console.log(value);".to_string(),
+            location: Some(Location {
+                path: "src/index.js".to_string(),
+                range: Some(Range {
+                    start_line: line,
+                    end_line: line,
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn output(issues: Vec<Issue>) -> Value {
+        let output = CodeClimateFormatter::new(issues).read().unwrap();
+        serde_json::from_slice(&output).unwrap()
+    }
+
+    #[test]
+    fn test_codeclimate_formatter() {
+        let mut security = issue(3);
+        security.tool = "semgrep".to_string();
+        security.rule_key = "eval".to_string();
+        security.level = Level::High.into();
+        security.category = Category::Vulnerability.into();
+        security.documentation_url = "https://semgrep.dev/r/eval".to_string();
+
+        let json = output(vec![issue(10), security]);
+
+        assert_eq!(json.as_array().unwrap().len(), 2);
+        assert_eq!(json[0]["check_name"], "semgrep:eval");
+        assert_eq!(json[0]["severity"], "critical");
+        assert_eq!(json[0]["categories"], json!(["Security"]));
+        assert_eq!(json[0]["content"]["body"], "https://semgrep.dev/r/eval");
+        assert_eq!(json[1]["check_name"], "eslint:no-console");
+        assert_eq!(json[1]["severity"], "major");
+        assert_eq!(json[1]["categories"], json!(["Style"]));
+        assert_eq!(
+            json[1]["location"],
+            json!({ "path": "src/index.js", "lines": { "begin": 10, "end": 10 } })
+        );
+    }
+
+    #[test]
+    fn test_fingerprints_are_stable() {
+        let before = output(vec![issue(10), issue(20)]);
+        let after = output(vec![issue(14), issue(24)]);
+
+        assert_eq!(before[0]["fingerprint"], after[0]["fingerprint"]);
+        assert_eq!(before[1]["fingerprint"], after[1]["fingerprint"]);
+        assert_ne!(before[0]["fingerprint"], before[1]["fingerprint"]);
+    }
+}
@@ -0,0 +1,176 @@
+use super::xml::escape;
+use anyhow::Result;
+use qlty_formats::Formatter;
+use qlty_types::analysis::v1::{Issue, Level};
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// Writes issues as a JUnit XML test report, with one test suite for each
+/// plugin and driver, and one failing test case for each issue.
+#[derive(Debug)]
+pub struct JunitFormatter {
+    pub issues: Vec<Issue>,
+}
+
+impl JunitFormatter {
+    pub fn new(issues: Vec<Issue>) -> Self {
+        Self { issues }
+    }
+
+    pub fn boxed(issues: Vec<Issue>) -> Box<dyn Formatter> {
+        Box::new(Self::new(issues))
+    }
+
+    fn suites(&self) -> BTreeMap<String, Vec<&Issue>> {
+        let mut suites: BTreeMap<String, Vec<&Issue>> = BTreeMap::new();
+
+        for issue in &self.issues {
+            suites.entry(suite_name(issue)).or_default().push(issue);
+        }
+
+        for issues in suites.values_mut() {
+            issues.sort_by_key(|issue| {
+                (
+                    issue.path(),
+                    issue.range().unwrap_or_default().start_line,
+                    issue.rule_key.clone(),
+                )
+            });
+        }
+
+        suites
+    }
+
+    fn write_test_case(&self, writer: &mut dyn Write, issue: &Issue) -> Result<()> {
+        let path = issue.path().unwrap_or_default();
+        let range = issue.range().unwrap_or_default();
+
+        writeln!(
+            writer,
+            r#"    <testcase name="{}" classname="{}" file="{}" line="{}">"#,
+            escape(&issue.rule_key),
+            escape(&path),
+            escape(&path),
+            range.start_line
+        )?;
+
+        let mut details = format!("{}:{}:{}", path, range.start_line, range.start_column);
+
+        if !issue.documentation_url.is_empty() {
+            details.push('\n');
+            details.push_str(&issue.documentation_url);
+        }
+
+        writeln!(
+            writer,
+            r#"      <failure message="{}" type="{}">{}</failure>"#,
+            escape(&issue.message),
+            level_name(issue.level()),
+            escape(&details)
+        )?;
+        writeln!(writer, "    </testcase>")?;
+
+        Ok(())
+    }
+}
+
+impl Formatter for JunitFormatter {
+    fn write_to(&self, writer: &mut dyn Write) -> Result<()> {
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            writer,
+            r#"<testsuites name="qlty" tests="{count}" failures="{count}">"#,
+            count = self.issues.len()
+        )?;
+
+        for (name, issues) in self.suites() {
+            writeln!(
+                writer,
+                r#"  <testsuite name="{name}" tests="{count}" failures="{count}">"#,
+                name = escape(&name),
+                count = issues.len()
+            )?;
+
+            for issue in issues {
+                self.write_test_case(writer, issue)?;
+            }
+
+            writeln!(writer, "  </testsuite>")?;
+        }
+
+        writeln!(writer, "</testsuites>")?;
+        Ok(())
+    }
+}
+
+fn suite_name(issue: &Issue) -> String {
+    if issue.driver.is_empty() {
+        issue.tool.clone()
+    } else {
+        format!("{}:{}", issue.tool, issue.driver)
+    }
+}
+
+fn level_name(level: Level) -> &'static str {
+    match level {
+        Level::Unspecified => "unspecified",
+        Level::Note => "note",
+        Level::Fmt => "fmt",
+        Level::Low => "low",
+        Level::Medium => "medium",
+        Level::High => "high",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::{Location, Range};
+
+    fn issue(tool: &str, driver: &str, rule_key: &str, path: &str, line: u32) -> Issue {
+        Issue {
+            tool: tool.to_string(),
+            driver: driver.to_string(),
+            rule_key: rule_key.to_string(),
+            message: format!("Violation of <{}>", rule_key),
+            level: Level::Medium.into(),
+            location: Some(Location {
+                path: path.to_string(),
+                range: Some(Range {
+                    start_line: line,
+                    start_column: 3,
+                    end_line: line,
+                    end_column: 8,
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_junit_formatter() {
+        let mut documented = issue("eslint", "lint", "no-var", "src/b.js", 4);
+        documented.documentation_url = "https://eslint.org/docs/rules/no-var".to_string();
+        documented.level = Level::High.into();
+
+        let formatter = JunitFormatter::boxed(vec![
+            documented,
+            issue("eslint", "lint", "eqeqeq", "src/a.js", 10),
+            issue("shellcheck", "", "SC2086", "bin/run.sh", 1),
+        ]);
+
+        let output = String::from_utf8(formatter.read().unwrap()).unwrap();
+        insta::assert_snapshot!(output);
+    }
+
+    #[test]
+    fn test_junit_formatter_without_issues() {
+        let output = String::from_utf8(JunitFormatter::new(vec![]).read().unwrap()).unwrap();
+
+        assert_eq!(
+            output,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"qlty\" tests=\"0\" failures=\"0\">\n</testsuites>\n"
+        );
+    }
+}
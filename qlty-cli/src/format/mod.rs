@@ -1,3 +1,33 @@
+mod checkstyle;
+mod codeclimate;
+mod junit;
 mod sarif;
+mod xml;
 
+pub use checkstyle::CheckstyleFormatter;
+pub use codeclimate::CodeClimateFormatter;
+pub use junit::JunitFormatter;
 pub use sarif::SarifFormatter;
+
+use qlty_formats::Formatter;
+use qlty_types::analysis::v1::{Issue, Message};
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Sarif,
+    Junit,
+    Checkstyle,
+    #[value(alias = "gitlab")]
+    Codeclimate,
+}
+
+impl OutputFormat {
+    pub fn formatter(&self, messages: Vec<Message>, issues: Vec<Issue>) -> Box<dyn Formatter> {
+        match self {
+            OutputFormat::Sarif => SarifFormatter::boxed(messages, issues),
+            OutputFormat::Junit => JunitFormatter::boxed(issues),
+            OutputFormat::Checkstyle => CheckstyleFormatter::boxed(issues),
+            OutputFormat::Codeclimate => CodeClimateFormatter::boxed(issues),
+        }
+    }
+}
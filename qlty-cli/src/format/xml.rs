@@ -0,0 +1,33 @@
+/// Escapes text for use in XML attributes and character data, dropping
+/// control characters which are not allowed in XML 1.0 documents.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape("if a < b && c > \"d\" then 'e'\u{1b}"),
+            "if a &lt; b &amp;&amp; c &gt; &quot;d&quot; then &apos;e&apos;"
+        );
+    }
+}
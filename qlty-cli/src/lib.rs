@@ -9,11 +9,13 @@ pub mod export;
 pub mod format;
 mod initializer;
 mod logging;
+mod lsp;
 pub mod shell;
 mod success;
 mod telemetry;
 mod ui;
 mod upgrade;
+mod watch;
 
 pub use arguments::Arguments;
 pub use auth::{clear_auth_token, load_or_retrieve_auth_token, store_auth_token};
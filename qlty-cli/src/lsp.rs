@@ -0,0 +1,72 @@
+mod diagnostics;
+mod server;
+mod shadow;
+mod transport;
+
+pub use server::Server;
+
+use anyhow::Result;
+use qlty_check::{planner::Planner, Executor, Processor, Settings};
+use qlty_config::config::CheckTrigger;
+use qlty_types::analysis::v1::{ExecutionVerb, Issue};
+use shadow::ShadowWorkspace;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+
+/// Runs checks and formatters on behalf of the language server, against the
+/// contents of the editor's buffer rather than the file on disk.
+pub trait Backend: Debug {
+    /// Returns the issues for a path relative to the workspace root.
+    fn check(&self, path: &Path, contents: &str) -> Result<Vec<Issue>>;
+
+    /// Returns the formatted contents for a path relative to the workspace root.
+    fn format(&self, path: &Path, contents: &str) -> Result<String>;
+}
+
+#[derive(Debug, Clone)]
+pub struct QltyBackend {
+    root: PathBuf,
+}
+
+impl QltyBackend {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn settings(root: &Path, path: &Path) -> Settings {
+        Settings {
+            root: root.to_path_buf(),
+            paths: vec![root.join(path)],
+            progress: false,
+            fail_level: None,
+            trigger: CheckTrigger::Ide,
+            // Each run uses a new shadow workspace, so nothing could be reused
+            cache: false,
+            ..Default::default()
+        }
+    }
+
+    fn run(verb: ExecutionVerb, shadow: &ShadowWorkspace, path: &Path) -> Result<Vec<Issue>> {
+        let plan = Planner::new(verb, &Self::settings(shadow.root(), path))?.compute()?;
+        let executor = Executor::new(&plan);
+        let results = executor.install_and_invoke()?;
+
+        let mut processor = Processor::new(&plan, results);
+        let report = processor.compute()?;
+
+        Ok(report.issues)
+    }
+}
+
+impl Backend for QltyBackend {
+    fn check(&self, path: &Path, contents: &str) -> Result<Vec<Issue>> {
+        let shadow = ShadowWorkspace::new(&self.root, path, contents)?;
+        Self::run(ExecutionVerb::Check, &shadow, path)
+    }
+
+    fn format(&self, path: &Path, contents: &str) -> Result<String> {
+        let shadow = ShadowWorkspace::new(&self.root, path, contents)?;
+        Self::run(ExecutionVerb::Fmt, &shadow, path)?;
+        shadow.read(path)
+    }
+}
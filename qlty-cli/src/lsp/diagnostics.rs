@@ -0,0 +1,315 @@
+use qlty_check::patcher::Patcher;
+use qlty_types::analysis::v1::{Issue, Level, Range};
+use serde_json::{json, Value};
+use similar::{DiffOp, TextDiff};
+
+const SOURCE: &str = "qlty";
+
+pub fn issue_to_diagnostic(issue: &Issue) -> Value {
+    let mut diagnostic = json!({
+        "range": issue_range(issue),
+        "severity": severity(issue.level()),
+        "code": format!("{}:{}", issue.tool, issue.rule_key),
+        "source": SOURCE,
+        "message": issue.message,
+    });
+
+    if !issue.documentation_url.is_empty() {
+        diagnostic["codeDescription"] = json!({ "href": issue.documentation_url });
+    }
+
+    diagnostic
+}
+
+/// Builds quick fix code actions for the patchable issues overlapping the
+/// requested line range. Each action applies the issue's patch to the buffer,
+/// which keeps the edits consistent with `qlty check --fix`.
+pub fn code_actions(
+    uri: &str,
+    source: &str,
+    issues: &[Issue],
+    start_line: u32,
+    end_line: u32,
+    allow_unsafe: bool,
+) -> Vec<Value> {
+    issues
+        .iter()
+        .filter(|issue| Patcher::is_patchable(issue, allow_unsafe))
+        .filter(|issue| {
+            issue.range().is_some_and(|range| {
+                let (start, end) = zero_based_lines(&range);
+                start <= end_line && end >= start_line
+            })
+        })
+        .filter_map(|issue| {
+            let suggestion = &issue.suggestions[0];
+            let patch = diffy::Patch::from_str(&suggestion.patch).ok()?;
+            let patched = diffy::apply(source, &patch).ok()?;
+
+            if patched == source {
+                return None;
+            }
+
+            let title = if suggestion.description.is_empty() {
+                format!("Fix {}:{}", issue.tool, issue.rule_key)
+            } else {
+                suggestion.description.clone()
+            };
+
+            Some(json!({
+                "title": title,
+                "kind": "quickfix",
+                "diagnostics": [issue_to_diagnostic(issue)],
+                "edit": {
+                    "changes": {
+                        uri: text_edits(source, &patched)
+                    }
+                }
+            }))
+        })
+        .collect()
+}
+
+/// Returns the edits turning `original` into `replacement`, one per run of
+/// changed lines, so that the editor keeps its cursor and folding state.
+pub fn text_edits(original: &str, replacement: &str) -> Vec<Value> {
+    let diff = TextDiff::from_lines(original, replacement);
+    let old_lines = diff.old_slices();
+    let new_lines = diff.new_slices();
+
+    let mut edits = vec![];
+    let mut pending: Option<(usize, usize, usize, usize)> = None;
+
+    for op in diff.ops() {
+        if let DiffOp::Equal { .. } = op {
+            if let Some(change) = pending.take() {
+                edits.push(text_edit(old_lines, new_lines, change));
+            }
+            continue;
+        }
+
+        let (old_range, new_range) = (op.old_range(), op.new_range());
+
+        pending = Some(match pending {
+            Some((old_start, _, new_start, _)) => {
+                (old_start, old_range.end, new_start, new_range.end)
+            }
+            None => (
+                old_range.start,
+                old_range.end,
+                new_range.start,
+                new_range.end,
+            ),
+        });
+    }
+
+    if let Some(change) = pending {
+        edits.push(text_edit(old_lines, new_lines, change));
+    }
+
+    edits
+}
+
+fn text_edit(
+    old_lines: &[&str],
+    new_lines: &[&str],
+    (old_start, old_end, new_start, new_end): (usize, usize, usize, usize),
+) -> Value {
+    json!({
+        "range": {
+            "start": line_position(old_lines, old_start),
+            "end": line_position(old_lines, old_end),
+        },
+        "newText": new_lines[new_start..new_end].concat(),
+    })
+}
+
+// The position at the start of a line, or at the end of the document when a
+// last line without a trailing newline is included
+fn line_position(lines: &[&str], index: usize) -> Value {
+    match lines.last() {
+        Some(last) if index == lines.len() && !last.ends_with('\n') => json!({
+            "line": index - 1,
+            "character": last.encode_utf16().count(),
+        }),
+        _ => json!({ "line": index, "character": 0 }),
+    }
+}
+
+fn issue_range(issue: &Issue) -> Value {
+    match issue.range() {
+        Some(range) => {
+            let (start_line, end_line) = zero_based_lines(&range);
+            let start_character = range.start_column.saturating_sub(1);
+
+            // Without an end column the diagnostic spans to the end of the line
+            let end = if range.end_column == 0 {
+                json!({ "line": end_line + 1, "character": 0 })
+            } else {
+                json!({ "line": end_line, "character": range.end_column.saturating_sub(1) })
+            };
+
+            json!({
+                "start": { "line": start_line, "character": start_character },
+                "end": end,
+            })
+        }
+        None => json!({
+            "start": { "line": 0, "character": 0 },
+            "end": { "line": 0, "character": 0 },
+        }),
+    }
+}
+
+fn zero_based_lines(range: &Range) -> (u32, u32) {
+    let start_line = range.start_line.saturating_sub(1);
+    let end_line = if range.end_line == 0 {
+        start_line
+    } else {
+        range.end_line.saturating_sub(1).max(start_line)
+    };
+
+    (start_line, end_line)
+}
+
+fn severity(level: Level) -> u8 {
+    match level {
+        Level::High => 1,
+        Level::Medium => 2,
+        Level::Low => 3,
+        Level::Fmt | Level::Note | Level::Unspecified => 4,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::{Location, Suggestion};
+
+    fn issue(start_line: u32, end_line: u32, start_column: u32, end_column: u32) -> Issue {
+        Issue {
+            tool: "eslint".into(),
+            rule_key: "no-var".into(),
+            message: "Unexpected var".into(),
+            level: Level::Medium.into(),
+            location: Some(Location {
+                path: "index.js".into(),
+                range: Some(Range {
+                    start_line,
+                    end_line,
+                    start_column,
+                    end_column,
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_issue_to_diagnostic() {
+        let mut issue = issue(3, 3, 5, 10);
+        issue.documentation_url = "https://eslint.org/docs/rules/no-var".into();
+
+        assert_eq!(
+            issue_to_diagnostic(&issue),
+            json!({
+                "range": {
+                    "start": { "line": 2, "character": 4 },
+                    "end": { "line": 2, "character": 9 },
+                },
+                "severity": 2,
+                "code": "eslint:no-var",
+                "source": "qlty",
+                "message": "Unexpected var",
+                "codeDescription": { "href": "https://eslint.org/docs/rules/no-var" },
+            })
+        );
+    }
+
+    #[test]
+    fn test_diagnostic_without_columns_spans_line() {
+        assert_eq!(
+            issue_to_diagnostic(&issue(4, 0, 0, 0))["range"],
+            json!({
+                "start": { "line": 3, "character": 0 },
+                "end": { "line": 4, "character": 0 },
+            })
+        );
+    }
+
+    #[test]
+    fn test_code_actions() {
+        let source = "var a = 1;\nvar b = 2;\n";
+        let mut fixable = issue(1, 1, 1, 4);
+        fixable.suggestions = vec![Suggestion {
+            description: "Use let".into(),
+            patch: diffy::create_patch(source, "let a = 1;\nvar b = 2;\n").to_string(),
+            ..Default::default()
+        }];
+        let unfixable = issue(2, 2, 1, 4);
+
+        let actions = code_actions(
+            "file:///index.js",
+            source,
+            &[fixable.clone(), unfixable],
+            0,
+            1,
+            false,
+        );
+
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0]["title"], "Use let");
+        assert_eq!(
+            actions[0]["edit"]["changes"]["file:///index.js"],
+            json!([{
+                "range": {
+                    "start": { "line": 0, "character": 0 },
+                    "end": { "line": 1, "character": 0 },
+                },
+                "newText": "let a = 1;\n",
+            }])
+        );
+
+        assert!(code_actions("file:///index.js", source, &[fixable], 1, 1, false).is_empty());
+    }
+
+    #[test]
+    fn test_text_edits() {
+        assert_eq!(
+            text_edits("a\nb\nc\nd\n", "a\nB\nc\nd\ne\n"),
+            vec![
+                json!({
+                    "range": {
+                        "start": { "line": 1, "character": 0 },
+                        "end": { "line": 2, "character": 0 },
+                    },
+                    "newText": "B\n",
+                }),
+                json!({
+                    "range": {
+                        "start": { "line": 4, "character": 0 },
+                        "end": { "line": 4, "character": 0 },
+                    },
+                    "newText": "e\n",
+                }),
+            ]
+        );
+
+        assert!(text_edits("a\n", "a\n").is_empty());
+    }
+
+    #[test]
+    fn test_text_edits_without_trailing_newline() {
+        assert_eq!(
+            text_edits("a\nbé", "a\nbé\n"),
+            vec![json!({
+                "range": {
+                    "start": { "line": 1, "character": 0 },
+                    "end": { "line": 1, "character": 2 },
+                },
+                "newText": "bé\n",
+            })]
+        );
+    }
+}
@@ -0,0 +1,647 @@
+use super::diagnostics::{code_actions, issue_to_diagnostic, text_edits};
+use super::transport::Transport;
+use super::Backend;
+use anyhow::{anyhow, Result};
+use qlty_config::version::QLTY_VERSION;
+use qlty_types::analysis::v1::Issue;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, error, info, warn};
+use url::Url;
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_REQUEST: i64 = -32600;
+const INTERNAL_ERROR: i64 = -32603;
+
+const MESSAGE_TYPE_ERROR: u8 = 1;
+
+const TEXT_DOCUMENT_SYNC_FULL: u8 = 1;
+
+#[derive(Debug)]
+pub struct Server<B: Backend, R: BufRead, W: Write> {
+    root: PathBuf,
+    backend: B,
+    transport: Transport<R, W>,
+    allow_unsafe: bool,
+    documents: HashMap<String, String>,
+    issues: HashMap<String, Vec<Issue>>,
+    shutdown_requested: bool,
+}
+
+impl<B: Backend, R: BufRead, W: Write> Server<B, R, W> {
+    pub fn new(root: PathBuf, backend: B, reader: R, writer: W) -> Self {
+        Self {
+            root,
+            backend,
+            transport: Transport::new(reader, writer),
+            allow_unsafe: false,
+            documents: HashMap::new(),
+            issues: HashMap::new(),
+            shutdown_requested: false,
+        }
+    }
+
+    pub fn allow_unsafe(mut self, allow_unsafe: bool) -> Self {
+        self.allow_unsafe = allow_unsafe;
+        self
+    }
+
+    /// Serves requests until the client sends `exit` or closes the input stream.
+    pub fn run(&mut self) -> Result<()> {
+        info!("Starting LSP server for {}", self.root.display());
+
+        while let Some(message) = self.transport.read_message()? {
+            let method = message["method"].as_str().unwrap_or_default().to_string();
+            let id = message.get("id").cloned();
+            let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+            if method == "exit" {
+                info!("LSP client requested exit");
+                return Ok(());
+            }
+
+            match id {
+                Some(id) => self.handle_request(id, &method, &params)?,
+                None => self.handle_notification(&method, &params)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub fn into_writer(self) -> W {
+        self.transport.into_writer()
+    }
+
+    fn handle_request(&mut self, id: Value, method: &str, params: &Value) -> Result<()> {
+        if self.shutdown_requested {
+            return self.respond_error(id, INVALID_REQUEST, "Server is shutting down");
+        }
+
+        let result = match method {
+            "initialize" => Ok(self.initialize()),
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Ok(Value::Null)
+            }
+            "textDocument/codeAction" => self.code_action(params),
+            "textDocument/formatting" => self.formatting(params),
+            _ => {
+                debug!("Unsupported LSP request: {}", method);
+                return self.respond_error(
+                    id,
+                    METHOD_NOT_FOUND,
+                    &format!("Unsupported method: {}", method),
+                );
+            }
+        };
+
+        match result {
+            Ok(result) => self
+                .transport
+                .write_message(&json!({ "jsonrpc": "2.0", "id": id, "result": result })),
+            Err(err) => {
+                error!("LSP request {} failed: {:?}", method, err);
+                self.respond_error(id, INTERNAL_ERROR, &err.to_string())
+            }
+        }
+    }
+
+    fn handle_notification(&mut self, method: &str, params: &Value) -> Result<()> {
+        match method {
+            "textDocument/didOpen" => {
+                let uri = Self::document_uri(params)?;
+
+                if let Some(text) = params["textDocument"]["text"].as_str() {
+                    self.documents.insert(uri.clone(), text.to_string());
+                }
+
+                self.publish_issues(&uri)
+            }
+            "textDocument/didChange" => {
+                let uri = Self::document_uri(params)?;
+
+                // With full sync, the last change holds the whole document
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str());
+
+                if let Some(text) = text {
+                    self.documents.insert(uri, text.to_string());
+                }
+
+                Ok(())
+            }
+            "textDocument/didSave" => {
+                let uri = Self::document_uri(params)?;
+                self.publish_issues(&uri)
+            }
+            "textDocument/didClose" => {
+                let uri = Self::document_uri(params)?;
+                self.documents.remove(&uri);
+                self.issues.remove(&uri);
+                self.publish_diagnostics(&uri, vec![])
+            }
+            _ => {
+                debug!("Ignoring LSP notification: {}", method);
+                Ok(())
+            }
+        }
+    }
+
+    fn initialize(&self) -> Value {
+        json!({
+            "capabilities": {
+                "textDocumentSync": {
+                    "openClose": true,
+                    "change": TEXT_DOCUMENT_SYNC_FULL,
+                    "save": { "includeText": false },
+                },
+                "codeActionProvider": { "codeActionKinds": ["quickfix"] },
+                "documentFormattingProvider": true,
+            },
+            "serverInfo": {
+                "name": "qlty",
+                "version": QLTY_VERSION,
+            },
+        })
+    }
+
+    fn publish_issues(&mut self, uri: &str) -> Result<()> {
+        let path = match self.relative_path(uri) {
+            Ok(path) => path,
+            Err(err) => {
+                warn!("Skipping document outside of workspace: {}", err);
+                return Ok(());
+            }
+        };
+
+        let contents = match self.document_text(uri, &path) {
+            Ok(contents) => contents,
+            Err(err) => {
+                error!("Failed to read {}: {:?}", path.display(), err);
+                return self.show_error(&format!("qlty check failed: {}", err));
+            }
+        };
+
+        match self.backend.check(&path, &contents) {
+            Ok(issues) => {
+                let issues = issues
+                    .into_iter()
+                    .filter(|issue| issue.path().map(PathBuf::from).as_ref() == Some(&path))
+                    .collect::<Vec<_>>();
+                let diagnostics = issues.iter().map(issue_to_diagnostic).collect();
+
+                self.issues.insert(uri.to_string(), issues);
+                self.publish_diagnostics(uri, diagnostics)
+            }
+            Err(err) => {
+                error!("Failed to check {}: {:?}", path.display(), err);
+                self.show_error(&format!("qlty check failed: {}", err))
+            }
+        }
+    }
+
+    fn publish_diagnostics(&mut self, uri: &str, diagnostics: Vec<Value>) -> Result<()> {
+        self.transport.write_message(&json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics },
+        }))
+    }
+
+    fn code_action(&self, params: &Value) -> Result<Value> {
+        let uri = Self::document_uri(params)?;
+        let issues = match self.issues.get(&uri) {
+            Some(issues) => issues,
+            None => return Ok(json!([])),
+        };
+
+        let path = self.relative_path(&uri)?;
+        let source = self.document_text(&uri, &path)?;
+        let start_line = params["range"]["start"]["line"].as_u64().unwrap_or(0) as u32;
+        let end_line = params["range"]["end"]["line"]
+            .as_u64()
+            .unwrap_or(start_line as u64) as u32;
+
+        Ok(Value::Array(code_actions(
+            &uri,
+            &source,
+            issues,
+            start_line,
+            end_line,
+            self.allow_unsafe,
+        )))
+    }
+
+    fn formatting(&self, params: &Value) -> Result<Value> {
+        let uri = Self::document_uri(params)?;
+        let path = self.relative_path(&uri)?;
+
+        let original = self.document_text(&uri, &path)?;
+        let formatted = self.backend.format(&path, &original)?;
+
+        Ok(Value::Array(text_edits(&original, &formatted)))
+    }
+
+    // Open documents are read from the editor's buffer, which may hold
+    // unsaved changes, and only fall back to the file on disk
+    fn document_text(&self, uri: &str, path: &Path) -> Result<String> {
+        match self.documents.get(uri) {
+            Some(text) => Ok(text.clone()),
+            None => Ok(std::fs::read_to_string(self.root.join(path))?),
+        }
+    }
+
+    fn show_error(&mut self, message: &str) -> Result<()> {
+        self.transport.write_message(&json!({
+            "jsonrpc": "2.0",
+            "method": "window/showMessage",
+            "params": { "type": MESSAGE_TYPE_ERROR, "message": message },
+        }))
+    }
+
+    fn respond_error(&mut self, id: Value, code: i64, message: &str) -> Result<()> {
+        self.transport.write_message(&json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": message },
+        }))
+    }
+
+    fn document_uri(params: &Value) -> Result<String> {
+        params["textDocument"]["uri"]
+            .as_str()
+            .map(|uri| uri.to_string())
+            .ok_or_else(|| anyhow!("Missing textDocument.uri"))
+    }
+
+    fn relative_path(&self, uri: &str) -> Result<PathBuf> {
+        let path = Url::parse(uri)?
+            .to_file_path()
+            .map_err(|_| anyhow!("Not a file URI: {}", uri))?;
+
+        Self::strip_root(&self.root, &path)
+            .ok_or_else(|| anyhow!("{} is not within {}", path.display(), self.root.display()))
+    }
+
+    fn strip_root(root: &Path, path: &Path) -> Option<PathBuf> {
+        path.strip_prefix(root)
+            .ok()
+            .map(Path::to_path_buf)
+            .or_else(|| {
+                // Editors may report paths through symlinks (e.g. /private/var on macOS)
+                let root = root.canonicalize().ok()?;
+                let path = path.canonicalize().ok()?;
+                path.strip_prefix(root).ok().map(Path::to_path_buf)
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::{Level, Location, Range, Suggestion};
+    use std::cell::RefCell;
+    use std::io::Cursor;
+    use tempfile::TempDir;
+
+    #[derive(Debug, Default)]
+    struct FakeBackend {
+        issues: Vec<Issue>,
+        formatted: Option<String>,
+        checked: RefCell<Vec<(PathBuf, String)>>,
+    }
+
+    impl Backend for FakeBackend {
+        fn check(&self, path: &Path, contents: &str) -> Result<Vec<Issue>> {
+            self.checked
+                .borrow_mut()
+                .push((path.to_path_buf(), contents.to_string()));
+            Ok(self.issues.clone())
+        }
+
+        fn format(&self, _path: &Path, contents: &str) -> Result<String> {
+            Ok(self
+                .formatted
+                .clone()
+                .unwrap_or_else(|| contents.to_string()))
+        }
+    }
+
+    struct Session {
+        root: TempDir,
+        uri: String,
+    }
+
+    impl Session {
+        fn new() -> Self {
+            let root = tempfile::tempdir().unwrap();
+            std::fs::write(root.path().join("index.js"), "var a = 1;\n").unwrap();
+            let uri = Url::from_file_path(root.path().join("index.js"))
+                .unwrap()
+                .to_string();
+
+            Self { root, uri }
+        }
+
+        fn run(&self, backend: FakeBackend, messages: &[Value]) -> Vec<Value> {
+            let mut input = String::new();
+
+            for message in messages {
+                let body = message.to_string();
+                input.push_str(&format!("Content-Length: {}\r\n\r\n{}", body.len(), body));
+            }
+
+            let mut server = Server::new(
+                self.root.path().to_path_buf(),
+                backend,
+                Cursor::new(input),
+                Vec::new(),
+            );
+            server.run().unwrap();
+
+            let output = server.into_writer();
+            let mut transport = Transport::new(Cursor::new(output), Vec::new());
+            let mut responses = vec![];
+
+            while let Some(message) = transport.read_message().unwrap() {
+                responses.push(message);
+            }
+
+            responses
+        }
+    }
+
+    fn issue() -> Issue {
+        Issue {
+            tool: "eslint".into(),
+            rule_key: "no-var".into(),
+            message: "Unexpected var".into(),
+            level: Level::High.into(),
+            location: Some(Location {
+                path: "index.js".into(),
+                range: Some(Range {
+                    start_line: 1,
+                    end_line: 1,
+                    start_column: 1,
+                    end_column: 4,
+                    ..Default::default()
+                }),
+            }),
+            suggestions: vec![Suggestion {
+                description: "Use let".into(),
+                patch: diffy::create_patch("var a = 1;\n", "let a = 1;\n").to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_initialize_and_shutdown() {
+        let session = Session::new();
+        let responses = session.run(
+            FakeBackend::default(),
+            &[
+                json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}),
+                json!({"jsonrpc": "2.0", "method": "initialized", "params": {}}),
+                json!({"jsonrpc": "2.0", "id": 2, "method": "shutdown"}),
+                json!({"jsonrpc": "2.0", "id": 3, "method": "textDocument/formatting"}),
+                json!({"jsonrpc": "2.0", "method": "exit"}),
+            ],
+        );
+
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(
+            responses[0]["result"]["capabilities"]["documentFormattingProvider"],
+            true
+        );
+        assert_eq!(
+            responses[1],
+            json!({"jsonrpc": "2.0", "id": 2, "result": null})
+        );
+        assert_eq!(responses[2]["error"]["code"], INVALID_REQUEST);
+    }
+
+    #[test]
+    fn test_unknown_request() {
+        let session = Session::new();
+        let responses = session.run(
+            FakeBackend::default(),
+            &[json!({"jsonrpc": "2.0", "id": 7, "method": "textDocument/hover"})],
+        );
+
+        assert_eq!(responses[0]["id"], 7);
+        assert_eq!(responses[0]["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_publish_diagnostics_and_code_actions() {
+        let session = Session::new();
+        let backend = FakeBackend {
+            issues: vec![issue()],
+            ..Default::default()
+        };
+
+        let responses = session.run(
+            backend,
+            &[
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": "textDocument/didOpen",
+                    "params": {
+                        "textDocument": {
+                            "uri": session.uri,
+                            "languageId": "javascript",
+                            "version": 1,
+                            "text": "var a = 1;\n",
+                        }
+                    }
+                }),
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "textDocument/codeAction",
+                    "params": {
+                        "textDocument": { "uri": session.uri },
+                        "range": {
+                            "start": { "line": 0, "character": 0 },
+                            "end": { "line": 0, "character": 3 },
+                        },
+                        "context": { "diagnostics": [] },
+                    }
+                }),
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": "textDocument/didClose",
+                    "params": { "textDocument": { "uri": session.uri } }
+                }),
+            ],
+        );
+
+        assert_eq!(responses.len(), 3);
+
+        assert_eq!(responses[0]["method"], "textDocument/publishDiagnostics");
+        assert_eq!(responses[0]["params"]["uri"], session.uri);
+        assert_eq!(
+            responses[0]["params"]["diagnostics"][0]["code"],
+            "eslint:no-var"
+        );
+        assert_eq!(responses[0]["params"]["diagnostics"][0]["severity"], 1);
+
+        let actions = responses[1]["result"].as_array().unwrap();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0]["title"], "Use let");
+        assert_eq!(
+            actions[0]["edit"]["changes"][session.uri.as_str()][0]["newText"],
+            "let a = 1;\n"
+        );
+        assert_eq!(
+            actions[0]["edit"]["changes"][session.uri.as_str()][0]["range"]["end"],
+            json!({ "line": 1, "character": 0 })
+        );
+
+        assert_eq!(responses[2]["params"]["diagnostics"], json!([]));
+    }
+
+    #[test]
+    fn test_checks_relative_path() {
+        let session = Session::new();
+        let backend = FakeBackend::default();
+        let mut server = Server::new(
+            session.root.path().to_path_buf(),
+            backend,
+            Cursor::new(String::new()),
+            Vec::new(),
+        );
+
+        server.publish_issues(&session.uri).unwrap();
+        assert_eq!(
+            server.backend.checked.borrow().clone(),
+            vec![(PathBuf::from("index.js"), "var a = 1;\n".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_checks_unsaved_changes() {
+        let session = Session::new();
+        let mut server = Server::new(
+            session.root.path().to_path_buf(),
+            FakeBackend::default(),
+            Cursor::new(String::new()),
+            Vec::new(),
+        );
+
+        server
+            .handle_notification(
+                "textDocument/didOpen",
+                &json!({
+                    "textDocument": {
+                        "uri": session.uri,
+                        "languageId": "javascript",
+                        "version": 1,
+                        "text": "var a = 1;\n",
+                    }
+                }),
+            )
+            .unwrap();
+        server
+            .handle_notification(
+                "textDocument/didChange",
+                &json!({
+                    "textDocument": { "uri": session.uri, "version": 2 },
+                    "contentChanges": [{ "text": "var b = 2;\n" }],
+                }),
+            )
+            .unwrap();
+        server
+            .handle_notification(
+                "textDocument/didSave",
+                &json!({ "textDocument": { "uri": session.uri } }),
+            )
+            .unwrap();
+
+        assert_eq!(
+            server.backend.checked.borrow()[1],
+            (PathBuf::from("index.js"), "var b = 2;\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_formatting_without_changes() {
+        let session = Session::new();
+        let responses = session.run(
+            FakeBackend::default(),
+            &[json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "textDocument/formatting",
+                "params": { "textDocument": { "uri": session.uri }, "options": {} }
+            })],
+        );
+
+        assert_eq!(responses[0]["result"], json!([]));
+    }
+
+    #[test]
+    fn test_formatting_unsaved_changes() {
+        let session = Session::new();
+        let backend = FakeBackend {
+            formatted: Some("let a = 1;\nlet b = 2;\n".to_string()),
+            ..Default::default()
+        };
+
+        let responses = session.run(
+            backend,
+            &[
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": "textDocument/didOpen",
+                    "params": {
+                        "textDocument": {
+                            "uri": session.uri,
+                            "languageId": "javascript",
+                            "version": 1,
+                            "text": "var a = 1;\n",
+                        }
+                    }
+                }),
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": "textDocument/didChange",
+                    "params": {
+                        "textDocument": { "uri": session.uri, "version": 2 },
+                        "contentChanges": [{ "text": "let a = 1;\nlet b=2;\n" }],
+                    }
+                }),
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "textDocument/formatting",
+                    "params": { "textDocument": { "uri": session.uri }, "options": {} }
+                }),
+            ],
+        );
+
+        assert_eq!(
+            responses[1]["result"],
+            json!([{
+                "range": {
+                    "start": { "line": 1, "character": 0 },
+                    "end": { "line": 2, "character": 0 },
+                },
+                "newText": "let b = 2;\n",
+            }])
+        );
+        assert_eq!(
+            std::fs::read_to_string(session.root.path().join("index.js")).unwrap(),
+            "var a = 1;\n"
+        );
+    }
+}
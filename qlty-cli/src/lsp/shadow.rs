@@ -0,0 +1,157 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+#[cfg(unix)]
+use std::os::unix::fs::symlink as symlink_dir;
+#[cfg(windows)]
+use std::os::windows::fs::symlink_dir;
+
+// Output of previous runs, which plugins never read
+const SKIPPED_LIBRARY_DIRECTORIES: [&str; 4] = ["logs", "out", "results", "plugin_cachedir"];
+
+/// A scratch copy of the workspace holding the editor's contents of a single
+/// document, so that unsaved changes can be checked and formatted without
+/// reading or rewriting the user's files.
+///
+/// Only the `.qlty` directory and the files directly inside each of the
+/// document's ancestor directories are copied, which is where plugins look
+/// for their configuration.
+#[derive(Debug)]
+pub struct ShadowWorkspace {
+    directory: TempDir,
+}
+
+impl ShadowWorkspace {
+    pub fn new(root: &Path, path: &Path, contents: &str) -> Result<Self> {
+        let directory = tempfile::Builder::new().prefix("qlty-lsp-").tempdir()?;
+
+        copy_library(&root.join(".qlty"), &directory.path().join(".qlty"))?;
+
+        if let Some(parent) = path.parent() {
+            for ancestor in parent.ancestors() {
+                copy_files(&root.join(ancestor), &directory.path().join(ancestor))?;
+            }
+        }
+
+        let document = directory.path().join(path);
+        fs::write(&document, contents)
+            .with_context(|| format!("Failed to write {}", document.display()))?;
+
+        Ok(Self { directory })
+    }
+
+    pub fn root(&self) -> &Path {
+        self.directory.path()
+    }
+
+    pub fn read(&self, path: &Path) -> Result<String> {
+        let document = self.root().join(path);
+        fs::read_to_string(&document)
+            .with_context(|| format!("Failed to read {}", document.display()))
+    }
+}
+
+fn copy_library(from: &Path, to: &Path) -> Result<()> {
+    if !from.is_dir() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+
+        if SKIPPED_LIBRARY_DIRECTORIES
+            .iter()
+            .any(|skipped| entry.file_name() == *skipped)
+        {
+            continue;
+        }
+
+        copy_entry(&entry.path(), &to.join(entry.file_name()))?;
+    }
+
+    Ok(())
+}
+
+fn copy_entry(from: &Path, to: &Path) -> Result<()> {
+    let file_type = fs::symlink_metadata(from)?.file_type();
+
+    if file_type.is_symlink() && from.is_dir() {
+        // Sources are symlinked to a checkout in the global cache
+        symlink_dir(fs::read_link(from)?, to)
+            .with_context(|| format!("Failed to link {}", to.display()))?;
+    } else if from.is_dir() {
+        fs::create_dir_all(to)?;
+
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_entry(&entry.path(), &to.join(entry.file_name()))?;
+        }
+    } else if from.is_file() {
+        fs::copy(from, to).with_context(|| format!("Failed to copy {}", from.display()))?;
+    }
+
+    Ok(())
+}
+
+fn copy_files(from: &Path, to: &Path) -> Result<()> {
+    if !from.is_dir() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() {
+            fs::copy(&path, to.join(entry.file_name()))
+                .with_context(|| format!("Failed to copy {}", path.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_shadow_workspace() {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir_all(root.path().join(".qlty/logs")).unwrap();
+        fs::create_dir_all(root.path().join("src/app")).unwrap();
+        fs::create_dir_all(root.path().join("lib")).unwrap();
+        fs::write(
+            root.path().join(".qlty/qlty.toml"),
+            "config_version = \"0\"",
+        )
+        .unwrap();
+        fs::write(root.path().join(".qlty/logs/qlty.log"), "").unwrap();
+        fs::write(root.path().join(".eslintrc.json"), "{}").unwrap();
+        fs::write(root.path().join("src/app/index.js"), "var a = 1;\n").unwrap();
+        fs::write(root.path().join("lib/other.js"), "").unwrap();
+
+        let shadow =
+            ShadowWorkspace::new(root.path(), Path::new("src/app/index.js"), "let a = 1;\n")
+                .unwrap();
+
+        assert!(shadow.root().join(".qlty/qlty.toml").exists());
+        assert!(!shadow.root().join(".qlty/logs").exists());
+        assert!(shadow.root().join(".eslintrc.json").exists());
+        assert!(!shadow.root().join("lib").exists());
+        assert_eq!(
+            shadow.read(Path::new("src/app/index.js")).unwrap(),
+            "let a = 1;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.path().join("src/app/index.js")).unwrap(),
+            "var a = 1;\n"
+        );
+    }
+}
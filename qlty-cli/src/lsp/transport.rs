@@ -0,0 +1,139 @@
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::io::{BufRead, Write};
+use tracing::trace;
+
+const CONTENT_LENGTH_HEADER: &str = "content-length";
+
+// Large enough for any source file an editor would send, but keeps a bad
+// header from allocating an unbounded buffer
+const MAX_CONTENT_LENGTH: usize = 64 * 1024 * 1024;
+
+/// Reads and writes JSON-RPC messages framed with `Content-Length` headers,
+/// as described by the Language Server Protocol base protocol.
+#[derive(Debug)]
+pub struct Transport<R: BufRead, W: Write> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: BufRead, W: Write> Transport<R, W> {
+    pub fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+
+    /// Returns `None` once the input stream is closed.
+    pub fn read_message(&mut self) -> Result<Option<Value>> {
+        let mut content_length = None;
+
+        loop {
+            let mut line = String::new();
+
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+
+            let line = line.trim_end_matches(['\r', '\n']);
+
+            if line.is_empty() {
+                if content_length.is_some() {
+                    break;
+                }
+
+                continue;
+            }
+
+            if let Some((name, value)) = line.split_once(':') {
+                if name.trim().eq_ignore_ascii_case(CONTENT_LENGTH_HEADER) {
+                    content_length = Some(
+                        value
+                            .trim()
+                            .parse::<usize>()
+                            .with_context(|| format!("Invalid Content-Length header: {}", line))?,
+                    );
+                }
+            } else {
+                bail!("Invalid LSP header: {}", line);
+            }
+        }
+
+        let content_length = content_length.unwrap();
+
+        if content_length > MAX_CONTENT_LENGTH {
+            bail!(
+                "Content-Length of {} bytes exceeds the maximum of {} bytes",
+                content_length,
+                MAX_CONTENT_LENGTH
+            );
+        }
+
+        let mut buffer = vec![0; content_length];
+        self.reader.read_exact(&mut buffer)?;
+
+        let message = serde_json::from_slice(&buffer)?;
+        trace!("LSP <- {}", message);
+        Ok(Some(message))
+    }
+
+    pub fn write_message(&mut self, message: &Value) -> Result<()> {
+        trace!("LSP -> {}", message);
+        let body = serde_json::to_string(message)?;
+        write!(
+            self.writer,
+            "Content-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_read_messages() {
+        let input = "Content-Length: 17\r\n\r\n{\"method\":\"exit\"}content-length:2\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\n{}";
+        let mut transport = Transport::new(Cursor::new(input), Vec::new());
+
+        assert_eq!(
+            transport.read_message().unwrap(),
+            Some(json!({"method": "exit"}))
+        );
+        assert_eq!(transport.read_message().unwrap(), Some(json!({})));
+        assert_eq!(transport.read_message().unwrap(), None);
+    }
+
+    #[test]
+    fn test_read_invalid_header() {
+        let mut transport = Transport::new(Cursor::new("garbage\r\n\r\n"), Vec::new());
+        assert!(transport.read_message().is_err());
+    }
+
+    #[test]
+    fn test_read_oversized_message() {
+        let input = format!("Content-Length: {}\r\n\r\n{{}}", MAX_CONTENT_LENGTH + 1);
+        let mut transport = Transport::new(Cursor::new(input), Vec::new());
+        assert!(transport.read_message().is_err());
+    }
+
+    #[test]
+    fn test_write_message() {
+        let mut transport = Transport::new(Cursor::new(""), Vec::new());
+        transport.write_message(&json!({"id": 1})).unwrap();
+
+        assert_eq!(
+            String::from_utf8(transport.into_writer()).unwrap(),
+            "Content-Length: 8\r\n\r\n{\"id\":1}"
+        );
+    }
+}
@@ -5,6 +5,8 @@ mod invocations;
 mod issues;
 mod level;
 mod messages;
+mod profile;
+mod review;
 mod source;
 mod steps;
 mod text;
@@ -13,5 +15,7 @@ mod unformatted;
 pub use errors::ErrorsFormatter;
 pub use fixes::ApplyMode;
 pub use highlight::Highlighter;
+pub use profile::print_profile;
+pub use review::review_fixes;
 pub use steps::Steps;
 pub use text::TextFormatter;
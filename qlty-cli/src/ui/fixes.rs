@@ -18,82 +18,46 @@ pub enum ApplyMode {
     Ask,
 }
 
-struct PatchCandidate {
-    issue: Issue,
-    source: SuggestionSource,
-    path: String,
-    patch: String,
-    original_code: String,
-    modified_code: String,
+pub(super) struct PatchCandidate {
+    pub issue: Issue,
+    pub source: SuggestionSource,
+    pub path: String,
+    pub patch: String,
+    pub original_code: String,
+    pub modified_code: String,
 }
 
-struct Line(Option<usize>);
-
-impl fmt::Display for Line {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self.0 {
-            None => write!(f, "    "),
-            Some(idx) => write!(f, "{:<4}", idx + 1),
-        }
-    }
-}
-
-pub fn print_fixes(
-    writer: &mut dyn std::io::Write,
-    issues: &[Issue],
-    root: &Path,
-    apply_mode: ApplyMode,
-) -> Result<bool> {
-    let mut dirty = false;
-    let mut apply_mode = apply_mode;
-    let mut patch_candidates = vec![];
-    let workspace = Workspace::new()?;
+impl PatchCandidate {
+    /// Builds a candidate from the first suggestion of the issue, or returns
+    /// none if the suggestion does not apply to the original code.
+    pub fn new(issue: &Issue, original_code: String) -> Option<Self> {
+        let location = issue.location.as_ref()?;
+        let suggestion = issue.suggestions.first()?;
 
-    for issue in issues {
-        if let Some(location) = &issue.location {
-            if let Some(suggestion) = issue.suggestions.first() {
-                if let Ok(patch) = Patch::from_str(&suggestion.patch) {
-                    let full_path = root.join(location.path.clone());
-                    let original_code = std::fs::read_to_string(&full_path)
-                        .with_context(|| format!("Failed to read file: {}", full_path.display()))?;
+        let Ok(patch) = Patch::from_str(&suggestion.patch) else {
+            warn!("Failed to parse patch: {}", suggestion.patch);
+            return None;
+        };
 
-                    if let Ok(modified_code) = diffy::apply(&original_code, &patch) {
-                        patch_candidates.push(PatchCandidate {
-                            issue: issue.clone(),
-                            source: SuggestionSource::try_from(suggestion.source)
-                                .unwrap_or_default(),
-                            path: location.path.clone(),
-                            patch: suggestion.patch.clone(),
-                            original_code,
-                            modified_code,
-                        });
-                    } else {
-                        warn!("Failed to apply patch: {}", suggestion.patch);
-                    }
-                } else {
-                    warn!("Failed to parse patch: {}", suggestion.patch);
-                }
-            }
-        }
-    }
+        let Ok(modified_code) = diffy::apply(&original_code, &patch) else {
+            warn!("Failed to apply patch: {}", suggestion.patch);
+            return None;
+        };
 
-    if patch_candidates.is_empty() {
-        return Ok(dirty);
+        Some(Self {
+            issue: issue.clone(),
+            source: SuggestionSource::try_from(suggestion.source).unwrap_or_default(),
+            path: location.path.clone(),
+            patch: suggestion.patch.clone(),
+            original_code,
+            modified_code,
+        })
     }
 
-    writeln!(
-        writer,
-        "{}{}{}",
-        style(" AUTOFIXES: ").bold().reverse(),
-        style(patch_candidates.len().to_formatted_string(&Locale::en))
-            .bold()
-            .reverse(),
-        style(" ").bold().reverse()
-    )?;
-    writeln!(writer)?;
-
-    for candidate in patch_candidates {
-        let diff = TextDiff::from_lines(&candidate.original_code, &candidate.modified_code);
+    /// Writes the issue and the colored diff of its fix. Returns false without
+    /// writing anything if the diff is empty.
+    pub fn write_to(&self, writer: &mut dyn std::io::Write) -> Result<bool> {
+        let diff = TextDiff::from_lines(&self.original_code, &self.modified_code);
         let mut patch_writer = vec![];
 
         for (idx, group) in diff.grouped_ops(3).iter().enumerate() {
@@ -134,35 +98,97 @@ pub fn print_fixes(
 
         // For a reason that I haven't figured out yet, sometimes we print
         // empty patches. This is a workaround to skip those issues.
-        if !patch_writer.is_empty() {
-            let start_line = candidate.issue.range().unwrap_or_default().start_line;
+        if patch_writer.is_empty() {
+            return Ok(false);
+        }
 
-            writeln!(
-                writer,
-                "{}{}",
-                style(&candidate.path).underlined(),
-                style(format!(":{}", start_line)).dim()
-            )?;
+        let start_line = self.issue.range().unwrap_or_default().start_line;
 
-            writeln!(
-                writer,
-                "{} {}",
-                formatted_level(candidate.issue.level()),
-                style(candidate.issue.message.replace('\n', " ").trim())
-            )?;
+        writeln!(
+            writer,
+            "{}{}",
+            style(&self.path).underlined(),
+            style(format!(":{}", start_line)).dim()
+        )?;
 
-            write!(writer, "{}", String::from_utf8_lossy(&patch_writer))?;
-            writeln!(
-                writer,
-                "{} {}",
-                formatted_source(&candidate.issue),
-                match candidate.source {
-                    SuggestionSource::Llm => format!("[{}]", style("ai fix").cyan()),
-                    _ => "".to_string(),
-                }
-            )?;
-            writeln!(writer)?;
+        writeln!(
+            writer,
+            "{} {}",
+            formatted_level(self.issue.level()),
+            style(self.issue.message.replace('\n', " ").trim())
+        )?;
+
+        write!(writer, "{}", String::from_utf8_lossy(&patch_writer))?;
+        writeln!(
+            writer,
+            "{} {}",
+            formatted_source(&self.issue),
+            match self.source {
+                SuggestionSource::Llm => format!("[{}]", style("ai fix").cyan()),
+                _ => "".to_string(),
+            }
+        )?;
+        writeln!(writer)?;
+
+        Ok(true)
+    }
+}
+
+struct Line(Option<usize>);
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            None => write!(f, "    "),
+            Some(idx) => write!(f, "{:<4}", idx + 1),
+        }
+    }
+}
 
+pub fn print_fixes(
+    writer: &mut dyn std::io::Write,
+    issues: &[Issue],
+    root: &Path,
+    apply_mode: ApplyMode,
+) -> Result<bool> {
+    let mut dirty = false;
+    let mut apply_mode = apply_mode;
+    let mut patch_candidates = vec![];
+    let workspace = Workspace::new()?;
+
+    for issue in issues {
+        if let Some(location) = &issue.location {
+            if issue.suggestions.is_empty() {
+                continue;
+            }
+
+            let full_path = root.join(location.path.clone());
+            let original_code = std::fs::read_to_string(&full_path)
+                .with_context(|| format!("Failed to read file: {}", full_path.display()))?;
+
+            if let Some(candidate) = PatchCandidate::new(issue, original_code) {
+                patch_candidates.push(candidate);
+            }
+        }
+    }
+
+    if patch_candidates.is_empty() {
+        return Ok(dirty);
+    }
+
+    writeln!(
+        writer,
+        "{}{}{}",
+        style(" AUTOFIXES: ").bold().reverse(),
+        style(patch_candidates.len().to_formatted_string(&Locale::en))
+            .bold()
+            .reverse(),
+        style(" ").bold().reverse()
+    )?;
+    writeln!(writer)?;
+
+    for candidate in patch_candidates {
+        if candidate.write_to(writer)? {
             if std::io::stdin().is_terminal() {
                 match apply_mode {
                     ApplyMode::None => {} // Skip and don't ask
@@ -0,0 +1,150 @@
+use anyhow::Result;
+use cli_table::{
+    format::{Border, HorizontalLine, Justify, Separator, VerticalLine},
+    Cell, CellStruct, Table,
+};
+use console::style;
+use qlty_check::profile::{Profile, SlowFile};
+
+const MAX_INVOCATIONS: usize = 20;
+
+pub fn print_profile(
+    writer: &mut dyn std::io::Write,
+    profile: &Profile,
+    slow_files: &[SlowFile],
+) -> Result<()> {
+    writeln!(writer)?;
+    writeln!(writer, "{}", style(" PROFILE ").bold().reverse())?;
+    writeln!(writer)?;
+
+    let totals = profile
+        .totals_by_plugin()
+        .into_iter()
+        .map(|(plugin, secs)| {
+            vec![
+                plugin.cell().justify(Justify::Left),
+                format!("{:.2}s", secs).cell().justify(Justify::Right),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let table = totals
+        .table()
+        .title(vec![
+            "Plugin".cell(),
+            "Total".cell().justify(Justify::Right),
+        ])
+        .border(Border::builder().build())
+        .separator(separator());
+    writeln!(writer, "{}", table.display()?)?;
+    writeln!(writer)?;
+
+    let rows = profile
+        .ranked()
+        .into_iter()
+        .take(MAX_INVOCATIONS)
+        .map(|invocation| {
+            vec![
+                invocation.label.clone().cell().justify(Justify::Left),
+                format!("{:.2}s", invocation.wall_secs)
+                    .cell()
+                    .justify(Justify::Right),
+                optional_cell(invocation.cpu_secs.map(|secs| format!("{:.2}s", secs))),
+                optional_cell(invocation.peak_rss_bytes.map(format_bytes)),
+                invocation
+                    .batch_size
+                    .to_string()
+                    .cell()
+                    .justify(Justify::Right),
+                if invocation.timed_out {
+                    "timeout".to_string()
+                } else {
+                    invocation
+                        .exit_code
+                        .map(|code| code.to_string())
+                        .unwrap_or_else(|| "-".to_string())
+                }
+                .cell()
+                .justify(Justify::Right),
+                invocation.id[..invocation.id.len().min(6)].cell(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let table = rows
+        .table()
+        .title(vec![
+            "Invocation".cell(),
+            "Wall".cell().justify(Justify::Right),
+            "CPU".cell().justify(Justify::Right),
+            "Peak RSS".cell().justify(Justify::Right),
+            "Files".cell().justify(Justify::Right),
+            "Exit".cell().justify(Justify::Right),
+            "ID".cell(),
+        ])
+        .border(Border::builder().build())
+        .separator(separator());
+    writeln!(writer, "{}", table.display()?)?;
+
+    if profile.invocations.len() > MAX_INVOCATIONS {
+        writeln!(
+            writer,
+            "{}",
+            style(format!(
+                "...and {} faster invocations",
+                profile.invocations.len() - MAX_INVOCATIONS
+            ))
+            .dim()
+        )?;
+    }
+
+    if !slow_files.is_empty() {
+        writeln!(writer)?;
+        writeln!(
+            writer,
+            "{}",
+            style("Files which repeatedly timed out (consider adding them to exclude_patterns):")
+                .bold()
+        )?;
+
+        for file in slow_files {
+            writeln!(
+                writer,
+                "  {} {}",
+                file.path,
+                style(format!(
+                    "timed out in {} of {} recent runs",
+                    file.timeouts, file.profiles
+                ))
+                .dim()
+            )?;
+        }
+    }
+
+    writeln!(writer)?;
+    Ok(())
+}
+
+fn separator() -> Separator {
+    Separator::builder()
+        .title(Some(HorizontalLine::default()))
+        .column(Some(VerticalLine::default()))
+        .build()
+}
+
+fn optional_cell(value: Option<String>) -> CellStruct {
+    value
+        .unwrap_or_else(|| "-".to_string())
+        .cell()
+        .justify(Justify::Right)
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+
+    if bytes as f64 >= 1024.0 * MB {
+        format!("{:.1} GB", bytes as f64 / (1024.0 * MB))
+    } else {
+        format!("{:.1} MB", bytes as f64 / MB)
+    }
+}
@@ -0,0 +1,194 @@
+use super::fixes::PatchCandidate;
+use anyhow::Result;
+use console::style;
+use dialoguer::{theme::ColorfulTheme, Input};
+use num_format::{Locale, ToFormattedString as _};
+use qlty_check::{
+    executor::staging_area::StagingArea, patcher::Patcher, results::FixedResult,
+    source_reader::SourceReader as _,
+};
+use qlty_types::analysis::v1::Issue;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Answer {
+    Accept,
+    Skip,
+    AcceptRule,
+    Quit,
+}
+
+/// Steps through the fix suggestions one file at a time, asking which ones to
+/// apply. Accepted fixes are applied through the staging area with the same
+/// safety checks as `--fix`.
+pub fn review_fixes(
+    writer: &mut dyn std::io::Write,
+    issues: &[Issue],
+    staging_area: &StagingArea,
+    allow_unsafe: bool,
+) -> Result<HashSet<FixedResult>> {
+    let files = fixable_issues_by_file(issues, allow_unsafe);
+    let total = files.values().map(Vec::len).sum::<usize>();
+
+    if total == 0 {
+        return Ok(HashSet::new());
+    }
+
+    writeln!(
+        writer,
+        "{}{}{}",
+        style(" REVIEW AUTOFIXES: ").bold().reverse(),
+        style(total.to_formatted_string(&Locale::en))
+            .bold()
+            .reverse(),
+        style(" ").bold().reverse()
+    )?;
+    writeln!(writer)?;
+
+    let mut accepted = vec![];
+    let mut accepted_rules = HashSet::new();
+
+    'files: for (path, issues) in files {
+        let original_code = staging_area.read(PathBuf::from(&path))?;
+
+        for issue in issues {
+            let Some(candidate) = PatchCandidate::new(issue, original_code.clone()) else {
+                continue;
+            };
+
+            if !candidate.write_to(writer)? {
+                continue;
+            }
+
+            let answer = if accepted_rules.contains(&issue.rule_id()) {
+                writeln!(
+                    writer,
+                    "{}",
+                    style(format!("Accepted all fixes for {}", issue.rule_id())).dim()
+                )?;
+                Answer::Accept
+            } else {
+                prompt_review_fix()?
+            };
+            writeln!(writer)?;
+
+            match answer {
+                Answer::Accept => accepted.push(issue.clone()),
+                Answer::Skip => {}
+                Answer::AcceptRule => {
+                    accepted_rules.insert(issue.rule_id());
+                    accepted.push(issue.clone());
+                }
+                Answer::Quit => break 'files,
+            }
+        }
+    }
+
+    if accepted.is_empty() {
+        return Ok(HashSet::new());
+    }
+
+    let fixed = Patcher::new(staging_area).try_apply(&accepted, allow_unsafe);
+
+    eprintln!(
+        "{}",
+        style(format!(
+            "✔ Fixed {} of {} accepted issues",
+            fixed.len(),
+            accepted.len()
+        ))
+        .green()
+        .bold()
+    );
+
+    Ok(fixed)
+}
+
+fn fixable_issues_by_file(issues: &[Issue], allow_unsafe: bool) -> BTreeMap<String, Vec<&Issue>> {
+    let mut files: BTreeMap<String, Vec<&Issue>> = BTreeMap::new();
+
+    for issue in issues {
+        if Patcher::is_patchable(issue, allow_unsafe) {
+            if let Some(path) = issue.path() {
+                files.entry(path).or_default().push(issue);
+            }
+        }
+    }
+
+    files
+}
+
+fn prompt_review_fix() -> Result<Answer> {
+    loop {
+        let answer = Input::<String>::with_theme(&ColorfulTheme::default())
+            .with_prompt("Apply this fix? [Yes/skip/rule/quit]")
+            .default("Y".to_string())
+            .show_default(false)
+            .allow_empty(true)
+            .interact_text()?;
+
+        if let Some(answer) = parse_answer(&answer) {
+            return Ok(answer);
+        }
+    }
+}
+
+fn parse_answer(answer: &str) -> Option<Answer> {
+    match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => Some(Answer::Accept),
+        "s" | "skip" | "n" | "no" => Some(Answer::Skip),
+        "r" | "rule" => Some(Answer::AcceptRule),
+        "q" | "quit" => Some(Answer::Quit),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::{Location, Suggestion};
+
+    fn issue(rule_key: &str, path: &str, patch: &str) -> Issue {
+        Issue {
+            tool: "eslint".to_string(),
+            rule_key: rule_key.to_string(),
+            location: Some(Location {
+                path: path.to_string(),
+                ..Default::default()
+            }),
+            suggestions: vec![Suggestion {
+                patch: patch.to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_answer() {
+        assert_eq!(parse_answer("Y"), Some(Answer::Accept));
+        assert_eq!(parse_answer("skip"), Some(Answer::Skip));
+        assert_eq!(parse_answer("n"), Some(Answer::Skip));
+        assert_eq!(parse_answer("Rule"), Some(Answer::AcceptRule));
+        assert_eq!(parse_answer("q"), Some(Answer::Quit));
+        assert_eq!(parse_answer("maybe"), None);
+    }
+
+    #[test]
+    fn test_fixable_issues_by_file() {
+        let issues = vec![
+            issue("no-var", "b.js", "patch"),
+            issue("no-var", "a.js", "patch"),
+            issue("no-var", "a.js", ""),
+            issue("@typescript-eslint/no-explicit-any", "a.js", "patch"),
+        ];
+
+        let files = fixable_issues_by_file(&issues, false);
+        assert_eq!(files.keys().collect::<Vec<_>>(), vec!["a.js", "b.js"]);
+        assert_eq!(files["a.js"].len(), 1);
+
+        let files = fixable_issues_by_file(&issues, true);
+        assert_eq!(files["a.js"].len(), 2);
+    }
+}
@@ -0,0 +1,192 @@
+use crate::ui::{ApplyMode, TextFormatter};
+use anyhow::Result;
+use console::{style, Term};
+use itertools::Itertools;
+use num_format::{Locale, ToFormattedString as _};
+use qlty_check::watcher::{Changes, Watcher};
+use qlty_check::{planner::Planner, Executor, Processor, Report, Settings};
+use qlty_config::Workspace;
+use qlty_types::analysis::v1::{ExecutionVerb, Issue};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// Re-runs checks or formatters on the files which change in the workspace,
+/// keeping the issues for unchanged files from earlier runs.
+#[derive(Debug)]
+pub struct WatchSession {
+    verb: ExecutionVerb,
+    workspace: Workspace,
+    settings: Settings,
+    apply_mode: ApplyMode,
+    issues: HashMap<Option<PathBuf>, Vec<Issue>>,
+    report: Option<Report>,
+    error: Option<String>,
+}
+
+impl WatchSession {
+    pub fn new(
+        verb: ExecutionVerb,
+        workspace: &Workspace,
+        settings: &Settings,
+        apply_mode: ApplyMode,
+    ) -> Self {
+        Self {
+            verb,
+            workspace: workspace.clone(),
+            settings: settings.clone(),
+            apply_mode,
+            issues: HashMap::new(),
+            report: None,
+            error: None,
+        }
+    }
+
+    /// Runs until the process is interrupted or an unrecoverable error occurs.
+    pub fn run(&mut self) -> Result<()> {
+        let mut watcher = Watcher::new(&self.workspace)?;
+
+        let settings = self.settings.clone();
+        let written = self.execute(&settings, &Changes::default());
+        self.redraw()?;
+
+        // Formatters and fixes write files, which must not trigger another run,
+        // while files saved by the user during a run are picked up by the next
+        watcher.acknowledge(&written)?;
+
+        loop {
+            let changes = watcher.wait_for_changes()?;
+            debug!("Re-running on changes: {:?}", changes);
+
+            let written = if changes.modified.is_empty() {
+                self.forget(&changes);
+                vec![]
+            } else {
+                let settings = self.settings_for(&changes.modified);
+                self.execute(&settings, &changes)
+            };
+
+            self.redraw()?;
+            watcher.acknowledge(&written)?;
+        }
+    }
+
+    /// Returns the paths of the files written by formatters or fixes.
+    fn execute(&mut self, settings: &Settings, changes: &Changes) -> Vec<PathBuf> {
+        match self.compute_report(settings) {
+            Ok(report) => {
+                let written = Self::written_paths(&report);
+                self.forget(changes);
+
+                // Issues which are not tied to a file always come from the latest run
+                self.issues.remove(&None);
+
+                for (path, issues) in report.issues_by_path() {
+                    self.issues.insert(path, issues);
+                }
+
+                self.report = Some(report);
+                self.error = None;
+
+                written
+            }
+            Err(err) => {
+                self.error = Some(err.to_string());
+                vec![]
+            }
+        }
+    }
+
+    fn compute_report(&self, settings: &Settings) -> Result<Report> {
+        let plan = Planner::new(self.verb, settings)?.compute()?;
+        let executor = Executor::new(&plan);
+        let results = executor.install_and_invoke()?;
+
+        let mut processor = Processor::new(&plan, results);
+        processor.compute()
+    }
+
+    fn written_paths(report: &Report) -> Vec<PathBuf> {
+        report
+            .formatted
+            .iter()
+            .map(|formatted| formatted.path.clone())
+            .chain(
+                report
+                    .fixed
+                    .iter()
+                    .map(|fixed| PathBuf::from(&fixed.location.path)),
+            )
+            .unique()
+            .collect()
+    }
+
+    fn forget(&mut self, changes: &Changes) {
+        for path in changes.modified.iter().chain(changes.removed.iter()) {
+            self.issues.remove(&Some(path.clone()));
+        }
+    }
+
+    fn settings_for(&self, paths: &[PathBuf]) -> Settings {
+        let mut settings = self.settings.clone();
+        settings.all = false;
+        settings.sample = None;
+        settings.upstream = None;
+        settings.index = false;
+        settings.index_file = None;
+        settings.paths = paths
+            .iter()
+            .map(|path| self.workspace.root.join(path))
+            .collect();
+        settings
+    }
+
+    fn redraw(&self) -> Result<()> {
+        Term::stdout().clear_screen().ok();
+
+        let issues = self
+            .issues
+            .values()
+            .flatten()
+            .sorted()
+            .cloned()
+            .collect_vec();
+
+        if let Some(report) = &self.report {
+            let mut report = report.clone();
+            report.issues = issues.clone();
+
+            let mut formatter = TextFormatter::new(
+                &report,
+                &self.workspace,
+                &self.settings,
+                false,
+                self.apply_mode,
+            );
+            formatter.write_to(&mut std::io::stdout())?;
+        }
+
+        if let Some(error) = &self.error {
+            eprintln!("{} {}", style("✖").red().bold(), error);
+        }
+
+        if self.verb == ExecutionVerb::Check {
+            if issues.is_empty() {
+                eprintln!("{} No issues", style("✔").green().bold());
+            } else {
+                eprintln!(
+                    "{} {} issues",
+                    style("✖").red().bold(),
+                    issues.len().to_formatted_string(&Locale::en)
+                );
+            }
+        }
+
+        eprintln!(
+            "{}",
+            style("Watching for changes... (press Ctrl+C to exit)").dim()
+        );
+
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
+mod ai;
 mod builder;
+mod cache;
 mod coverage;
 mod download;
 mod exclude;
@@ -17,7 +19,9 @@ pub mod triage;
 pub use self::ignore::{Ignore, ALL_WILDCARD};
 pub use self::overrides::Override;
 use self::smells::Smells;
+pub use ai::{Ai, AiBackend};
 pub use builder::Builder;
+pub use cache::{Cache, CacheBackend};
 pub use coverage::Coverage;
 pub use download::{Cpu, DownloadDef, DownloadFileType, OperatingSystem, System};
 pub use exclude::Exclude;
@@ -81,6 +85,12 @@ pub struct QltyConfig {
     #[serde(default)]
     pub coverage: Coverage,
 
+    #[serde(default)]
+    pub cache: Cache,
+
+    #[serde(default)]
+    pub ai: Ai,
+
     #[serde(default)]
     pub runtimes: EnabledRuntimes,
 
@@ -0,0 +1,90 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AiBackend {
+    /// Generate fixes with Qlty Cloud
+    #[default]
+    Qlty,
+    /// Generate fixes with an OpenAI-compatible chat completions endpoint.
+    /// These fixes are unsafe and only applied with `--unsafe`.
+    Openai,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct Ai {
+    #[serde(default)]
+    pub backend: AiBackend,
+
+    /// Base URL of the `openai` backend, such as `http://localhost:8080/v1`
+    pub url: Option<String>,
+
+    /// Model name sent to the `openai` backend
+    pub model: Option<String>,
+
+    /// Environment variable holding the API key of the `openai` backend
+    #[serde(default = "default_api_key_env")]
+    pub api_key_env: String,
+
+    /// Prompt used by the `openai` backend, sent once for each batch of issues
+    /// in a file. Supports the `${path}`, `${issues}` and `${source}` placeholders.
+    pub prompt: Option<String>,
+
+    /// Seconds to wait for each response from the `openai` backend
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+
+    #[serde(default = "default_max_fixes")]
+    pub max_fixes: usize,
+
+    #[serde(default = "default_max_fixes_per_file")]
+    pub max_fixes_per_file: usize,
+
+    #[serde(default = "default_max_concurrent_fixes")]
+    pub max_concurrent_fixes: usize,
+
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: usize,
+}
+
+impl Default for Ai {
+    fn default() -> Self {
+        Self {
+            backend: AiBackend::default(),
+            url: None,
+            model: None,
+            api_key_env: default_api_key_env(),
+            prompt: None,
+            timeout: default_timeout(),
+            max_fixes: default_max_fixes(),
+            max_fixes_per_file: default_max_fixes_per_file(),
+            max_concurrent_fixes: default_max_concurrent_fixes(),
+            max_batch_size: default_max_batch_size(),
+        }
+    }
+}
+
+fn default_api_key_env() -> String {
+    "OPENAI_API_KEY".to_string()
+}
+
+fn default_timeout() -> u64 {
+    120
+}
+
+fn default_max_fixes() -> usize {
+    500
+}
+
+fn default_max_fixes_per_file() -> usize {
+    30
+}
+
+fn default_max_concurrent_fixes() -> usize {
+    10
+}
+
+fn default_max_batch_size() -> usize {
+    15
+}
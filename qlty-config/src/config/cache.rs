@@ -0,0 +1,31 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheBackend {
+    /// Only cache results in the local `.qlty/results` directory
+    #[default]
+    Local,
+    /// Share results through an HTTP cache supporting GET and PUT
+    Http,
+    /// Share results through a directory, such as a network mount
+    Directory,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct Cache {
+    #[serde(default)]
+    pub backend: CacheBackend,
+
+    /// Base URL of the `http` backend. A bearer token may be provided with
+    /// the `QLTY_CACHE_TOKEN` environment variable.
+    pub url: Option<String>,
+
+    /// Shared directory of the `directory` backend
+    pub directory: Option<String>,
+
+    /// Read results from the shared cache without writing new ones
+    #[serde(default)]
+    pub read_only: bool,
+}
@@ -5,4 +5,15 @@ use serde::{Deserialize, Serialize};
 pub struct Coverage {
     pub paths: Option<Vec<String>>,
     pub ignores: Option<Vec<String>>,
+
+    /// Exclude lines marked with coverage pragmas in the source files, such as
+    /// `LCOV_EXCL_LINE` or `pragma: no cover`, for tools which don't honor them.
+    #[serde(default)]
+    pub pragmas: bool,
+
+    /// Comment marker excluding lines from coverage when `pragmas` is enabled,
+    /// in addition to the pragmas of common coverage tools. Defaults to
+    /// `qlty-ignore-coverage`.
+    #[serde(default)]
+    pub ignore_marker: Option<String>,
 }
@@ -9,6 +9,12 @@ pub trait IssueTransformer: Debug + Send + Sync + 'static {
         Some(issue)
     }
 
+    /// Whether this transformer only hides issues from the output, such as by
+    /// level or baseline, rather than deciding which issues exist.
+    fn hides_issues(&self) -> bool {
+        false
+    }
+
     fn transform_batch(&self, issues: Vec<Issue>) -> Vec<Issue> {
         issues
             .par_iter()
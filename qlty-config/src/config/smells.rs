@@ -1,6 +1,7 @@
 use super::IssueMode;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, JsonSchema)]
 pub struct BooleanLogic {
@@ -56,6 +57,22 @@ pub struct FunctionComplexity {
     pub threshold: Option<usize>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, JsonSchema)]
+pub struct LargeClass {
+    #[serde(default = "_default_true")]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub methods_threshold: Option<usize>,
+
+    #[serde(default)]
+    pub fields_threshold: Option<usize>,
+
+    /// Number of unrelated groups of methods and fields (LCOM4)
+    #[serde(default)]
+    pub lcom4_threshold: Option<usize>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
 pub struct IdenticalCode {
     #[serde(default = "_default_true")]
@@ -74,6 +91,58 @@ pub struct SimilarCode {
     pub threshold: Option<usize>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct NearMissCode {
+    #[serde(default = "_default_true")]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub threshold: Option<usize>,
+
+    /// Minimum share of matching syntax, from 0.0 to 1.0, for two blocks of
+    /// code to be reported as near-miss duplicates
+    #[serde(default)]
+    pub similarity: Option<f64>,
+}
+
+/// A house rule written as a tree-sitter query. Each match is reported as an
+/// issue at the node captured as `@match`, or else at the query's first
+/// capture, once a file has at least `threshold` matches.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct QueryCheck {
+    #[serde(default = "_default_true")]
+    pub enabled: bool,
+
+    /// Rule key of the reported issues
+    pub name: String,
+
+    pub query: String,
+
+    pub message: String,
+
+    /// Languages to run the query against. When empty, it runs against every
+    /// language whose grammar it is valid for.
+    #[serde(default)]
+    pub languages: Vec<String>,
+
+    /// Only check files whose path relative to the workspace root matches one of these globs
+    #[serde(default)]
+    pub file_patterns: Vec<String>,
+
+    #[serde(default)]
+    pub level: Option<String>,
+
+    #[serde(default)]
+    pub category: Option<String>,
+
+    #[serde(default)]
+    pub threshold: Option<usize>,
+
+    /// Thresholds for specific languages, overriding `threshold`
+    #[serde(default)]
+    pub thresholds: HashMap<String, usize>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
 pub struct Duplication {
     #[serde(default)]
@@ -109,11 +178,20 @@ pub struct Smells {
     #[serde(default)]
     pub function_complexity: Option<FunctionComplexity>,
 
+    #[serde(default)]
+    pub large_class: Option<LargeClass>,
+
     #[serde(default)]
     pub identical_code: Option<IdenticalCode>,
 
     #[serde(default)]
     pub similar_code: Option<SimilarCode>,
+
+    #[serde(default)]
+    pub near_miss_code: Option<NearMissCode>,
+
+    #[serde(default)]
+    pub queries: Vec<QueryCheck>,
 }
 
 const fn _default_true() -> bool {
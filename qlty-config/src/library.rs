@@ -63,6 +63,14 @@ impl Library {
         self.local_root.join("results")
     }
 
+    pub fn history_dir(&self) -> PathBuf {
+        self.results_dir().join("history")
+    }
+
+    pub fn profiles_dir(&self) -> PathBuf {
+        self.results_dir().join("profiles")
+    }
+
     pub fn plugin_cachedir_dir(&self) -> PathBuf {
         self.local_root.join("plugin_cachedir")
     }
@@ -83,6 +91,10 @@ impl Library {
         self.local_root.join(".gitignore")
     }
 
+    pub fn baseline_path(&self) -> PathBuf {
+        self.local_root.join("baseline.json")
+    }
+
     pub fn status(&self) -> Result<Vec<FolderStatus>> {
         let mut statuses = vec![];
 
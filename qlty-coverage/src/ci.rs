@@ -1,25 +1,41 @@
+mod azure;
+mod bitbucket;
 mod bitrise;
 mod buildkite;
 mod circleci;
+mod cloudbuild;
+mod codebuild;
 mod codefresh;
+mod custom;
+mod drone;
 mod github;
 mod gitlab;
 mod harness;
 mod jenkins;
 mod semaphore;
+mod teamcity;
 mod travisci;
+mod woodpecker;
 
+pub use azure::AzurePipelines;
+pub use bitbucket::BitbucketPipelines;
 pub use bitrise::Bitrise;
 pub use buildkite::Buildkite;
 pub use circleci::CircleCI;
+pub use cloudbuild::CloudBuild;
+pub use codebuild::CodeBuild;
 pub use codefresh::Codefresh;
+pub use custom::Custom;
+pub use drone::Drone;
 pub use github::GitHub;
 pub use gitlab::GitLab;
 pub use harness::Harness;
 pub use jenkins::Jenkins;
 use qlty_types::tests::v1::CoverageMetadata;
 pub use semaphore::Semaphore;
+pub use teamcity::TeamCity;
 pub use travisci::TravisCI;
+pub use woodpecker::Woodpecker;
 
 const QLTY_CI_UPLOADER_TOOL: &str = "QLTY_CI_UPLOADER_TOOL";
 const QLTY_CI_UPLOADER_TOOL_VERSION: &str = "QLTY_CI_UPLOADER_TOOL_VERSION";
@@ -85,16 +101,25 @@ pub fn current() -> Option<Box<dyn CI>> {
 
 pub fn all() -> Vec<Box<dyn CI>> {
     vec![
+        // Explicit QLTY_CI_* configuration wins over detection
+        Box::<Custom>::default(),
+        Box::<AzurePipelines>::default(),
+        Box::<BitbucketPipelines>::default(),
         Box::<Bitrise>::default(),
         Box::<Buildkite>::default(),
         Box::<CircleCI>::default(),
+        Box::<CloudBuild>::default(),
+        Box::<CodeBuild>::default(),
         Box::<Codefresh>::default(),
+        Box::<Drone>::default(),
         Box::<GitHub>::default(),
         Box::<GitLab>::default(),
         Box::<Harness>::default(),
         Box::<Jenkins>::default(),
         Box::<Semaphore>::default(),
+        Box::<TeamCity>::default(),
         Box::<TravisCI>::default(),
+        Box::<Woodpecker>::default(),
     ]
 }
 
@@ -0,0 +1,293 @@
+use crate::ci::CI;
+use qlty_config::env::{EnvSource, SystemEnv};
+
+#[derive(Debug)]
+pub struct AzurePipelines {
+    env: Box<dyn EnvSource>,
+}
+
+impl Default for AzurePipelines {
+    fn default() -> Self {
+        Self {
+            env: Box::<SystemEnv>::default(),
+        }
+    }
+}
+
+impl AzurePipelines {
+    /// The source branch of a pull request, or else the branch being built,
+    /// as a full ref (e.g. refs/heads/main or refs/tags/v1.0.0).
+    fn source_ref(&self) -> String {
+        self.env
+            .var("SYSTEM_PULLREQUEST_SOURCEBRANCH")
+            .filter(|source| !source.is_empty())
+            .or_else(|| self.env.var("BUILD_SOURCEBRANCH"))
+            .unwrap_or_default()
+    }
+}
+
+impl CI for AzurePipelines {
+    fn detect(&self) -> bool {
+        self.env.var("TF_BUILD").unwrap_or_default().to_lowercase() == "true"
+    }
+
+    fn ci_name(&self) -> String {
+        "Azure Pipelines".to_string()
+    }
+
+    fn ci_url(&self) -> String {
+        self.env
+            .var("SYSTEM_TEAMFOUNDATIONCOLLECTIONURI")
+            .unwrap_or_default()
+    }
+
+    fn branch(&self) -> String {
+        let source_ref = self.source_ref();
+
+        if source_ref.starts_with("refs/tags/") {
+            "".to_string()
+        } else {
+            source_ref
+                .strip_prefix("refs/heads/")
+                .unwrap_or(&source_ref)
+                .to_string()
+        }
+    }
+
+    fn workflow(&self) -> String {
+        self.env.var("BUILD_DEFINITIONNAME").unwrap_or_default()
+    }
+
+    fn job(&self) -> String {
+        self.env.var("SYSTEM_JOBDISPLAYNAME").unwrap_or_default()
+    }
+
+    fn build_id(&self) -> String {
+        self.env.var("BUILD_BUILDID").unwrap_or_default()
+    }
+
+    fn build_url(&self) -> String {
+        let project = self.env.var("SYSTEM_TEAMPROJECT").unwrap_or_default();
+
+        if self.ci_url().is_empty() || project.is_empty() || self.build_id().is_empty() {
+            return "".to_string();
+        }
+
+        format!(
+            "{}/{}/_build/results?buildId={}",
+            self.ci_url().trim_end_matches('/'),
+            project,
+            self.build_id()
+        )
+    }
+
+    fn pull_number(&self) -> String {
+        // GitHub repositories expose the pull request number, while Azure Repos
+        // only expose the pull request ID
+        self.env
+            .var("SYSTEM_PULLREQUEST_PULLREQUESTNUMBER")
+            .filter(|number| !number.is_empty())
+            .or_else(|| self.env.var("SYSTEM_PULLREQUEST_PULLREQUESTID"))
+            .unwrap_or_default()
+    }
+
+    fn repository_name(&self) -> String {
+        self.env.var("BUILD_REPOSITORY_NAME").unwrap_or_default()
+    }
+
+    fn repository_url(&self) -> String {
+        self.env.var("BUILD_REPOSITORY_URI").unwrap_or_default()
+    }
+
+    fn pull_url(&self) -> String {
+        // Azure Pipelines doesn't expose a pull url
+        "".to_string()
+    }
+
+    fn commit_sha(&self) -> String {
+        // On pull request builds BUILD_SOURCEVERSION is the merge commit, so prefer the
+        // head commit of the pull request when it is available.
+        self.env
+            .var("SYSTEM_PULLREQUEST_SOURCECOMMITID")
+            .filter(|sha| !sha.is_empty())
+            .or_else(|| self.env.var("BUILD_SOURCEVERSION"))
+            .unwrap_or_default()
+    }
+
+    fn git_tag(&self) -> Option<String> {
+        self.source_ref()
+            .strip_prefix("refs/tags/")
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HashMapEnv {
+        inner: HashMap<String, String>,
+    }
+
+    impl HashMapEnv {
+        pub fn new(env: HashMap<String, String>) -> Self {
+            Self { inner: env }
+        }
+    }
+
+    impl EnvSource for HashMapEnv {
+        fn var(&self, name: &str) -> Option<String> {
+            self.inner.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detect_ci() {
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::default()),
+        };
+        assert_eq!(ci.detect(), false);
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("TF_BUILD".to_string(), "True".to_string());
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.detect(), true);
+        assert_eq!(&ci.ci_name(), "Azure Pipelines");
+    }
+
+    #[test]
+    fn branch() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "BUILD_SOURCEBRANCH".to_string(),
+            "refs/heads/feature/login".to_string(),
+        );
+
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "feature/login");
+        assert_eq!(ci.git_tag(), None);
+    }
+
+    #[test]
+    fn pull_request() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "BUILD_SOURCEBRANCH".to_string(),
+            "refs/pull/42/merge".to_string(),
+        );
+        env.insert("BUILD_SOURCEVERSION".to_string(), "merge123".to_string());
+        env.insert(
+            "SYSTEM_PULLREQUEST_SOURCEBRANCH".to_string(),
+            "refs/heads/feature".to_string(),
+        );
+        env.insert(
+            "SYSTEM_PULLREQUEST_SOURCECOMMITID".to_string(),
+            "head123".to_string(),
+        );
+        env.insert(
+            "SYSTEM_PULLREQUEST_PULLREQUESTID".to_string(),
+            "1001".to_string(),
+        );
+        env.insert(
+            "SYSTEM_PULLREQUEST_PULLREQUESTNUMBER".to_string(),
+            "42".to_string(),
+        );
+
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "feature");
+        assert_eq!(&ci.commit_sha(), "head123");
+        assert_eq!(&ci.pull_number(), "42");
+    }
+
+    #[test]
+    fn pull_number_from_azure_repos() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "SYSTEM_PULLREQUEST_PULLREQUESTID".to_string(),
+            "1001".to_string(),
+        );
+
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.pull_number(), "1001");
+    }
+
+    #[test]
+    fn commit_sha() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BUILD_SOURCEVERSION".to_string(), "abc123".to_string());
+
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.commit_sha(), "abc123");
+    }
+
+    #[test]
+    fn git_tag() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "BUILD_SOURCEBRANCH".to_string(),
+            "refs/tags/v1.2.3".to_string(),
+        );
+
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.git_tag(), Some("v1.2.3".to_string()));
+        assert_eq!(&ci.branch(), "");
+    }
+
+    #[test]
+    fn build_id() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BUILD_BUILDID".to_string(), "1234".to_string());
+
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.build_id(), "1234");
+    }
+
+    #[test]
+    fn build_url() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BUILD_BUILDID".to_string(), "1234".to_string());
+        env.insert("SYSTEM_TEAMPROJECT".to_string(), "project".to_string());
+        env.insert(
+            "SYSTEM_TEAMFOUNDATIONCOLLECTIONURI".to_string(),
+            "https://dev.azure.com/org/".to_string(),
+        );
+
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(
+            &ci.build_url(),
+            "https://dev.azure.com/org/project/_build/results?buildId=1234"
+        );
+    }
+
+    #[test]
+    fn workflow_and_job() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BUILD_DEFINITIONNAME".to_string(), "CI".to_string());
+        env.insert("SYSTEM_JOBDISPLAYNAME".to_string(), "Test".to_string());
+
+        let ci = AzurePipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.workflow(), "CI");
+        assert_eq!(&ci.job(), "Test");
+    }
+}
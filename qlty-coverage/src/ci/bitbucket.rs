@@ -0,0 +1,226 @@
+use crate::ci::CI;
+use qlty_config::env::{EnvSource, SystemEnv};
+
+#[derive(Debug)]
+pub struct BitbucketPipelines {
+    env: Box<dyn EnvSource>,
+}
+
+impl Default for BitbucketPipelines {
+    fn default() -> Self {
+        Self {
+            env: Box::<SystemEnv>::default(),
+        }
+    }
+}
+
+impl CI for BitbucketPipelines {
+    fn detect(&self) -> bool {
+        self.env.var("BITBUCKET_BUILD_NUMBER").is_some()
+    }
+
+    fn ci_name(&self) -> String {
+        "Bitbucket Pipelines".to_string()
+    }
+
+    fn ci_url(&self) -> String {
+        "https://bitbucket.org".to_string()
+    }
+
+    fn branch(&self) -> String {
+        self.env.var("BITBUCKET_BRANCH").unwrap_or_default()
+    }
+
+    fn workflow(&self) -> String {
+        self.env.var("BITBUCKET_PIPELINE_UUID").unwrap_or_default()
+    }
+
+    fn job(&self) -> String {
+        self.env.var("BITBUCKET_STEP_UUID").unwrap_or_default()
+    }
+
+    fn build_id(&self) -> String {
+        self.env.var("BITBUCKET_BUILD_NUMBER").unwrap_or_default()
+    }
+
+    fn build_url(&self) -> String {
+        if self.repository_url().is_empty() || self.build_id().is_empty() {
+            return "".to_string();
+        }
+
+        format!(
+            "{}/addon/pipelines/home#!/results/{}",
+            self.repository_url(),
+            self.build_id()
+        )
+    }
+
+    fn pull_number(&self) -> String {
+        self.env.var("BITBUCKET_PR_ID").unwrap_or_default()
+    }
+
+    fn repository_name(&self) -> String {
+        self.env.var("BITBUCKET_REPO_FULL_NAME").unwrap_or_default()
+    }
+
+    fn repository_url(&self) -> String {
+        self.env
+            .var("BITBUCKET_GIT_HTTP_ORIGIN")
+            .unwrap_or_default()
+    }
+
+    fn pull_url(&self) -> String {
+        if !self.pull_number().is_empty() && !self.repository_url().is_empty() {
+            format!(
+                "{}/pull-requests/{}",
+                self.repository_url(),
+                self.pull_number()
+            )
+        } else {
+            "".to_string()
+        }
+    }
+
+    fn commit_sha(&self) -> String {
+        self.env.var("BITBUCKET_COMMIT").unwrap_or_default()
+    }
+
+    fn git_tag(&self) -> Option<String> {
+        self.env.var("BITBUCKET_TAG").filter(|tag| !tag.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HashMapEnv {
+        inner: HashMap<String, String>,
+    }
+
+    impl HashMapEnv {
+        pub fn new(env: HashMap<String, String>) -> Self {
+            Self { inner: env }
+        }
+    }
+
+    impl EnvSource for HashMapEnv {
+        fn var(&self, name: &str) -> Option<String> {
+            self.inner.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detect_ci() {
+        let ci = BitbucketPipelines {
+            env: Box::new(HashMapEnv::default()),
+        };
+        assert_eq!(ci.detect(), false);
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BITBUCKET_BUILD_NUMBER".to_string(), "7".to_string());
+        let ci = BitbucketPipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.detect(), true);
+        assert_eq!(&ci.ci_name(), "Bitbucket Pipelines");
+        assert_eq!(&ci.ci_url(), "https://bitbucket.org");
+    }
+
+    #[test]
+    fn branch() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BITBUCKET_BRANCH".to_string(), "main".to_string());
+
+        let ci = BitbucketPipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "main");
+    }
+
+    #[test]
+    fn commit_sha() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BITBUCKET_COMMIT".to_string(), "abc123".to_string());
+
+        let ci = BitbucketPipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.commit_sha(), "abc123");
+    }
+
+    #[test]
+    fn pull_request() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BITBUCKET_PR_ID".to_string(), "42".to_string());
+        env.insert(
+            "BITBUCKET_GIT_HTTP_ORIGIN".to_string(),
+            "http://bitbucket.org/team/repo".to_string(),
+        );
+
+        let ci = BitbucketPipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.pull_number(), "42");
+        assert_eq!(
+            &ci.pull_url(),
+            "http://bitbucket.org/team/repo/pull-requests/42"
+        );
+    }
+
+    #[test]
+    fn build_id() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BITBUCKET_BUILD_NUMBER".to_string(), "1234".to_string());
+
+        let ci = BitbucketPipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.build_id(), "1234");
+    }
+
+    #[test]
+    fn build_url() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BITBUCKET_BUILD_NUMBER".to_string(), "1234".to_string());
+        env.insert(
+            "BITBUCKET_GIT_HTTP_ORIGIN".to_string(),
+            "http://bitbucket.org/team/repo".to_string(),
+        );
+
+        let ci = BitbucketPipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(
+            &ci.build_url(),
+            "http://bitbucket.org/team/repo/addon/pipelines/home#!/results/1234"
+        );
+    }
+
+    #[test]
+    fn repository_name() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "BITBUCKET_REPO_FULL_NAME".to_string(),
+            "team/repo".to_string(),
+        );
+
+        let ci = BitbucketPipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.repository_name(), "team/repo");
+    }
+
+    #[test]
+    fn git_tag() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BITBUCKET_TAG".to_string(), "v1.2.3".to_string());
+
+        let ci = BitbucketPipelines {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.git_tag(), Some("v1.2.3".to_string()));
+    }
+}
@@ -16,7 +16,11 @@ impl Default for Buildkite {
 
 impl CI for Buildkite {
     fn detect(&self) -> bool {
+        // Buildkite-compatible agents set the BUILDKITE_* build variables
+        // without necessarily setting BUILDKITE itself
         self.env.var("BUILDKITE").is_some()
+            || (self.env.var("BUILDKITE_BUILD_ID").is_some()
+                && self.env.var("BUILDKITE_COMMIT").is_some())
     }
 
     fn ci_name(&self) -> String {
@@ -119,6 +123,22 @@ mod test {
         assert_eq!(not_buildkite.detect(), false);
     }
 
+    #[test]
+    fn detect_compatible_ci() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BUILDKITE_BUILD_ID".to_string(), "1234".to_string());
+        let ci = Buildkite {
+            env: Box::new(HashMapEnv::new(env.clone())),
+        };
+        assert_eq!(ci.detect(), false);
+
+        env.insert("BUILDKITE_COMMIT".to_string(), "abc123".to_string());
+        let ci = Buildkite {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.detect(), true);
+    }
+
     #[test]
     fn branch() {
         let mut env: HashMap<String, String> = HashMap::default();
@@ -0,0 +1,194 @@
+use crate::ci::CI;
+use qlty_config::env::{EnvSource, SystemEnv};
+
+/// Google Cloud Build exposes its substitutions to build steps only when they
+/// are mapped into `env` in cloudbuild.yaml, using the substitution names
+/// (BUILD_ID, PROJECT_ID, LOCATION, BRANCH_NAME, TAG_NAME, COMMIT_SHA,
+/// REPO_NAME, TRIGGER_NAME and _PR_NUMBER).
+#[derive(Debug)]
+pub struct CloudBuild {
+    env: Box<dyn EnvSource>,
+}
+
+impl Default for CloudBuild {
+    fn default() -> Self {
+        Self {
+            env: Box::<SystemEnv>::default(),
+        }
+    }
+}
+
+impl CI for CloudBuild {
+    fn detect(&self) -> bool {
+        // Set by Cloud Build in every step, unlike the substitutions
+        self.env.var("BUILDER_OUTPUT").is_some()
+    }
+
+    fn ci_name(&self) -> String {
+        "Google Cloud Build".to_string()
+    }
+
+    fn ci_url(&self) -> String {
+        "https://console.cloud.google.com/cloud-build".to_string()
+    }
+
+    fn branch(&self) -> String {
+        self.env.var("BRANCH_NAME").unwrap_or_default()
+    }
+
+    fn workflow(&self) -> String {
+        self.env.var("TRIGGER_NAME").unwrap_or_default()
+    }
+
+    fn job(&self) -> String {
+        "".to_string()
+    }
+
+    fn build_id(&self) -> String {
+        self.env.var("BUILD_ID").unwrap_or_default()
+    }
+
+    fn build_url(&self) -> String {
+        let location = self
+            .env
+            .var("LOCATION")
+            .unwrap_or_else(|| "global".to_string());
+        let project = self.env.var("PROJECT_ID").unwrap_or_default();
+
+        if project.is_empty() || self.build_id().is_empty() {
+            return "".to_string();
+        }
+
+        format!(
+            "{}/builds;region={}/{}?project={}",
+            self.ci_url(),
+            location,
+            self.build_id(),
+            project
+        )
+    }
+
+    fn pull_number(&self) -> String {
+        self.env.var("_PR_NUMBER").unwrap_or_default()
+    }
+
+    fn repository_name(&self) -> String {
+        self.env.var("REPO_NAME").unwrap_or_default()
+    }
+
+    fn repository_url(&self) -> String {
+        "".to_string()
+    }
+
+    fn pull_url(&self) -> String {
+        "".to_string()
+    }
+
+    fn commit_sha(&self) -> String {
+        self.env.var("COMMIT_SHA").unwrap_or_default()
+    }
+
+    fn git_tag(&self) -> Option<String> {
+        self.env.var("TAG_NAME").filter(|tag| !tag.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HashMapEnv {
+        inner: HashMap<String, String>,
+    }
+
+    impl HashMapEnv {
+        pub fn new(env: HashMap<String, String>) -> Self {
+            Self { inner: env }
+        }
+    }
+
+    impl EnvSource for HashMapEnv {
+        fn var(&self, name: &str) -> Option<String> {
+            self.inner.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detect_ci() {
+        let ci = CloudBuild {
+            env: Box::new(HashMapEnv::default()),
+        };
+        assert_eq!(ci.detect(), false);
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BUILDER_OUTPUT".to_string(), "/builder/outputs".to_string());
+        let ci = CloudBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.detect(), true);
+        assert_eq!(&ci.ci_name(), "Google Cloud Build");
+    }
+
+    #[test]
+    fn branch() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BRANCH_NAME".to_string(), "main".to_string());
+
+        let ci = CloudBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "main");
+    }
+
+    #[test]
+    fn pull_number() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("_PR_NUMBER".to_string(), "42".to_string());
+
+        let ci = CloudBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.pull_number(), "42");
+    }
+
+    #[test]
+    fn commit_sha() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("COMMIT_SHA".to_string(), "abc123".to_string());
+
+        let ci = CloudBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.commit_sha(), "abc123");
+    }
+
+    #[test]
+    fn build_url() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BUILD_ID".to_string(), "b-1234".to_string());
+        env.insert("PROJECT_ID".to_string(), "my-project".to_string());
+        env.insert("LOCATION".to_string(), "us-central1".to_string());
+
+        let ci = CloudBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.build_id(), "b-1234");
+        assert_eq!(
+            &ci.build_url(),
+            "https://console.cloud.google.com/cloud-build/builds;region=us-central1/b-1234?project=my-project"
+        );
+    }
+
+    #[test]
+    fn git_tag() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("TAG_NAME".to_string(), "v1.2.3".to_string());
+
+        let ci = CloudBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.git_tag(), Some("v1.2.3".to_string()));
+    }
+}
@@ -0,0 +1,264 @@
+use crate::ci::CI;
+use qlty_config::env::{EnvSource, SystemEnv};
+
+#[derive(Debug)]
+pub struct CodeBuild {
+    env: Box<dyn EnvSource>,
+}
+
+impl Default for CodeBuild {
+    fn default() -> Self {
+        Self {
+            env: Box::<SystemEnv>::default(),
+        }
+    }
+}
+
+impl CodeBuild {
+    /// The event that triggered a webhook build, such as `pr/42`,
+    /// `branch/main` or `tag/v1.0.0`, falling back to the source version.
+    fn trigger(&self) -> String {
+        self.env
+            .var("CODEBUILD_WEBHOOK_TRIGGER")
+            .filter(|trigger| !trigger.is_empty())
+            .or_else(|| self.env.var("CODEBUILD_SOURCE_VERSION"))
+            .unwrap_or_default()
+    }
+
+    fn project(&self) -> String {
+        // CODEBUILD_BUILD_ID is formatted as <project>:<uuid>
+        self.build_id()
+            .split_once(':')
+            .map(|(project, _)| project.to_string())
+            .unwrap_or_default()
+    }
+}
+
+impl CI for CodeBuild {
+    fn detect(&self) -> bool {
+        self.env.var("CODEBUILD_BUILD_ID").is_some()
+    }
+
+    fn ci_name(&self) -> String {
+        "AWS CodeBuild".to_string()
+    }
+
+    fn ci_url(&self) -> String {
+        "https://console.aws.amazon.com/codesuite/codebuild".to_string()
+    }
+
+    fn branch(&self) -> String {
+        let head_ref = self
+            .env
+            .var("CODEBUILD_WEBHOOK_HEAD_REF")
+            .unwrap_or_default();
+
+        if let Some(branch) = head_ref.strip_prefix("refs/heads/") {
+            return branch.to_string();
+        }
+
+        self.trigger()
+            .strip_prefix("branch/")
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn workflow(&self) -> String {
+        self.project()
+    }
+
+    fn job(&self) -> String {
+        // CodeBuild runs a single job per build
+        "".to_string()
+    }
+
+    fn build_id(&self) -> String {
+        self.env.var("CODEBUILD_BUILD_ID").unwrap_or_default()
+    }
+
+    fn build_url(&self) -> String {
+        let region = self.env.var("AWS_REGION").unwrap_or_default();
+
+        if region.is_empty() || self.project().is_empty() {
+            return "".to_string();
+        }
+
+        format!(
+            "https://{region}.console.aws.amazon.com/codesuite/codebuild/projects/{}/build/{}/?region={region}",
+            self.project(),
+            self.build_id().replace(':', "%3A"),
+        )
+    }
+
+    fn pull_number(&self) -> String {
+        self.trigger()
+            .strip_prefix("pr/")
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn repository_name(&self) -> String {
+        // CodeBuild doesn't expose a repository name
+        "".to_string()
+    }
+
+    fn repository_url(&self) -> String {
+        self.env
+            .var("CODEBUILD_SOURCE_REPO_URL")
+            .unwrap_or_default()
+    }
+
+    fn pull_url(&self) -> String {
+        // CodeBuild doesn't expose a pull url
+        "".to_string()
+    }
+
+    fn commit_sha(&self) -> String {
+        self.env
+            .var("CODEBUILD_RESOLVED_SOURCE_VERSION")
+            .unwrap_or_default()
+    }
+
+    fn git_tag(&self) -> Option<String> {
+        self.trigger()
+            .strip_prefix("tag/")
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HashMapEnv {
+        inner: HashMap<String, String>,
+    }
+
+    impl HashMapEnv {
+        pub fn new(env: HashMap<String, String>) -> Self {
+            Self { inner: env }
+        }
+    }
+
+    impl EnvSource for HashMapEnv {
+        fn var(&self, name: &str) -> Option<String> {
+            self.inner.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detect_ci() {
+        let ci = CodeBuild {
+            env: Box::new(HashMapEnv::default()),
+        };
+        assert_eq!(ci.detect(), false);
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CODEBUILD_BUILD_ID".to_string(), "app:0a1b2c".to_string());
+        let ci = CodeBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.detect(), true);
+        assert_eq!(&ci.ci_name(), "AWS CodeBuild");
+        assert_eq!(&ci.workflow(), "app");
+    }
+
+    #[test]
+    fn branch() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "CODEBUILD_WEBHOOK_HEAD_REF".to_string(),
+            "refs/heads/feature".to_string(),
+        );
+
+        let ci = CodeBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "feature");
+    }
+
+    #[test]
+    fn branch_from_trigger() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "CODEBUILD_WEBHOOK_TRIGGER".to_string(),
+            "branch/main".to_string(),
+        );
+
+        let ci = CodeBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "main");
+        assert_eq!(&ci.pull_number(), "");
+    }
+
+    #[test]
+    fn pull_number() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CODEBUILD_WEBHOOK_TRIGGER".to_string(), "pr/42".to_string());
+
+        let ci = CodeBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.pull_number(), "42");
+    }
+
+    #[test]
+    fn pull_number_from_source_version() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CODEBUILD_SOURCE_VERSION".to_string(), "pr/7".to_string());
+
+        let ci = CodeBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.pull_number(), "7");
+    }
+
+    #[test]
+    fn commit_sha() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "CODEBUILD_RESOLVED_SOURCE_VERSION".to_string(),
+            "abc123".to_string(),
+        );
+
+        let ci = CodeBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.commit_sha(), "abc123");
+    }
+
+    #[test]
+    fn build_url() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CODEBUILD_BUILD_ID".to_string(), "app:0a1b2c".to_string());
+        env.insert("AWS_REGION".to_string(), "us-east-1".to_string());
+
+        let ci = CodeBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.build_id(), "app:0a1b2c");
+        assert_eq!(
+            &ci.build_url(),
+            "https://us-east-1.console.aws.amazon.com/codesuite/codebuild/projects/app/build/app%3A0a1b2c/?region=us-east-1"
+        );
+    }
+
+    #[test]
+    fn git_tag() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "CODEBUILD_WEBHOOK_TRIGGER".to_string(),
+            "tag/v1.2.3".to_string(),
+        );
+
+        let ci = CodeBuild {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.git_tag(), Some("v1.2.3".to_string()));
+        assert_eq!(&ci.branch(), "");
+    }
+}
@@ -0,0 +1,181 @@
+use crate::ci::CI;
+use qlty_config::env::{EnvSource, SystemEnv};
+
+/// A CI system described entirely by QLTY_CI_* environment variables, for
+/// providers that are not detected automatically. Setting QLTY_CI_NAME
+/// enables it, and it takes precedence over any detected provider.
+#[derive(Debug)]
+pub struct Custom {
+    env: Box<dyn EnvSource>,
+}
+
+impl Default for Custom {
+    fn default() -> Self {
+        Self {
+            env: Box::<SystemEnv>::default(),
+        }
+    }
+}
+
+impl Custom {
+    fn var(&self, name: &str) -> String {
+        self.env
+            .var(&format!("QLTY_CI_{}", name))
+            .unwrap_or_default()
+    }
+}
+
+impl CI for Custom {
+    fn detect(&self) -> bool {
+        !self.var("NAME").is_empty()
+    }
+
+    fn ci_name(&self) -> String {
+        self.var("NAME")
+    }
+
+    fn ci_url(&self) -> String {
+        self.var("URL")
+    }
+
+    fn branch(&self) -> String {
+        self.var("BRANCH")
+    }
+
+    fn workflow(&self) -> String {
+        self.var("WORKFLOW")
+    }
+
+    fn job(&self) -> String {
+        self.var("JOB")
+    }
+
+    fn build_id(&self) -> String {
+        self.var("BUILD_ID")
+    }
+
+    fn build_url(&self) -> String {
+        self.var("BUILD_URL")
+    }
+
+    fn pull_number(&self) -> String {
+        self.var("PULL_NUMBER")
+    }
+
+    fn repository_name(&self) -> String {
+        self.var("REPOSITORY_NAME")
+    }
+
+    fn repository_url(&self) -> String {
+        self.var("REPOSITORY_URL")
+    }
+
+    fn pull_url(&self) -> String {
+        self.var("PULL_URL")
+    }
+
+    fn commit_sha(&self) -> String {
+        self.var("COMMIT_SHA")
+    }
+
+    fn git_tag(&self) -> Option<String> {
+        Some(self.var("GIT_TAG")).filter(|tag| !tag.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HashMapEnv {
+        inner: HashMap<String, String>,
+    }
+
+    impl HashMapEnv {
+        pub fn new(env: HashMap<String, String>) -> Self {
+            Self { inner: env }
+        }
+    }
+
+    impl EnvSource for HashMapEnv {
+        fn var(&self, name: &str) -> Option<String> {
+            self.inner.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detect_ci() {
+        let ci = Custom {
+            env: Box::new(HashMapEnv::default()),
+        };
+        assert_eq!(ci.detect(), false);
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("QLTY_CI_NAME".to_string(), "".to_string());
+        let ci = Custom {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.detect(), false);
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("QLTY_CI_NAME".to_string(), "Concourse".to_string());
+        let ci = Custom {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.detect(), true);
+        assert_eq!(&ci.ci_name(), "Concourse");
+    }
+
+    #[test]
+    fn metadata() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("QLTY_CI_NAME".to_string(), "Concourse".to_string());
+        env.insert("QLTY_CI_BRANCH".to_string(), "feature".to_string());
+        env.insert("QLTY_CI_COMMIT_SHA".to_string(), "abc123".to_string());
+        env.insert("QLTY_CI_PULL_NUMBER".to_string(), "42".to_string());
+        env.insert("QLTY_CI_BUILD_ID".to_string(), "1234".to_string());
+
+        let ci = Custom {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        let metadata = ci.metadata();
+        assert_eq!(&metadata.ci, "Concourse");
+        assert_eq!(&metadata.branch, "feature");
+        assert_eq!(&metadata.commit_sha, "abc123");
+        assert_eq!(&metadata.pull_request_number, "42");
+        assert_eq!(&metadata.build_id, "1234");
+        assert_eq!(metadata.git_tag, None);
+    }
+
+    #[test]
+    fn git_tag() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("QLTY_CI_GIT_TAG".to_string(), "v1.2.3".to_string());
+
+        let ci = Custom {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.git_tag(), Some("v1.2.3".to_string()));
+    }
+
+    #[test]
+    fn urls() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "QLTY_CI_BUILD_URL".to_string(),
+            "https://ci.example.com/builds/1234".to_string(),
+        );
+        env.insert(
+            "QLTY_CI_REPOSITORY_URL".to_string(),
+            "https://git.example.com/org/repo".to_string(),
+        );
+
+        let ci = Custom {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.build_url(), "https://ci.example.com/builds/1234");
+        assert_eq!(&ci.repository_url(), "https://git.example.com/org/repo");
+    }
+}
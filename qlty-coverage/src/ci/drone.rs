@@ -0,0 +1,209 @@
+use crate::ci::CI;
+use qlty_config::env::{EnvSource, SystemEnv};
+
+#[derive(Debug)]
+pub struct Drone {
+    env: Box<dyn EnvSource>,
+}
+
+impl Default for Drone {
+    fn default() -> Self {
+        Self {
+            env: Box::<SystemEnv>::default(),
+        }
+    }
+}
+
+impl CI for Drone {
+    fn detect(&self) -> bool {
+        // Harness also sets DRONE=true, and is detected on its own signal
+        self.env.var("DRONE").unwrap_or_default() == "true"
+            && self.env.var("HARNESS_EXECUTION_ID").is_none()
+    }
+
+    fn ci_name(&self) -> String {
+        "Drone".to_string()
+    }
+
+    fn ci_url(&self) -> String {
+        match (
+            self.env.var("DRONE_SYSTEM_PROTO"),
+            self.env.var("DRONE_SYSTEM_HOST"),
+        ) {
+            (Some(proto), Some(host)) => format!("{}://{}", proto, host),
+            _ => "".to_string(),
+        }
+    }
+
+    fn branch(&self) -> String {
+        // On PR builds DRONE_BRANCH is the target branch, so prefer the source branch.
+        if !self.pull_number().is_empty() {
+            let source = self.env.var("DRONE_SOURCE_BRANCH").unwrap_or_default();
+            if !source.is_empty() {
+                return source;
+            }
+        }
+        self.env.var("DRONE_BRANCH").unwrap_or_default()
+    }
+
+    fn workflow(&self) -> String {
+        self.env.var("DRONE_STAGE_NAME").unwrap_or_default()
+    }
+
+    fn job(&self) -> String {
+        self.env.var("DRONE_STEP_NAME").unwrap_or_default()
+    }
+
+    fn build_id(&self) -> String {
+        self.env.var("DRONE_BUILD_NUMBER").unwrap_or_default()
+    }
+
+    fn build_url(&self) -> String {
+        self.env.var("DRONE_BUILD_LINK").unwrap_or_default()
+    }
+
+    fn pull_number(&self) -> String {
+        self.env.var("DRONE_PULL_REQUEST").unwrap_or_default()
+    }
+
+    fn repository_name(&self) -> String {
+        self.env.var("DRONE_REPO").unwrap_or_default()
+    }
+
+    fn repository_url(&self) -> String {
+        self.env.var("DRONE_REPO_LINK").unwrap_or_default()
+    }
+
+    fn pull_url(&self) -> String {
+        // Drone doesn't expose a pull url
+        "".to_string()
+    }
+
+    fn commit_sha(&self) -> String {
+        self.env.var("DRONE_COMMIT_SHA").unwrap_or_default()
+    }
+
+    fn git_tag(&self) -> Option<String> {
+        self.env.var("DRONE_TAG").filter(|tag| !tag.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HashMapEnv {
+        inner: HashMap<String, String>,
+    }
+
+    impl HashMapEnv {
+        pub fn new(env: HashMap<String, String>) -> Self {
+            Self { inner: env }
+        }
+    }
+
+    impl EnvSource for HashMapEnv {
+        fn var(&self, name: &str) -> Option<String> {
+            self.inner.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detect_ci() {
+        let ci = Drone {
+            env: Box::new(HashMapEnv::default()),
+        };
+        assert_eq!(ci.detect(), false);
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("DRONE".to_string(), "true".to_string());
+        env.insert("DRONE_SYSTEM_PROTO".to_string(), "https".to_string());
+        env.insert(
+            "DRONE_SYSTEM_HOST".to_string(),
+            "drone.example.com".to_string(),
+        );
+        let ci = Drone {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.detect(), true);
+        assert_eq!(&ci.ci_name(), "Drone");
+        assert_eq!(&ci.ci_url(), "https://drone.example.com");
+    }
+
+    #[test]
+    fn does_not_detect_harness() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("DRONE".to_string(), "true".to_string());
+        env.insert("HARNESS_EXECUTION_ID".to_string(), "exec-1".to_string());
+
+        let ci = Drone {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.detect(), false);
+    }
+
+    #[test]
+    fn branch() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("DRONE_BRANCH".to_string(), "main".to_string());
+
+        let ci = Drone {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "main");
+    }
+
+    #[test]
+    fn pull_request() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("DRONE_BRANCH".to_string(), "main".to_string());
+        env.insert("DRONE_SOURCE_BRANCH".to_string(), "feature".to_string());
+        env.insert("DRONE_PULL_REQUEST".to_string(), "42".to_string());
+
+        let ci = Drone {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "feature");
+        assert_eq!(&ci.pull_number(), "42");
+    }
+
+    #[test]
+    fn commit_sha() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("DRONE_COMMIT_SHA".to_string(), "abc123".to_string());
+
+        let ci = Drone {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.commit_sha(), "abc123");
+    }
+
+    #[test]
+    fn build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("DRONE_BUILD_NUMBER".to_string(), "1234".to_string());
+        env.insert(
+            "DRONE_BUILD_LINK".to_string(),
+            "https://drone.example.com/org/repo/1234".to_string(),
+        );
+
+        let ci = Drone {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.build_id(), "1234");
+        assert_eq!(&ci.build_url(), "https://drone.example.com/org/repo/1234");
+    }
+
+    #[test]
+    fn git_tag() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("DRONE_TAG".to_string(), "v1.2.3".to_string());
+
+        let ci = Drone {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.git_tag(), Some("v1.2.3".to_string()));
+    }
+}
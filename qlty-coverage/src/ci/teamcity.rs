@@ -0,0 +1,286 @@
+use crate::ci::CI;
+use qlty_config::env::{EnvSource, SystemEnv};
+use std::collections::HashMap;
+
+/// TeamCity does not set environment variables for the branch or the pull
+/// request number. They are read from the `teamcity.build.branch` and
+/// `teamcity.pullRequest.number` configuration parameters, which TeamCity
+/// writes to a properties file referenced by TEAMCITY_BUILD_PROPERTIES_FILE.
+/// BRANCH_NAME and PULL_REQUEST_NUMBER take precedence when they are mapped
+/// from those parameters in the build configuration, such as
+/// `env.BRANCH_NAME = %teamcity.build.branch%`.
+#[derive(Debug)]
+pub struct TeamCity {
+    env: Box<dyn EnvSource>,
+}
+
+impl Default for TeamCity {
+    fn default() -> Self {
+        Self {
+            env: Box::<SystemEnv>::default(),
+        }
+    }
+}
+
+impl CI for TeamCity {
+    fn detect(&self) -> bool {
+        self.env.var("TEAMCITY_VERSION").is_some()
+    }
+
+    fn ci_name(&self) -> String {
+        "TeamCity".to_string()
+    }
+
+    fn ci_url(&self) -> String {
+        // TeamCity doesn't expose its server url
+        "".to_string()
+    }
+
+    fn branch(&self) -> String {
+        let branch = self
+            .env
+            .var("BRANCH_NAME")
+            .or_else(|| self.build_parameter("teamcity.build.branch"))
+            .unwrap_or_default();
+        branch
+            .strip_prefix("refs/heads/")
+            .unwrap_or(&branch)
+            .to_string()
+    }
+
+    fn workflow(&self) -> String {
+        self.env.var("TEAMCITY_PROJECT_NAME").unwrap_or_default()
+    }
+
+    fn job(&self) -> String {
+        self.env.var("TEAMCITY_BUILDCONF_NAME").unwrap_or_default()
+    }
+
+    fn build_id(&self) -> String {
+        self.env.var("BUILD_NUMBER").unwrap_or_default()
+    }
+
+    fn build_url(&self) -> String {
+        // TeamCity doesn't expose a build url
+        "".to_string()
+    }
+
+    fn pull_number(&self) -> String {
+        self.env
+            .var("PULL_REQUEST_NUMBER")
+            .or_else(|| self.build_parameter("teamcity.pullRequest.number"))
+            .unwrap_or_default()
+    }
+
+    fn repository_name(&self) -> String {
+        "".to_string()
+    }
+
+    fn repository_url(&self) -> String {
+        "".to_string()
+    }
+
+    fn pull_url(&self) -> String {
+        "".to_string()
+    }
+
+    fn commit_sha(&self) -> String {
+        self.env.var("BUILD_VCS_NUMBER").unwrap_or_default()
+    }
+}
+
+impl TeamCity {
+    // Configuration parameters are in a second file, referenced from the
+    // build properties file
+    fn build_parameter(&self, name: &str) -> Option<String> {
+        let build_properties = read_properties(&self.env.var("TEAMCITY_BUILD_PROPERTIES_FILE")?)?;
+        let configuration_properties =
+            read_properties(build_properties.get("teamcity.configuration.properties.file")?)?;
+
+        configuration_properties
+            .get(name)
+            .or_else(|| build_properties.get(name))
+            .filter(|value| !value.is_empty())
+            .cloned()
+    }
+}
+
+/// Parses the `key=value` lines of a Java properties file, as written by
+/// TeamCity, where `:`, `=` and `\` are escaped with a backslash.
+fn read_properties(path: &str) -> Option<HashMap<String, String>> {
+    let contents = std::fs::read_to_string(path).ok()?;
+
+    Some(
+        contents
+            .lines()
+            .map(str::trim_start)
+            .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+            .filter_map(|line| {
+                let mut key = String::new();
+                let mut chars = line.chars();
+
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => key.extend(chars.next()),
+                        '=' | ':' => return Some((key, unescape(chars.as_str()))),
+                        _ => key.push(c),
+                    }
+                }
+
+                None
+            })
+            .collect(),
+    )
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            _ => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HashMapEnv {
+        inner: HashMap<String, String>,
+    }
+
+    impl HashMapEnv {
+        pub fn new(env: HashMap<String, String>) -> Self {
+            Self { inner: env }
+        }
+    }
+
+    impl EnvSource for HashMapEnv {
+        fn var(&self, name: &str) -> Option<String> {
+            self.inner.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detect_ci() {
+        let ci = TeamCity {
+            env: Box::new(HashMapEnv::default()),
+        };
+        assert_eq!(ci.detect(), false);
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("TEAMCITY_VERSION".to_string(), "2024.03".to_string());
+        let ci = TeamCity {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.detect(), true);
+        assert_eq!(&ci.ci_name(), "TeamCity");
+    }
+
+    #[test]
+    fn branch() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BRANCH_NAME".to_string(), "refs/heads/main".to_string());
+
+        let ci = TeamCity {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "main");
+    }
+
+    #[test]
+    fn pull_number() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("PULL_REQUEST_NUMBER".to_string(), "42".to_string());
+
+        let ci = TeamCity {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.pull_number(), "42");
+    }
+
+    #[test]
+    fn branch_and_pull_number_from_build_properties() {
+        let directory = tempfile::tempdir().unwrap();
+        let configuration_file = directory.path().join("config.properties");
+        let build_file = directory.path().join("build.properties");
+
+        std::fs::write(
+            &configuration_file,
+            "#TeamCity build configuration parameters\nteamcity.build.branch=refs/heads/feature\\:1\nteamcity.pullRequest.number=42\n",
+        )
+        .unwrap();
+        std::fs::write(
+            &build_file,
+            format!(
+                "teamcity.configuration.properties.file={}\n",
+                configuration_file
+                    .display()
+                    .to_string()
+                    .replace('\\', "\\\\")
+                    .replace(':', "\\:")
+            ),
+        )
+        .unwrap();
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert(
+            "TEAMCITY_BUILD_PROPERTIES_FILE".to_string(),
+            build_file.display().to_string(),
+        );
+
+        let ci = TeamCity {
+            env: Box::new(HashMapEnv::new(env.clone())),
+        };
+        assert_eq!(&ci.branch(), "feature:1");
+        assert_eq!(&ci.pull_number(), "42");
+
+        env.insert("BRANCH_NAME".to_string(), "main".to_string());
+        let ci = TeamCity {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "main");
+    }
+
+    #[test]
+    fn commit_sha() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BUILD_VCS_NUMBER".to_string(), "abc123".to_string());
+
+        let ci = TeamCity {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.commit_sha(), "abc123");
+    }
+
+    #[test]
+    fn build_id() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("BUILD_NUMBER".to_string(), "1234".to_string());
+
+        let ci = TeamCity {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.build_id(), "1234");
+    }
+
+    #[test]
+    fn workflow_and_job() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("TEAMCITY_PROJECT_NAME".to_string(), "App".to_string());
+        env.insert("TEAMCITY_BUILDCONF_NAME".to_string(), "Test".to_string());
+
+        let ci = TeamCity {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.workflow(), "App");
+        assert_eq!(&ci.job(), "Test");
+    }
+}
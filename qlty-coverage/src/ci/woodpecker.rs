@@ -0,0 +1,186 @@
+use crate::ci::CI;
+use qlty_config::env::{EnvSource, SystemEnv};
+
+#[derive(Debug)]
+pub struct Woodpecker {
+    env: Box<dyn EnvSource>,
+}
+
+impl Default for Woodpecker {
+    fn default() -> Self {
+        Self {
+            env: Box::<SystemEnv>::default(),
+        }
+    }
+}
+
+impl CI for Woodpecker {
+    fn detect(&self) -> bool {
+        self.env.var("CI").unwrap_or_default() == "woodpecker"
+    }
+
+    fn ci_name(&self) -> String {
+        "Woodpecker".to_string()
+    }
+
+    fn ci_url(&self) -> String {
+        self.env.var("CI_SYSTEM_URL").unwrap_or_default()
+    }
+
+    fn branch(&self) -> String {
+        // On PR builds CI_COMMIT_BRANCH is the target branch, so prefer the source branch.
+        if !self.pull_number().is_empty() {
+            let source = self.env.var("CI_COMMIT_SOURCE_BRANCH").unwrap_or_default();
+            if !source.is_empty() {
+                return source;
+            }
+        }
+        self.env.var("CI_COMMIT_BRANCH").unwrap_or_default()
+    }
+
+    fn workflow(&self) -> String {
+        self.env.var("CI_WORKFLOW_NAME").unwrap_or_default()
+    }
+
+    fn job(&self) -> String {
+        self.env.var("CI_STEP_NAME").unwrap_or_default()
+    }
+
+    fn build_id(&self) -> String {
+        self.env.var("CI_PIPELINE_NUMBER").unwrap_or_default()
+    }
+
+    fn build_url(&self) -> String {
+        self.env.var("CI_PIPELINE_URL").unwrap_or_default()
+    }
+
+    fn pull_number(&self) -> String {
+        self.env.var("CI_COMMIT_PULL_REQUEST").unwrap_or_default()
+    }
+
+    fn repository_name(&self) -> String {
+        self.env.var("CI_REPO").unwrap_or_default()
+    }
+
+    fn repository_url(&self) -> String {
+        self.env.var("CI_REPO_URL").unwrap_or_default()
+    }
+
+    fn pull_url(&self) -> String {
+        // Woodpecker doesn't expose a pull url
+        "".to_string()
+    }
+
+    fn commit_sha(&self) -> String {
+        self.env.var("CI_COMMIT_SHA").unwrap_or_default()
+    }
+
+    fn git_tag(&self) -> Option<String> {
+        self.env.var("CI_COMMIT_TAG").filter(|tag| !tag.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HashMapEnv {
+        inner: HashMap<String, String>,
+    }
+
+    impl HashMapEnv {
+        pub fn new(env: HashMap<String, String>) -> Self {
+            Self { inner: env }
+        }
+    }
+
+    impl EnvSource for HashMapEnv {
+        fn var(&self, name: &str) -> Option<String> {
+            self.inner.get(name).cloned()
+        }
+    }
+
+    #[test]
+    fn detect_ci() {
+        let ci = Woodpecker {
+            env: Box::new(HashMapEnv::default()),
+        };
+        assert_eq!(ci.detect(), false);
+
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CI".to_string(), "woodpecker".to_string());
+        let ci = Woodpecker {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.detect(), true);
+        assert_eq!(&ci.ci_name(), "Woodpecker");
+    }
+
+    #[test]
+    fn branch() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CI_COMMIT_BRANCH".to_string(), "main".to_string());
+
+        let ci = Woodpecker {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "main");
+    }
+
+    #[test]
+    fn pull_request() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CI_COMMIT_BRANCH".to_string(), "main".to_string());
+        env.insert("CI_COMMIT_SOURCE_BRANCH".to_string(), "feature".to_string());
+        env.insert("CI_COMMIT_PULL_REQUEST".to_string(), "42".to_string());
+
+        let ci = Woodpecker {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.branch(), "feature");
+        assert_eq!(&ci.pull_number(), "42");
+    }
+
+    #[test]
+    fn commit_sha() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CI_COMMIT_SHA".to_string(), "abc123".to_string());
+
+        let ci = Woodpecker {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.commit_sha(), "abc123");
+    }
+
+    #[test]
+    fn build() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CI_PIPELINE_NUMBER".to_string(), "1234".to_string());
+        env.insert(
+            "CI_PIPELINE_URL".to_string(),
+            "https://ci.example.com/repos/1/pipeline/1234".to_string(),
+        );
+
+        let ci = Woodpecker {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(&ci.build_id(), "1234");
+        assert_eq!(
+            &ci.build_url(),
+            "https://ci.example.com/repos/1/pipeline/1234"
+        );
+    }
+
+    #[test]
+    fn git_tag() {
+        let mut env: HashMap<String, String> = HashMap::default();
+        env.insert("CI_COMMIT_TAG".to_string(), "v1.2.3".to_string());
+
+        let ci = Woodpecker {
+            env: Box::new(HashMapEnv::new(env)),
+        };
+        assert_eq!(ci.git_tag(), Some("v1.2.3".to_string()));
+    }
+}
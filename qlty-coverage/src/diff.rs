@@ -0,0 +1,216 @@
+use qlty_analysis::code::FileIndex;
+use qlty_types::tests::v1::FileCoverage;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Coverage of the lines added or changed in a diff.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct DiffCoverage {
+    pub files: Vec<FileDiffCoverage>,
+    pub covered_lines: u64,
+    pub uncovered_lines: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct FileDiffCoverage {
+    pub path: String,
+    pub covered_lines: u64,
+    pub uncovered_lines: u64,
+    /// Inclusive ranges of changed lines which are not covered
+    pub uncovered_ranges: Vec<(u32, u32)>,
+}
+
+impl FileDiffCoverage {
+    pub fn coverage_percentage(&self) -> Option<f64> {
+        percentage(self.covered_lines, self.uncovered_lines)
+    }
+}
+
+impl DiffCoverage {
+    /// Intersects the file coverages with the changed lines of the diff.
+    /// Reports for the same file are combined, so a line counts as covered if
+    /// any report covers it. Changed lines which are not executable are
+    /// ignored, as are changed files without coverage data.
+    pub fn compute(file_coverages: &[FileCoverage], changed_lines: &FileIndex) -> Self {
+        let mut combined: BTreeMap<&str, Vec<i64>> = BTreeMap::new();
+
+        for file_coverage in file_coverages {
+            let hits = combined.entry(file_coverage.path.as_str()).or_default();
+
+            if hits.len() < file_coverage.hits.len() {
+                hits.resize(file_coverage.hits.len(), -1);
+            }
+
+            for (combined_hit, hit) in hits.iter_mut().zip(&file_coverage.hits) {
+                *combined_hit = (*combined_hit).max(*hit);
+            }
+        }
+
+        let mut diff_coverage = Self::default();
+
+        for (path, hits) in combined {
+            if !changed_lines.matches_path(Path::new(path)) {
+                continue;
+            }
+
+            let file = Self::compute_file(path, &hits, changed_lines);
+
+            if file.covered_lines + file.uncovered_lines > 0 {
+                diff_coverage.covered_lines += file.covered_lines;
+                diff_coverage.uncovered_lines += file.uncovered_lines;
+                diff_coverage.files.push(file);
+            }
+        }
+
+        diff_coverage
+    }
+
+    fn compute_file(path: &str, hits: &[i64], changed_lines: &FileIndex) -> FileDiffCoverage {
+        let mut file = FileDiffCoverage {
+            path: path.to_string(),
+            ..Default::default()
+        };
+
+        for (index, hit) in hits.iter().enumerate() {
+            let line = index as u32 + 1;
+
+            if *hit < 0 || !changed_lines.matches_line_range(Path::new(path), line..=line) {
+                continue;
+            }
+
+            if *hit > 0 {
+                file.covered_lines += 1;
+            } else {
+                file.uncovered_lines += 1;
+
+                match file.uncovered_ranges.last_mut() {
+                    Some((_, end)) if Self::only_skipped_between(hits, *end, line) => *end = line,
+                    _ => file.uncovered_ranges.push((line, line)),
+                }
+            }
+        }
+
+        file
+    }
+
+    /// Whether every line strictly between the two lines is not executable,
+    /// so that uncovered lines separated only by blank lines or comments are
+    /// reported as a single range.
+    fn only_skipped_between(hits: &[i64], start: u32, end: u32) -> bool {
+        (start + 1..end).all(|line| hits[line as usize - 1] < 0)
+    }
+
+    pub fn coverage_percentage(&self) -> Option<f64> {
+        percentage(self.covered_lines, self.uncovered_lines)
+    }
+
+    /// Whether the diff coverage is below the threshold. Diffs without any
+    /// coverable changed lines never fail.
+    pub fn is_below(&self, threshold: f64) -> bool {
+        self.coverage_percentage()
+            .is_some_and(|percentage| percentage < threshold)
+    }
+}
+
+fn percentage(covered: u64, uncovered: u64) -> Option<f64> {
+    let coverable = covered + uncovered;
+
+    if coverable == 0 {
+        None
+    } else {
+        Some(covered as f64 / coverable as f64 * 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_coverage(path: &str, hits: Vec<i64>) -> FileCoverage {
+        FileCoverage {
+            path: path.to_string(),
+            hits,
+            ..Default::default()
+        }
+    }
+
+    fn changed_lines(lines: &[(&str, u32)]) -> FileIndex {
+        let mut index = FileIndex::new();
+
+        for (path, line) in lines {
+            index.insert_line(Path::new(path), *line);
+        }
+
+        index
+    }
+
+    #[test]
+    fn test_compute() {
+        let coverages = vec![
+            file_coverage("src/a.rs", vec![1, 0, -1, 0, 0, 3, 0]),
+            file_coverage("src/b.rs", vec![1, 1]),
+            file_coverage("src/unchanged.rs", vec![0, 0]),
+        ];
+        let index = changed_lines(&[
+            ("src/a.rs", 1),
+            ("src/a.rs", 2),
+            ("src/a.rs", 3),
+            ("src/a.rs", 4),
+            ("src/a.rs", 6),
+            ("src/a.rs", 7),
+            ("src/b.rs", 2),
+            ("src/missing.rs", 1),
+        ]);
+
+        let diff_coverage = DiffCoverage::compute(&coverages, &index);
+
+        assert_eq!(
+            diff_coverage.files,
+            vec![
+                FileDiffCoverage {
+                    path: "src/a.rs".to_string(),
+                    covered_lines: 2,
+                    uncovered_lines: 3,
+                    uncovered_ranges: vec![(2, 4), (7, 7)],
+                },
+                FileDiffCoverage {
+                    path: "src/b.rs".to_string(),
+                    covered_lines: 1,
+                    uncovered_lines: 0,
+                    uncovered_ranges: vec![],
+                },
+            ]
+        );
+        assert_eq!(diff_coverage.coverage_percentage(), Some(50.0));
+        assert!(diff_coverage.is_below(80.0));
+        assert!(!diff_coverage.is_below(50.0));
+    }
+
+    #[test]
+    fn test_compute_combines_reports() {
+        let coverages = vec![
+            file_coverage("src/a.rs", vec![0, 0, -1]),
+            file_coverage("src/a.rs", vec![0, 2]),
+        ];
+        let index = changed_lines(&[("src/a.rs", 1), ("src/a.rs", 2), ("src/a.rs", 3)]);
+
+        let diff_coverage = DiffCoverage::compute(&coverages, &index);
+
+        assert_eq!(diff_coverage.covered_lines, 1);
+        assert_eq!(diff_coverage.uncovered_lines, 1);
+        assert_eq!(diff_coverage.files[0].uncovered_ranges, vec![(1, 1)]);
+    }
+
+    #[test]
+    fn test_compute_without_coverable_lines() {
+        let coverages = vec![file_coverage("src/a.rs", vec![-1, 1])];
+        let index = changed_lines(&[("src/a.rs", 1)]);
+
+        let diff_coverage = DiffCoverage::compute(&coverages, &index);
+
+        assert!(diff_coverage.files.is_empty());
+        assert_eq!(diff_coverage.coverage_percentage(), None);
+        assert!(!diff_coverage.is_below(100.0));
+    }
+}
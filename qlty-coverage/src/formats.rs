@@ -18,6 +18,10 @@ pub enum Formats {
     Jacoco,
     Qlty,
     XccovJson,
+    Istanbul,
+    GcovJson,
+    LlvmCov,
+    Opencover,
 }
 
 impl std::fmt::Display for Formats {
@@ -32,6 +36,10 @@ impl std::fmt::Display for Formats {
             Formats::Jacoco => write!(f, "jacoco"),
             Formats::Qlty => write!(f, "qlty"),
             Formats::XccovJson => write!(f, "xccov-json"),
+            Formats::Istanbul => write!(f, "istanbul"),
+            Formats::GcovJson => write!(f, "gcov-json"),
+            Formats::LlvmCov => write!(f, "llvm-cov"),
+            Formats::Opencover => write!(f, "opencover"),
         }
     }
 }
@@ -42,7 +50,20 @@ impl TryFrom<&Path> for Formats {
     fn try_from(path: &Path) -> Result<Self> {
         match path.extension().and_then(std::ffi::OsStr::to_str) {
             Some("info") | Some("lcov") => Ok(Formats::Lcov),
-            Some("json") => Ok(Formats::Simplecov),
+            Some("json") => {
+                let path_str = path.to_str().unwrap();
+                if path_str.contains("coverage-final") {
+                    Ok(Formats::Istanbul)
+                } else {
+                    // Several tools write JSON reports, so look for a marker of each
+                    match std::fs::read_to_string(path) {
+                        Ok(content) if content.contains("llvm.coverage.json.export") => Ok(Formats::LlvmCov),
+                        Ok(content) if content.contains("\"gcc_version\"") => Ok(Formats::GcovJson),
+                        Ok(content) if content.contains("\"statementMap\"") => Ok(Formats::Istanbul),
+                        _ => Ok(Formats::Simplecov),
+                    }
+                }
+            }
             Some("jsonl") => Ok(Formats::Qlty),
             Some("out") => Ok(Formats::Coverprofile),
             Some("xml") => {
@@ -53,10 +74,13 @@ impl TryFrom<&Path> for Formats {
                     Ok(Formats::Clover)
                 } else if path_str.contains("dotcover") {
                     Ok(Formats::Dotcover)
+                } else if path_str.contains("opencover") {
+                    Ok(Formats::Opencover)
                 } else {
-                    // Try to detect dotCover by reading content
+                    // Try to detect dotCover or OpenCover by reading content
                     match std::fs::read_to_string(path) {
                         Ok(content) if content.contains("DotCoverVersion") => Ok(Formats::Dotcover),
+                        Ok(content) if content.contains("<CoverageSession") => Ok(Formats::Opencover),
                         _ => Ok(Formats::Cobertura),
                     }
                 }
@@ -84,6 +108,10 @@ impl FromStr for Formats {
             "jacoco" => Ok(Formats::Jacoco),
             "qlty" => Ok(Formats::Qlty),
             "xccov-json" => Ok(Formats::XccovJson),
+            "istanbul" => Ok(Formats::Istanbul),
+            "gcov-json" => Ok(Formats::GcovJson),
+            "llvm-cov" => Ok(Formats::LlvmCov),
+            "opencover" => Ok(Formats::Opencover),
             _ => bail!("Unsupported coverage report format: {}", s),
         }
     }
@@ -100,6 +128,10 @@ pub fn parser_for(&format: &Formats) -> Box<dyn Parser> {
         Formats::Jacoco => Box::new(parser::Jacoco::new()),
         Formats::Qlty => Box::new(parser::Qlty::new()),
         Formats::XccovJson => Box::new(parser::XccovJson::new()),
+        Formats::Istanbul => Box::new(parser::Istanbul::new()),
+        Formats::GcovJson => Box::new(parser::GcovJson::new()),
+        Formats::LlvmCov => Box::new(parser::LlvmCov::new()),
+        Formats::Opencover => Box::new(parser::Opencover::new()),
     }
 }
 
@@ -180,6 +212,49 @@ mod tests {
         std::fs::remove_file(path).unwrap();
     }
 
+    #[test]
+    fn test_try_from_istanbul_json() {
+        let path = Path::new("coverage/coverage-final.json");
+        let format = Formats::try_from(path).unwrap();
+        assert_eq!(format, Formats::Istanbul);
+    }
+
+    #[test]
+    fn test_try_from_opencover_xml() {
+        let path = Path::new("opencover.xml");
+        let format = Formats::try_from(path).unwrap();
+        assert_eq!(format, Formats::Opencover);
+    }
+
+    #[test]
+    fn test_try_from_json_by_content() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        for (content, expected) in [
+            (r#"{"type": "llvm.coverage.json.export"}"#, Formats::LlvmCov),
+            (
+                r#"{"gcc_version": "13.2.0", "files": []}"#,
+                Formats::GcovJson,
+            ),
+            (r#"{"/src/a.js": {"statementMap": {}}}"#, Formats::Istanbul),
+            (r#"{"RSpec": {"coverage": {}}}"#, Formats::Simplecov),
+        ] {
+            let path = temp_dir.path().join("coverage.json");
+            std::fs::write(&path, content).unwrap();
+            let format = Formats::try_from(path.as_path()).unwrap();
+            assert_eq!(format, expected);
+        }
+    }
+
+    #[test]
+    fn test_try_from_opencover_xml_by_content() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("results.xml");
+        std::fs::write(&path, "<CoverageSession><Modules /></CoverageSession>").unwrap();
+        let format = Formats::try_from(path.as_path()).unwrap();
+        assert_eq!(format, Formats::Opencover);
+    }
+
     #[test]
     fn test_try_from_unknown_extension() {
         let path = Path::new("coverage.txt");
@@ -0,0 +1,96 @@
+use qlty_formats::{Formatter, JsonEachRowFormatter};
+use qlty_types::tests::v1::FileCoverage;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+mod cobertura;
+mod html;
+mod lcov;
+
+pub use cobertura::CoberturaFormatter;
+pub use html::HtmlFormatter;
+pub use lcov::LcovFormatter;
+
+#[derive(clap::ValueEnum, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormats {
+    #[default]
+    Qlty,
+    Lcov,
+    Cobertura,
+    Html,
+}
+
+impl std::fmt::Display for OutputFormats {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            OutputFormats::Qlty => write!(f, "qlty"),
+            OutputFormats::Lcov => write!(f, "lcov"),
+            OutputFormats::Cobertura => write!(f, "cobertura"),
+            OutputFormats::Html => write!(f, "html"),
+        }
+    }
+}
+
+impl OutputFormats {
+    pub fn default_output(&self) -> &'static str {
+        match self {
+            OutputFormats::Qlty => "coverage.jsonl",
+            OutputFormats::Lcov => "lcov.info",
+            OutputFormats::Cobertura => "cobertura.xml",
+            OutputFormats::Html => "coverage.html",
+        }
+    }
+}
+
+/// Builds the formatter for the output format. Formats which show sources,
+/// such as HTML, read them relative to the root.
+pub fn formatter_for(
+    format: OutputFormats,
+    file_coverages: Vec<FileCoverage>,
+    root: &Path,
+) -> Box<dyn Formatter> {
+    match format {
+        OutputFormats::Qlty => JsonEachRowFormatter::boxed(file_coverages),
+        OutputFormats::Lcov => LcovFormatter::boxed(file_coverages),
+        OutputFormats::Cobertura => CoberturaFormatter::boxed(file_coverages),
+        OutputFormats::Html => {
+            Box::new(HtmlFormatter::new(file_coverages).with_root(root.to_path_buf()))
+        }
+    }
+}
+
+/// Lines with hit data, as (line number, hits) pairs.
+fn executable_lines(file_coverage: &FileCoverage) -> impl Iterator<Item = (u32, i64)> + '_ {
+    file_coverage
+        .hits
+        .iter()
+        .enumerate()
+        .filter(|(_, hits)| **hits >= 0)
+        .map(|(index, hits)| (index as u32 + 1, *hits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_html_formatter_reads_sources_from_root() {
+        let root = tempdir().unwrap();
+        std::fs::write(root.path().join("lib.rs"), "fn main() {}\n").unwrap();
+
+        let file_coverages = vec![FileCoverage {
+            path: "lib.rs".to_string(),
+            hits: vec![1],
+            ..Default::default()
+        }];
+
+        let output = formatter_for(OutputFormats::Html, file_coverages, root.path())
+            .read()
+            .unwrap();
+
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .contains("<pre>fn main() {}</pre>"));
+    }
+}
@@ -0,0 +1,268 @@
+use super::executable_lines;
+use anyhow::Result;
+use qlty_formats::Formatter;
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+use xml::escape::escape_str_attribute;
+
+/// Writes coverage as a Cobertura XML report, with one package per directory
+#[derive(Debug)]
+pub struct CoberturaFormatter {
+    file_coverages: Vec<FileCoverage>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Counts {
+    lines_covered: i64,
+    lines_valid: i64,
+    branches_covered: i64,
+    branches_valid: i64,
+}
+
+impl Counts {
+    fn for_file(file_coverage: &FileCoverage) -> Self {
+        let mut counts = Self::default();
+
+        for (_, hits) in executable_lines(file_coverage) {
+            counts.lines_valid += 1;
+
+            if hits > 0 {
+                counts.lines_covered += 1;
+            }
+        }
+
+        for branch in &file_coverage.branches {
+            counts.branches_valid += branch.total;
+            counts.branches_covered += branch.covered;
+        }
+
+        counts
+    }
+
+    fn add(&mut self, other: Self) {
+        self.lines_covered += other.lines_covered;
+        self.lines_valid += other.lines_valid;
+        self.branches_covered += other.branches_covered;
+        self.branches_valid += other.branches_valid;
+    }
+
+    fn line_rate(&self) -> String {
+        rate(self.lines_covered, self.lines_valid)
+    }
+
+    fn branch_rate(&self) -> String {
+        rate(self.branches_covered, self.branches_valid)
+    }
+}
+
+impl CoberturaFormatter {
+    pub fn new(file_coverages: Vec<FileCoverage>) -> Self {
+        Self { file_coverages }
+    }
+
+    pub fn boxed(file_coverages: Vec<FileCoverage>) -> Box<dyn Formatter> {
+        Box::new(Self::new(file_coverages))
+    }
+
+    fn packages(&self) -> BTreeMap<String, Vec<&FileCoverage>> {
+        let mut packages: BTreeMap<String, Vec<&FileCoverage>> = BTreeMap::new();
+
+        for file_coverage in &self.file_coverages {
+            let package = Path::new(&file_coverage.path)
+                .parent()
+                .map(|parent| parent.to_string_lossy().replace('\\', "/"))
+                .filter(|parent| !parent.is_empty())
+                .unwrap_or_else(|| ".".to_string());
+
+            packages.entry(package).or_default().push(file_coverage);
+        }
+
+        packages
+    }
+
+    fn write_class(writer: &mut dyn Write, file_coverage: &FileCoverage) -> Result<()> {
+        let counts = Counts::for_file(file_coverage);
+        let path = escape_str_attribute(&file_coverage.path);
+        let branches: BTreeMap<u32, &BranchCoverage> = file_coverage
+            .branches
+            .iter()
+            .map(|branch| (branch.line, branch))
+            .collect();
+
+        writeln!(
+            writer,
+            r#"        <class name="{}" filename="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+            path,
+            path,
+            counts.line_rate(),
+            counts.branch_rate()
+        )?;
+        writeln!(writer, "          <methods/>")?;
+        writeln!(writer, "          <lines>")?;
+
+        // Branches are only written for lines with hit data, as Cobertura
+        // attaches them to a line
+        for (line, hits) in executable_lines(file_coverage) {
+            match branches.get(&line) {
+                Some(branch) if branch.total > 0 => writeln!(
+                    writer,
+                    r#"            <line number="{}" hits="{}" branch="true" condition-coverage="{}% ({}/{})"/>"#,
+                    line,
+                    hits,
+                    branch.covered * 100 / branch.total,
+                    branch.covered,
+                    branch.total
+                )?,
+                _ => writeln!(
+                    writer,
+                    r#"            <line number="{}" hits="{}" branch="false"/>"#,
+                    line, hits
+                )?,
+            }
+        }
+
+        writeln!(writer, "          </lines>")?;
+        writeln!(writer, "        </class>")?;
+        Ok(())
+    }
+}
+
+impl Formatter for CoberturaFormatter {
+    fn write_to(&self, writer: &mut dyn Write) -> Result<()> {
+        let packages = self.packages();
+        let mut total = Counts::default();
+
+        for file_coverage in &self.file_coverages {
+            total.add(Counts::for_file(file_coverage));
+        }
+
+        writeln!(writer, r#"<?xml version="1.0" ?>"#)?;
+        writeln!(
+            writer,
+            r#"<coverage line-rate="{}" branch-rate="{}" lines-covered="{}" lines-valid="{}" branches-covered="{}" branches-valid="{}" complexity="0" version="qlty">"#,
+            total.line_rate(),
+            total.branch_rate(),
+            total.lines_covered,
+            total.lines_valid,
+            total.branches_covered,
+            total.branches_valid
+        )?;
+        writeln!(writer, "  <sources>")?;
+        writeln!(writer, "    <source>.</source>")?;
+        writeln!(writer, "  </sources>")?;
+        writeln!(writer, "  <packages>")?;
+
+        for (name, file_coverages) in packages {
+            let mut counts = Counts::default();
+
+            for file_coverage in &file_coverages {
+                counts.add(Counts::for_file(file_coverage));
+            }
+
+            writeln!(
+                writer,
+                r#"    <package name="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+                escape_str_attribute(&name),
+                counts.line_rate(),
+                counts.branch_rate()
+            )?;
+            writeln!(writer, "      <classes>")?;
+
+            for file_coverage in file_coverages {
+                Self::write_class(writer, file_coverage)?;
+            }
+
+            writeln!(writer, "      </classes>")?;
+            writeln!(writer, "    </package>")?;
+        }
+
+        writeln!(writer, "  </packages>")?;
+        writeln!(writer, "</coverage>")?;
+        Ok(())
+    }
+}
+
+fn rate(covered: i64, total: i64) -> String {
+    if total == 0 {
+        "1".to_string()
+    } else {
+        format!("{:.4}", covered as f64 / total as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Cobertura;
+    use crate::Parser;
+
+    fn file_coverages() -> Vec<FileCoverage> {
+        vec![
+            FileCoverage {
+                path: "lib.rs".to_string(),
+                hits: vec![1],
+                ..Default::default()
+            },
+            FileCoverage {
+                path: "src/a&b.rs".to_string(),
+                hits: vec![1, 0, -1, 4],
+                branches: vec![BranchCoverage {
+                    line: 2,
+                    total: 2,
+                    covered: 1,
+                }],
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_write() {
+        let output = CoberturaFormatter::new(file_coverages()).read().unwrap();
+
+        insta::assert_snapshot!(String::from_utf8(output).unwrap(), @r#"
+        <?xml version="1.0" ?>
+        <coverage line-rate="0.7500" branch-rate="0.5000" lines-covered="3" lines-valid="4" branches-covered="1" branches-valid="2" complexity="0" version="qlty">
+          <sources>
+            <source>.</source>
+          </sources>
+          <packages>
+            <package name="." line-rate="1.0000" branch-rate="1" complexity="0">
+              <classes>
+                <class name="lib.rs" filename="lib.rs" line-rate="1.0000" branch-rate="1" complexity="0">
+                  <methods/>
+                  <lines>
+                    <line number="1" hits="1" branch="false"/>
+                  </lines>
+                </class>
+              </classes>
+            </package>
+            <package name="src" line-rate="0.6667" branch-rate="0.5000" complexity="0">
+              <classes>
+                <class name="src/a&amp;b.rs" filename="src/a&amp;b.rs" line-rate="0.6667" branch-rate="0.5000" complexity="0">
+                  <methods/>
+                  <lines>
+                    <line number="1" hits="1" branch="false"/>
+                    <line number="2" hits="0" branch="true" condition-coverage="50% (1/2)"/>
+                    <line number="4" hits="4" branch="false"/>
+                  </lines>
+                </class>
+              </classes>
+            </package>
+          </packages>
+        </coverage>
+        "#);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let output = CoberturaFormatter::new(file_coverages()).read().unwrap();
+        let parsed = Cobertura::new()
+            .parse_text(&String::from_utf8(output).unwrap())
+            .unwrap();
+
+        assert_eq!(parsed, file_coverages());
+    }
+}
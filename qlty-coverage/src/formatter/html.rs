@@ -0,0 +1,279 @@
+use super::executable_lines;
+use anyhow::Result;
+use qlty_formats::Formatter;
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use xml::escape::escape_str_pcdata;
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2em; color: #1f2328; }
+table { border-collapse: collapse; }
+.summary th, .summary td { padding: 4px 12px; border-bottom: 1px solid #d0d7de; text-align: right; }
+.summary th:first-child, .summary td:first-child { text-align: left; }
+.summary .total td { font-weight: bold; }
+.source { width: 100%; font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: 12px; }
+.source td { padding: 0 8px; vertical-align: top; }
+.source .number, .source .hits, .source .branches { text-align: right; color: #656d76; white-space: nowrap; }
+.source pre { margin: 0; }
+.covered { background: #dafbe1; }
+.missed { background: #ffebe9; }
+.partial { background: #fff8c5; }
+"#;
+
+/// Writes coverage as a self-contained static HTML page, with a summary table
+/// and the source of each file annotated with hit counts. Sources are read
+/// relative to the root, which defaults to the current directory.
+#[derive(Debug)]
+pub struct HtmlFormatter {
+    file_coverages: Vec<FileCoverage>,
+    root: PathBuf,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Totals {
+    covered: i64,
+    missed: i64,
+    branches_covered: i64,
+    branches_total: i64,
+}
+
+impl Totals {
+    fn for_file(file_coverage: &FileCoverage) -> Self {
+        let mut totals = Self::default();
+
+        for (_, hits) in executable_lines(file_coverage) {
+            if hits > 0 {
+                totals.covered += 1;
+            } else {
+                totals.missed += 1;
+            }
+        }
+
+        for branch in &file_coverage.branches {
+            totals.branches_covered += branch.covered;
+            totals.branches_total += branch.total;
+        }
+
+        totals
+    }
+
+    fn add(&mut self, other: Self) {
+        self.covered += other.covered;
+        self.missed += other.missed;
+        self.branches_covered += other.branches_covered;
+        self.branches_total += other.branches_total;
+    }
+
+    fn line_percent(&self) -> String {
+        percent(self.covered, self.covered + self.missed)
+    }
+
+    fn branch_percent(&self) -> String {
+        percent(self.branches_covered, self.branches_total)
+    }
+}
+
+impl HtmlFormatter {
+    pub fn new(file_coverages: Vec<FileCoverage>) -> Self {
+        Self {
+            file_coverages,
+            root: PathBuf::from("."),
+        }
+    }
+
+    pub fn boxed(file_coverages: Vec<FileCoverage>) -> Box<dyn Formatter> {
+        Box::new(Self::new(file_coverages))
+    }
+
+    pub fn with_root(mut self, root: PathBuf) -> Self {
+        self.root = root;
+        self
+    }
+
+    fn write_summary(&self, writer: &mut dyn Write) -> Result<()> {
+        let mut total = Totals::default();
+
+        writeln!(writer, r#"<table class="summary">"#)?;
+        writeln!(
+            writer,
+            "<tr><th>File</th><th>Covered</th><th>Missed</th><th>Lines</th><th>Branches</th></tr>"
+        )?;
+
+        for (index, file_coverage) in self.file_coverages.iter().enumerate() {
+            let totals = Totals::for_file(file_coverage);
+            total.add(totals);
+
+            writeln!(
+                writer,
+                r##"<tr><td><a href="#file-{}">{}</a></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>"##,
+                index,
+                escape_str_pcdata(&file_coverage.path),
+                totals.covered,
+                totals.missed,
+                totals.line_percent(),
+                totals.branch_percent()
+            )?;
+        }
+
+        writeln!(
+            writer,
+            r#"<tr class="total"><td>Total</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>"#,
+            total.covered,
+            total.missed,
+            total.line_percent(),
+            total.branch_percent()
+        )?;
+        writeln!(writer, "</table>")?;
+        Ok(())
+    }
+
+    fn write_file(
+        &self,
+        writer: &mut dyn Write,
+        index: usize,
+        file_coverage: &FileCoverage,
+    ) -> Result<()> {
+        writeln!(
+            writer,
+            r#"<section id="file-{}"><h2>{}</h2>"#,
+            index,
+            escape_str_pcdata(&file_coverage.path)
+        )?;
+
+        let Ok(source) = std::fs::read_to_string(self.root.join(&file_coverage.path)) else {
+            writeln!(writer, "<p>Source not available.</p></section>")?;
+            return Ok(());
+        };
+
+        let branches: HashMap<u32, &BranchCoverage> = file_coverage
+            .branches
+            .iter()
+            .map(|branch| (branch.line, branch))
+            .collect();
+
+        writeln!(writer, r#"<table class="source">"#)?;
+
+        for (index, code) in source.lines().enumerate() {
+            let line = index as u32 + 1;
+            let hits = file_coverage.hits.get(index).copied().unwrap_or(-1);
+            let branch = branches.get(&line);
+
+            let class = match (hits, branch) {
+                (hits, _) if hits < 0 => "",
+                (0, _) => "missed",
+                (_, Some(branch)) if branch.covered < branch.total => "partial",
+                _ => "covered",
+            };
+
+            writeln!(
+                writer,
+                r#"<tr class="{}"><td class="number">{}</td><td class="hits">{}</td><td class="branches">{}</td><td><pre>{}</pre></td></tr>"#,
+                class,
+                line,
+                if hits < 0 {
+                    String::new()
+                } else {
+                    hits.to_string()
+                },
+                branch
+                    .map(|branch| format!("{}/{}", branch.covered, branch.total))
+                    .unwrap_or_default(),
+                escape_str_pcdata(code)
+            )?;
+        }
+
+        writeln!(writer, "</table></section>")?;
+        Ok(())
+    }
+}
+
+impl Formatter for HtmlFormatter {
+    fn write_to(&self, writer: &mut dyn Write) -> Result<()> {
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, r#"<html lang="en">"#)?;
+        writeln!(writer, r#"<head><meta charset="utf-8">"#)?;
+        writeln!(writer, "<title>Coverage report</title>")?;
+        writeln!(writer, "<style>{}</style>", STYLE)?;
+        writeln!(writer, "</head>")?;
+        writeln!(writer, "<body>")?;
+        writeln!(writer, "<h1>Coverage report</h1>")?;
+
+        self.write_summary(writer)?;
+
+        for (index, file_coverage) in self.file_coverages.iter().enumerate() {
+            self.write_file(writer, index, file_coverage)?;
+        }
+
+        writeln!(writer, "</body>")?;
+        writeln!(writer, "</html>")?;
+        Ok(())
+    }
+}
+
+fn percent(covered: i64, total: i64) -> String {
+    if total == 0 {
+        "-".to_string()
+    } else {
+        format!("{:.1}%", covered as f64 / total as f64 * 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_write() {
+        let root = tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join("src")).unwrap();
+        std::fs::write(
+            root.path().join("src/lib.rs"),
+            "fn main() {\n    if a < b {}\n}\n",
+        )
+        .unwrap();
+
+        let file_coverages = vec![
+            FileCoverage {
+                path: "src/lib.rs".to_string(),
+                hits: vec![1, 2],
+                branches: vec![BranchCoverage {
+                    line: 2,
+                    total: 2,
+                    covered: 1,
+                }],
+                ..Default::default()
+            },
+            FileCoverage {
+                path: "src/missing.rs".to_string(),
+                hits: vec![0],
+                ..Default::default()
+            },
+        ];
+
+        let output = HtmlFormatter::new(file_coverages)
+            .with_root(root.path().to_path_buf())
+            .read()
+            .unwrap();
+        let html = String::from_utf8(output).unwrap();
+
+        assert!(html.contains(
+            r##"<tr><td><a href="#file-0">src/lib.rs</a></td><td>2</td><td>0</td><td>100.0%</td><td>50.0%</td></tr>"##
+        ));
+        assert!(html.contains(
+            r#"<tr class="total"><td>Total</td><td>2</td><td>1</td><td>66.7%</td><td>50.0%</td></tr>"#
+        ));
+        assert!(html.contains(
+            r#"<tr class="partial"><td class="number">2</td><td class="hits">2</td><td class="branches">1/2</td><td><pre>    if a &lt; b {}</pre></td></tr>"#
+        ));
+        assert!(html.contains(
+            r#"<tr class=""><td class="number">3</td><td class="hits"></td><td class="branches"></td><td><pre>}</pre></td></tr>"#
+        ));
+        assert!(html.contains(
+            r#"<section id="file-1"><h2>src/missing.rs</h2>
+<p>Source not available.</p></section>"#
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+use super::executable_lines;
+use anyhow::Result;
+use qlty_formats::Formatter;
+use qlty_types::tests::v1::FileCoverage;
+use std::io::Write;
+
+/// Writes coverage as an lcov tracefile
+#[derive(Debug)]
+pub struct LcovFormatter {
+    file_coverages: Vec<FileCoverage>,
+}
+
+impl LcovFormatter {
+    pub fn new(file_coverages: Vec<FileCoverage>) -> Self {
+        Self { file_coverages }
+    }
+
+    pub fn boxed(file_coverages: Vec<FileCoverage>) -> Box<dyn Formatter> {
+        Box::new(Self::new(file_coverages))
+    }
+}
+
+impl Formatter for LcovFormatter {
+    fn write_to(&self, writer: &mut dyn Write) -> Result<()> {
+        for file_coverage in &self.file_coverages {
+            writeln!(writer, "TN:")?;
+            writeln!(writer, "SF:{}", file_coverage.path)?;
+
+            let mut lines_found = 0;
+            let mut lines_hit = 0;
+
+            for (line, hits) in executable_lines(file_coverage) {
+                writeln!(writer, "DA:{},{}", line, hits)?;
+                lines_found += 1;
+
+                if hits > 0 {
+                    lines_hit += 1;
+                }
+            }
+
+            let mut branches_found = 0;
+            let mut branches_hit = 0;
+
+            // Only the number of branches taken is known, not which ones or how
+            // often, so the first `covered` branches are written as taken once
+            for (block, branch) in file_coverage.branches.iter().enumerate() {
+                let line_executed = branch
+                    .line
+                    .checked_sub(1)
+                    .and_then(|index| file_coverage.hits.get(index as usize))
+                    .is_some_and(|hits| *hits > 0);
+
+                for index in 0..branch.total {
+                    let taken = if index < branch.covered {
+                        "1"
+                    } else if line_executed {
+                        "0"
+                    } else {
+                        "-"
+                    };
+
+                    writeln!(writer, "BRDA:{},{},{},{}", branch.line, block, index, taken)?;
+                }
+
+                branches_found += branch.total;
+                branches_hit += branch.covered;
+            }
+
+            if branches_found > 0 {
+                writeln!(writer, "BRF:{}", branches_found)?;
+                writeln!(writer, "BRH:{}", branches_hit)?;
+            }
+
+            writeln!(writer, "LF:{}", lines_found)?;
+            writeln!(writer, "LH:{}", lines_hit)?;
+            writeln!(writer, "end_of_record")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Lcov;
+    use crate::Parser;
+    use qlty_types::tests::v1::BranchCoverage;
+
+    fn file_coverage() -> FileCoverage {
+        FileCoverage {
+            path: "src/lib.rs".to_string(),
+            hits: vec![1, 0, -1, 4],
+            branches: vec![BranchCoverage {
+                line: 2,
+                total: 2,
+                covered: 1,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_write() {
+        let output = LcovFormatter::new(vec![file_coverage()]).read().unwrap();
+
+        insta::assert_snapshot!(String::from_utf8(output).unwrap(), @r"
+        TN:
+        SF:src/lib.rs
+        DA:1,1
+        DA:2,0
+        DA:4,4
+        BRDA:2,0,0,1
+        BRDA:2,0,1,-
+        BRF:2
+        BRH:1
+        LF:3
+        LH:2
+        end_of_record
+        ");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let output = LcovFormatter::new(vec![file_coverage()]).read().unwrap();
+        let parsed = Lcov::new()
+            .parse_text(&String::from_utf8(output).unwrap())
+            .unwrap();
+
+        assert_eq!(parsed, vec![file_coverage()]);
+    }
+}
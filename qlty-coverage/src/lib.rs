@@ -1,8 +1,11 @@
 pub mod ci;
+pub mod diff;
 pub mod export;
 pub mod formats;
+pub mod formatter;
 pub mod git;
 mod java_src_dir_finder;
+pub mod merge;
 pub mod parser;
 pub mod print;
 pub mod publish;
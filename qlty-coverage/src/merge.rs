@@ -0,0 +1,101 @@
+use crate::publish::merge_branches;
+use crate::transformer::ComputeSummary;
+use crate::Transformer;
+use qlty_types::tests::v1::FileCoverage;
+use std::collections::BTreeMap;
+
+/// Combines file coverages from several reports into one coverage per path,
+/// sorted by path. Hits for the same line are summed. Unlike publish-time
+/// summing, reports of different lengths are padded rather than truncated,
+/// since shards of a test suite often only execute part of a file.
+pub fn merge_file_coverages(file_coverages: Vec<FileCoverage>) -> Vec<FileCoverage> {
+    let mut merged: BTreeMap<String, FileCoverage> = BTreeMap::new();
+
+    for file_coverage in file_coverages {
+        match merged.get_mut(&file_coverage.path) {
+            Some(existing) => {
+                merge_hits(&mut existing.hits, &file_coverage.hits);
+                merge_branches(&mut existing.branches, &file_coverage.branches);
+            }
+            None => {
+                merged.insert(file_coverage.path.clone(), file_coverage);
+            }
+        }
+    }
+
+    let compute_summary = ComputeSummary::new();
+
+    merged
+        .into_values()
+        .filter_map(|file_coverage| compute_summary.transform(file_coverage))
+        .collect()
+}
+
+/// Sums executed lines. A line without data in one report takes the value
+/// from the other, while other negative markers (such as ignored lines) win.
+fn merge_hits(existing: &mut Vec<i64>, other: &[i64]) {
+    if existing.len() < other.len() {
+        existing.resize(other.len(), -1);
+    }
+
+    for (hit, other_hit) in existing.iter_mut().zip(other) {
+        *hit = match (*hit, *other_hit) {
+            (a, b) if a >= 0 && b >= 0 => a + b,
+            (-1, b) if b >= 0 => b,
+            (a, -1) if a >= 0 => a,
+            (a, b) => a.min(b),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qlty_types::tests::v1::BranchCoverage;
+
+    fn file_coverage(path: &str, hits: Vec<i64>) -> FileCoverage {
+        FileCoverage {
+            path: path.to_string(),
+            hits,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_hits() {
+        let mut hits = vec![1, 0, -1, -1, 2];
+        merge_hits(&mut hits, &[2, 0, 3, -1, -2, 0, 1]);
+        assert_eq!(hits, vec![3, 0, 3, -1, -2, 0, 1]);
+    }
+
+    #[test]
+    fn test_merge_file_coverages() {
+        let mut a = file_coverage("src/a.rs", vec![1, 0]);
+        a.branches = vec![BranchCoverage {
+            line: 2,
+            total: 2,
+            covered: 0,
+        }];
+
+        let mut a2 = file_coverage("src/a.rs", vec![0, 1, 1]);
+        a2.branches = vec![BranchCoverage {
+            line: 2,
+            total: 2,
+            covered: 1,
+        }];
+
+        let merged = merge_file_coverages(vec![file_coverage("src/b.rs", vec![1]), a, a2]);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].path, "src/a.rs");
+        assert_eq!(merged[0].hits, vec![1, 1, 1]);
+        assert_eq!(merged[0].branches[0].covered, 1);
+
+        let summary = merged[0].summary.unwrap();
+        assert_eq!(summary.covered, 3);
+        assert_eq!(summary.branches_covered, 1);
+        assert_eq!(summary.branches_missed, 1);
+
+        assert_eq!(merged[1].path, "src/b.rs");
+    }
+}
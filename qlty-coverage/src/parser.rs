@@ -6,8 +6,12 @@ mod clover;
 mod cobertura;
 mod coverprofile;
 mod dotcover;
+mod gcov;
+mod istanbul;
 mod jacoco;
 mod lcov;
+mod llvm_cov;
+mod opencover;
 mod qlty;
 mod simplecov;
 mod xccov;
@@ -16,8 +20,12 @@ pub use clover::Clover;
 pub use cobertura::Cobertura;
 pub use coverprofile::Coverprofile;
 pub use dotcover::Dotcover;
+pub use gcov::GcovJson;
+pub use istanbul::Istanbul;
 pub use jacoco::Jacoco;
 pub use lcov::Lcov;
+pub use llvm_cov::LlvmCov;
+pub use opencover::Opencover;
 pub use qlty::Qlty;
 pub use simplecov::Simplecov;
 pub use xccov::XccovJson;
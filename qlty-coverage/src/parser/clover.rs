@@ -1,6 +1,6 @@
 use crate::Parser;
 use anyhow::{Context, Result};
-use qlty_types::tests::v1::FileCoverage;
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage};
 use std::io::BufReader;
 use std::str::FromStr;
 use xml::reader::{EventReader, XmlEvent};
@@ -12,6 +12,7 @@ struct CloverFile {
     name: String,
     path: Option<String>,
     lines: Vec<(i64, i64)>, // (line_num, count)
+    branches: Vec<BranchCoverage>,
     loc: Option<i64>,
 }
 
@@ -60,6 +61,7 @@ impl Clover {
                                     name: file_name,
                                     path: file_path,
                                     lines: Vec::new(),
+                                    branches: Vec::new(),
                                     loc: None,
                                 });
                             }
@@ -68,6 +70,9 @@ impl Clover {
                             if let Some(ref mut file) = current_file {
                                 let mut num = 0;
                                 let mut count = 0;
+                                let mut is_condition = false;
+                                let mut true_count = 0;
+                                let mut false_count = 0;
 
                                 for attr in attributes {
                                     match attr.name.local_name.as_str() {
@@ -85,12 +90,30 @@ impl Clover {
                                                 format!("Failed to parse 'count' attribute value: {}", attr.value)
                                             })?;
                                         }
+                                        "type" => is_condition = attr.value == "cond",
+                                        "truecount" => {
+                                            true_count = i64::from_str(&attr.value).unwrap_or(0)
+                                        }
+                                        "falsecount" => {
+                                            false_count = i64::from_str(&attr.value).unwrap_or(0)
+                                        }
                                         _ => {}
                                     }
                                 }
 
                                 if num > 0 {
                                     file.lines.push((num, count));
+
+                                    // A condition has two outcomes, each of which is covered
+                                    // once it has been evaluated at least once
+                                    if is_condition {
+                                        file.branches.push(BranchCoverage {
+                                            line: num as u32,
+                                            total: 2,
+                                            covered: (true_count > 0) as i64
+                                                + (false_count > 0) as i64,
+                                        });
+                                    }
                                 }
                             }
                         }
@@ -164,9 +187,13 @@ impl Parser for Clover {
                 }
             }
 
+            let mut branches = file.branches;
+            branches.sort_by_key(|branch| branch.line);
+
             let file_coverage = FileCoverage {
                 path: file.path.unwrap_or(file.name),
                 hits: line_hits,
+                branches,
                 ..Default::default()
             };
 
@@ -1,6 +1,6 @@
 use crate::Parser;
 use anyhow::{Context, Result};
-use qlty_types::tests::v1::FileCoverage;
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage};
 use serde::Deserialize;
 use std::collections::BTreeMap;
 
@@ -41,6 +41,21 @@ struct Lines {
 struct Line {
     number: String,
     hits: String,
+    #[serde(rename = "condition-coverage", default)]
+    condition_coverage: Option<String>,
+}
+
+impl Line {
+    /// Parses the taken and total branch counts from a condition coverage
+    /// attribute such as `50% (1/2)`.
+    fn branch_counts(&self) -> Option<(i64, i64)> {
+        let condition_coverage = self.condition_coverage.as_ref()?;
+        let start = condition_coverage.find('(')?;
+        let end = condition_coverage.find(')')?;
+        let (covered, total) = condition_coverage.get(start + 1..end)?.split_once('/')?;
+
+        Some((covered.trim().parse().ok()?, total.trim().parse().ok()?))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -78,6 +93,7 @@ impl Parser for Cobertura {
 
         for (filename, lines) in lines_by_filename {
             let mut line_hits = Vec::new();
+            let mut branches: BTreeMap<u32, BranchCoverage> = BTreeMap::new();
             let mut sorted_lines = lines.clone();
             sorted_lines.sort_by_key(|line| line.number.parse::<i32>().unwrap_or_default());
 
@@ -94,6 +110,14 @@ impl Parser for Cobertura {
                         } else {
                             line_hits[line_number - 1] += hits;
                         }
+
+                        if let Some((covered, total)) = line.branch_counts() {
+                            // The same line may be listed by several classes
+                            let branch = branches.entry(line_number as u32).or_default();
+                            branch.line = line_number as u32;
+                            branch.total = branch.total.max(total);
+                            branch.covered = branch.covered.max(covered);
+                        }
                     }
                 }
             }
@@ -101,6 +125,7 @@ impl Parser for Cobertura {
             let file_coverage = FileCoverage {
                 path: filename,
                 hits: line_hits,
+                branches: branches.into_values().collect(),
                 ..Default::default()
             };
 
@@ -187,6 +212,16 @@ mod tests {
         - "9"
         - "-1"
         - "3"
+      branches:
+        - line: 18
+          total: "2"
+          covered: "2"
+        - line: 23
+          total: "2"
+          covered: "1"
+        - line: 25
+          total: "2"
+          covered: "2"
     - path: search/ISortedArraySearch.java
     - path: search/LinearSearch.java
       hits:
@@ -230,6 +265,16 @@ mod tests {
         - "-1"
         - "-1"
         - "5"
+      branches:
+        - line: 13
+          total: "2"
+          covered: "1"
+        - line: 15
+          total: "2"
+          covered: "2"
+        - line: 17
+          total: "2"
+          covered: "1"
     "#);
     }
 }
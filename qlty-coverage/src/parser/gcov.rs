@@ -0,0 +1,156 @@
+use crate::Parser;
+use anyhow::{Context, Result};
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Deserialize)]
+struct GcovReport {
+    #[serde(default)]
+    files: Vec<GcovFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GcovFile {
+    file: String,
+    #[serde(default)]
+    lines: Vec<GcovLine>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GcovLine {
+    line_number: u32,
+    count: i64,
+    #[serde(default)]
+    branches: Vec<GcovBranch>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GcovBranch {
+    count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GcovJson {}
+
+impl GcovJson {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn parse_file_coverage(file: GcovFile) -> FileCoverage {
+        // A line is listed once per function containing it, such as for each
+        // instantiation of a template, so counts for the same line are summed
+        let mut line_hits: BTreeMap<u32, i64> = BTreeMap::new();
+        let mut branches: BTreeMap<u32, BranchCoverage> = BTreeMap::new();
+
+        for line in &file.lines {
+            if line.line_number == 0 {
+                continue;
+            }
+
+            *line_hits.entry(line.line_number).or_insert(0) += line.count;
+
+            if !line.branches.is_empty() {
+                let branch = branches.entry(line.line_number).or_default();
+                branch.line = line.line_number;
+                branch.total += line.branches.len() as i64;
+                branch.covered += line
+                    .branches
+                    .iter()
+                    .filter(|branch| branch.count > 0)
+                    .count() as i64;
+            }
+        }
+
+        let maximum_line_number = line_hits.keys().max().copied().unwrap_or(0);
+        let mut hits = vec![-1; maximum_line_number as usize];
+
+        for (line, count) in line_hits {
+            hits[line as usize - 1] = count;
+        }
+
+        FileCoverage {
+            path: file.file,
+            hits,
+            branches: branches.into_values().collect(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Parser for GcovJson {
+    fn parse_text(&self, text: &str) -> Result<Vec<FileCoverage>> {
+        let mut file_coverages = vec![];
+
+        // `gcov --json-format --stdout` writes one document per data file
+        for report in serde_json::Deserializer::from_str(text).into_iter::<GcovReport>() {
+            let report = report.with_context(|| "Failed to parse gcov JSON")?;
+
+            for file in report.files {
+                file_coverages.push(Self::parse_file_coverage(file));
+            }
+        }
+
+        Ok(file_coverages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcov_results() {
+        let input = include_str!("../../tests/fixtures/gcov/sample.gcov.json");
+
+        let parsed_results = GcovJson::new().parse_text(input).unwrap();
+        insta::assert_yaml_snapshot!(parsed_results, @r#"
+        - path: src/main.c
+          hits:
+            - "-1"
+            - "-1"
+            - "3"
+            - "3"
+            - "1"
+            - "2"
+            - "0"
+            - "2"
+            - "-1"
+            - "-1"
+            - "1"
+            - "-1"
+            - "1"
+          branches:
+            - line: 4
+              total: "2"
+              covered: "2"
+            - line: 6
+              total: "2"
+              covered: "1"
+        - path: /usr/include/stdio.h
+        - path: src/util.h
+          hits:
+            - "-1"
+            - "3"
+        "#);
+    }
+
+    #[test]
+    fn multiple_documents() {
+        let input = r#"{"gcc_version": "13.2.0", "files": [{"file": "a.c", "lines": [{"line_number": 1, "count": 1}]}]}
+{"gcc_version": "13.2.0", "files": [{"file": "b.c", "lines": [{"line_number": 2, "count": 0}]}]}
+"#;
+
+        let parsed_results = GcovJson::new().parse_text(input).unwrap();
+        insta::assert_yaml_snapshot!(parsed_results, @r#"
+        - path: a.c
+          hits:
+            - "1"
+        - path: b.c
+          hits:
+            - "-1"
+            - "0"
+        "#);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::Parser;
+use anyhow::{Context, Result};
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Deserialize)]
+struct IstanbulFile {
+    path: Option<String>,
+    #[serde(rename = "statementMap", default)]
+    statement_map: HashMap<String, Location>,
+    #[serde(rename = "branchMap", default)]
+    branch_map: HashMap<String, Branch>,
+    #[serde(default)]
+    s: HashMap<String, i64>,
+    #[serde(default)]
+    b: HashMap<String, Vec<i64>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Location {
+    start: Position,
+}
+
+#[derive(Debug, Deserialize)]
+struct Position {
+    line: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Branch {
+    loc: Option<Location>,
+    line: Option<u32>,
+}
+
+impl Branch {
+    fn line(&self) -> Option<u32> {
+        self.line
+            .or_else(|| self.loc.as_ref().and_then(|loc| loc.start.line))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Istanbul {}
+
+impl Istanbul {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Parser for Istanbul {
+    fn parse_text(&self, text: &str) -> Result<Vec<FileCoverage>> {
+        let report: BTreeMap<String, IstanbulFile> =
+            serde_json::from_str(text).with_context(|| "Failed to parse Istanbul JSON")?;
+
+        let mut file_coverages = vec![];
+
+        for (key, file) in report {
+            // Istanbul reports a line as executed as often as its most
+            // executed statement, which is what its own lcov reporter does
+            let mut line_hits: BTreeMap<u32, i64> = BTreeMap::new();
+
+            for (id, location) in &file.statement_map {
+                let (Some(line), Some(count)) = (location.start.line, file.s.get(id)) else {
+                    continue;
+                };
+
+                let hits = line_hits.entry(line).or_insert(0);
+                *hits = (*hits).max(*count);
+            }
+
+            let mut branches: BTreeMap<u32, BranchCoverage> = BTreeMap::new();
+
+            for (id, branch) in &file.branch_map {
+                let (Some(line), Some(counts)) = (branch.line(), file.b.get(id)) else {
+                    continue;
+                };
+
+                let branch = branches.entry(line).or_default();
+                branch.line = line;
+                branch.total += counts.len() as i64;
+                branch.covered += counts.iter().filter(|count| **count > 0).count() as i64;
+            }
+
+            let maximum_line_number = line_hits.keys().max().copied().unwrap_or(0);
+            let mut hits = vec![-1; maximum_line_number as usize];
+
+            for (line, count) in line_hits {
+                if line > 0 {
+                    hits[line as usize - 1] = count;
+                }
+            }
+
+            file_coverages.push(FileCoverage {
+                path: file.path.unwrap_or(key),
+                hits,
+                branches: branches.into_values().collect(),
+                ..Default::default()
+            });
+        }
+
+        Ok(file_coverages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn istanbul_results() {
+        let input = include_str!("../../tests/fixtures/istanbul/coverage-final.json");
+
+        let parsed_results = Istanbul::new().parse_text(input).unwrap();
+        insta::assert_yaml_snapshot!(parsed_results, @r#"
+        - path: /home/runner/work/app/src/math.js
+          hits:
+            - "1"
+            - "-1"
+            - "4"
+            - "0"
+            - "-1"
+            - "4"
+            - "-1"
+            - "-1"
+            - "1"
+          branches:
+            - line: 3
+              total: "2"
+              covered: "1"
+        - path: /home/runner/work/app/src/unused.js
+          hits:
+            - "-1"
+            - "0"
+          branches:
+            - line: 2
+              total: "3"
+        "#);
+    }
+
+    #[test]
+    fn empty_report() {
+        let parsed_results = Istanbul::new().parse_text("{}").unwrap();
+        assert!(parsed_results.is_empty());
+    }
+}
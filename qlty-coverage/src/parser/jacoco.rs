@@ -1,7 +1,7 @@
 use crate::Parser;
 use anyhow::{Context, Result};
 use qlty_config::env::{EnvSource, SystemEnv};
-use qlty_types::tests::v1::FileCoverage;
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage};
 use serde::Deserialize;
 use serde_xml_rs;
 use std::path::Path;
@@ -39,6 +39,10 @@ struct Sourcefile {
 struct Line {
     nr: i64,
     ci: i64,
+    #[serde(default)]
+    mb: i64,
+    #[serde(default)]
+    cb: i64,
 }
 
 pub struct Jacoco {
@@ -125,6 +129,7 @@ impl Parser for Jacoco {
         for package in all_packages {
             for sourcefile in package.sourcefile.iter() {
                 let mut line_hits = Vec::new();
+                let mut branches = Vec::new();
                 if let Some(lines) = sourcefile.line.as_ref() {
                     for line in lines {
                         // Fill in any missing lines with -1 to indicate that are omitted
@@ -133,6 +138,14 @@ impl Parser for Jacoco {
                         }
 
                         line_hits.push(line.ci);
+
+                        if line.mb + line.cb > 0 {
+                            branches.push(BranchCoverage {
+                                line: line.nr as u32,
+                                total: line.mb + line.cb,
+                                covered: line.cb,
+                            });
+                        }
                     }
                 }
 
@@ -142,6 +155,7 @@ impl Parser for Jacoco {
                 let file_coverage = FileCoverage {
                     path,
                     hits: line_hits,
+                    branches,
                     ..Default::default()
                 };
 
@@ -409,6 +423,27 @@ mod tests {
         assert!(parsed_results.is_empty());
     }
 
+    #[test]
+    fn jacoco_branches() {
+        let input = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><report name="branches"><package name="app"><sourcefile name="Main.java"><line nr="2" mi="0" ci="3" mb="0" cb="0" /><line nr="3" mi="0" ci="3" mb="1" cb="3" /><line nr="4" mi="2" ci="0" mb="2" cb="0" /></sourcefile></package></report>"#;
+
+        let parsed_results = Jacoco::new().parse_text(input).unwrap();
+        insta::assert_yaml_snapshot!(parsed_results, @r###"
+        - path: app/Main.java
+          hits:
+            - "-1"
+            - "3"
+            - "3"
+            - "0"
+          branches:
+            - line: 3
+              total: "4"
+              covered: "3"
+            - line: 4
+              total: "2"
+        "###);
+    }
+
     #[test]
     fn jacoco_with_groups() {
         let input = include_str!("../../tests/fixtures/jacoco/sample_with_groups.xml");
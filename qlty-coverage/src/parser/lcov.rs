@@ -1,7 +1,7 @@
 use crate::Parser;
 use anyhow::Result;
-use qlty_types::tests::v1::FileCoverage;
-use std::collections::HashMap;
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage};
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct Lcov {}
@@ -27,6 +27,7 @@ impl Parser for Lcov {
                 };
 
                 let mut line_numbers_to_hits = HashMap::new();
+                let mut line_numbers_to_branches: BTreeMap<u32, BranchCoverage> = BTreeMap::new();
 
                 for lcov_line in lcov_lines.by_ref() {
                     if let Some(lcov_line) = lcov_line.strip_prefix(DA) {
@@ -39,6 +40,27 @@ impl Parser for Lcov {
                         let hits_count = hits_count.parse::<u64>().unwrap();
 
                         *line_numbers_to_hits.entry(line_number).or_insert(0) += hits_count;
+                    } else if let Some(lcov_line) = lcov_line.strip_prefix(BRDA) {
+                        // BRDA:<line>,<block>,<branch>,<taken> where taken is "-" if
+                        // the block containing the branch was never executed
+                        let split = lcov_line.split(',').collect::<Vec<_>>();
+
+                        if split.len() != 4 {
+                            continue;
+                        }
+
+                        let Ok(line_number) = split[0].parse::<u32>() else {
+                            continue;
+                        };
+                        let taken = split[3].parse::<u64>().unwrap_or(0);
+
+                        let branch = line_numbers_to_branches.entry(line_number).or_default();
+                        branch.line = line_number;
+                        branch.total += 1;
+
+                        if taken > 0 {
+                            branch.covered += 1;
+                        }
                     } else if lcov_line.starts_with(END_OF_RECORD) {
                         break;
                     }
@@ -52,6 +74,7 @@ impl Parser for Lcov {
                 }
 
                 file_coverage.hits = line_hits;
+                file_coverage.branches = line_numbers_to_branches.into_values().collect();
                 file_coverages.push(file_coverage);
             }
         }
@@ -62,6 +85,7 @@ impl Parser for Lcov {
 
 const SF: &str = "SF:";
 const DA: &str = "DA:";
+const BRDA: &str = "BRDA:";
 const END_OF_RECORD: &str = "end_of_record";
 
 #[cfg(test)]
@@ -146,9 +170,61 @@ DA:5,10
         - "-1"
         - "-1"
         - "2"
+      branches:
+        - line: 11
+          total: "2"
+          covered: "1"
+        - line: 15
+          total: "2"
+          covered: "1"
+        - line: 19
+          total: "2"
+          covered: "2"
+        - line: 21
+          total: "2"
+          covered: "1"
+        - line: 32
+          total: "2"
+          covered: "1"
+        - line: 52
+          total: "2"
+          covered: "1"
+        - line: 73
+          total: "2"
+          covered: "1"
+        - line: 91
+          total: "2"
+          covered: "1"
     "#);
     }
 
+    #[test]
+    fn test_branches() {
+        let input = r#"
+SF:src/lib.rs
+DA:1,1
+DA:2,0
+BRDA:1,0,0,1
+BRDA:1,0,1,0
+BRDA:2,1,0,-
+BRDA:2,1,1,-
+end_of_record
+"#;
+
+        insta::assert_yaml_snapshot!(Lcov::new().parse_text(input).unwrap(), @r#"
+        - path: src/lib.rs
+          hits:
+            - "1"
+            - "0"
+          branches:
+            - line: 1
+              total: "2"
+              covered: "1"
+            - line: 2
+              total: "2"
+        "#);
+    }
+
     #[test]
     fn test_empty() {
         let input = r#"
@@ -0,0 +1,222 @@
+use crate::Parser;
+use anyhow::{Context, Result};
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Deserialize)]
+struct LlvmCovExport {
+    #[serde(default)]
+    data: Vec<LlvmCovData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LlvmCovData {
+    #[serde(default)]
+    files: Vec<LlvmCovFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LlvmCovFile {
+    filename: String,
+    #[serde(default)]
+    segments: Vec<Vec<Value>>,
+    #[serde(default)]
+    branches: Vec<Vec<Value>>,
+}
+
+// Segments are exported as arrays of
+// [line, column, count, hasCount, isRegionEntry, isGapRegion], where the
+// last element is missing in exports older than version 2.0.1
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    line: u32,
+    count: i64,
+    has_count: bool,
+    is_region_entry: bool,
+    is_gap_region: bool,
+}
+
+impl Segment {
+    fn from_values(values: &[Value]) -> Option<Self> {
+        Some(Self {
+            line: values.first()?.as_u64()? as u32,
+            count: values.get(2)?.as_u64()? as i64,
+            has_count: values.get(3)?.as_bool()?,
+            is_region_entry: values.get(4)?.as_bool()?,
+            is_gap_region: values.get(5).and_then(Value::as_bool).unwrap_or(false),
+        })
+    }
+
+    fn starts_region(&self) -> bool {
+        !self.is_gap_region && self.has_count && self.is_region_entry
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LlvmCov {}
+
+impl LlvmCov {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn parse_file_coverage(file: LlvmCovFile) -> FileCoverage {
+        let mut segments: Vec<Segment> = file
+            .segments
+            .iter()
+            .filter_map(|values| Segment::from_values(values))
+            .collect();
+        segments.sort_by_key(|segment| segment.line);
+
+        let mut branches: BTreeMap<u32, BranchCoverage> = BTreeMap::new();
+
+        // Branches are exported as arrays of
+        // [lineStart, columnStart, lineEnd, columnEnd, trueCount, falseCount, ...]
+        for values in &file.branches {
+            let (Some(line), Some(true_count), Some(false_count)) = (
+                values.first().and_then(Value::as_u64),
+                values.get(4).and_then(Value::as_u64),
+                values.get(5).and_then(Value::as_u64),
+            ) else {
+                continue;
+            };
+
+            let branch = branches.entry(line as u32).or_default();
+            branch.line = line as u32;
+            branch.total += 2;
+            branch.covered += (true_count > 0) as i64 + (false_count > 0) as i64;
+        }
+
+        FileCoverage {
+            path: file.filename,
+            hits: Self::line_hits(&segments),
+            branches: branches.into_values().collect(),
+            ..Default::default()
+        }
+    }
+
+    // Mirrors how llvm-cov itself computes line coverage: a line is executable
+    // if it starts a region or continues one from a previous line, and is
+    // executed as often as the most executed region on it
+    fn line_hits(segments: &[Segment]) -> Vec<i64> {
+        let maximum_line_number = segments.iter().map(|segment| segment.line).max();
+        let Some(maximum_line_number) = maximum_line_number else {
+            return vec![];
+        };
+
+        let mut hits = vec![-1; maximum_line_number as usize];
+        let mut wrapped_segment: Option<&Segment> = None;
+        let mut index = 0;
+
+        while index < segments.len() && segments[index].line == 0 {
+            index += 1;
+        }
+
+        for line in 1..=maximum_line_number {
+            let start = index;
+
+            while index < segments.len() && segments[index].line == line {
+                index += 1;
+            }
+
+            let line_segments = &segments[start..index];
+
+            let starts_skipped_region = line_segments
+                .first()
+                .is_some_and(|segment| !segment.has_count && segment.is_region_entry);
+            let region_starts: Vec<&Segment> = line_segments
+                .iter()
+                .filter(|segment| segment.starts_region())
+                .collect();
+
+            let mapped = !starts_skipped_region
+                && (wrapped_segment.is_some_and(|segment| segment.has_count)
+                    || !region_starts.is_empty());
+
+            if mapped {
+                let count = wrapped_segment.map(|segment| segment.count).unwrap_or(0);
+
+                hits[line as usize - 1] = region_starts
+                    .iter()
+                    .map(|segment| segment.count)
+                    .fold(count, i64::max);
+            }
+
+            if let Some(last_segment) = line_segments.last() {
+                wrapped_segment = Some(last_segment);
+            }
+        }
+
+        hits
+    }
+}
+
+impl Parser for LlvmCov {
+    fn parse_text(&self, text: &str) -> Result<Vec<FileCoverage>> {
+        let export: LlvmCovExport =
+            serde_json::from_str(text).with_context(|| "Failed to parse llvm-cov JSON export")?;
+
+        Ok(export
+            .data
+            .into_iter()
+            .flat_map(|data| data.files)
+            .map(Self::parse_file_coverage)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn llvm_cov_results() {
+        let input = include_str!("../../tests/fixtures/llvm-cov/sample.json");
+
+        let parsed_results = LlvmCov::new().parse_text(input).unwrap();
+        insta::assert_yaml_snapshot!(parsed_results, @r#"
+        - path: /home/runner/work/app/src/lib.rs
+          hits:
+            - "3"
+            - "3"
+            - "1"
+            - "2"
+            - "2"
+            - "2"
+            - "3"
+            - "-1"
+            - "0"
+            - "0"
+            - "0"
+          branches:
+            - line: 2
+              total: "2"
+              covered: "2"
+        - path: /home/runner/work/app/src/main.rs
+          hits:
+            - "1"
+            - "1"
+            - "1"
+        "#);
+    }
+
+    #[test]
+    fn skipped_region() {
+        let input = r#"{"data": [{"files": [{"filename": "a.c", "segments": [
+            [1, 1, 1, true, true, false],
+            [2, 1, 0, false, true, false],
+            [3, 1, 1, true, false, false]
+        ]}]}], "type": "llvm.coverage.json.export"}"#;
+
+        let parsed_results = LlvmCov::new().parse_text(input).unwrap();
+        insta::assert_yaml_snapshot!(parsed_results, @r#"
+        - path: a.c
+          hits:
+            - "1"
+            - "-1"
+            - "-1"
+        "#);
+    }
+}
@@ -0,0 +1,190 @@
+use crate::Parser;
+use anyhow::{Context, Result};
+use qlty_types::tests::v1::{BranchCoverage, FileCoverage};
+use std::collections::{BTreeMap, HashMap};
+use std::io::BufReader;
+use xml::attribute::OwnedAttribute;
+use xml::reader::{EventReader, XmlEvent};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Opencover {}
+
+#[derive(Debug, Default)]
+struct OpencoverFile {
+    lines: BTreeMap<u32, i64>,
+    branches: BTreeMap<u32, BranchCoverage>,
+}
+
+impl Opencover {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn parse_xml(&self, text: &str) -> Result<BTreeMap<String, OpencoverFile>> {
+        let reader = BufReader::new(text.as_bytes());
+        let parser = EventReader::new(reader);
+
+        let mut files: BTreeMap<String, OpencoverFile> = BTreeMap::new();
+        // File uids are only unique within a module
+        let mut paths: HashMap<String, String> = HashMap::new();
+        let mut current_uid: Option<String> = None;
+
+        for event in parser {
+            match event {
+                Ok(XmlEvent::StartElement {
+                    name, attributes, ..
+                }) => match name.local_name.as_str() {
+                    "Module" => {
+                        paths.clear();
+                        current_uid = None;
+                    }
+                    "File" => {
+                        if let (Some(uid), Some(path)) = (
+                            attribute(&attributes, "uid"),
+                            attribute(&attributes, "fullPath"),
+                        ) {
+                            paths.insert(uid.to_string(), path.to_string());
+                        }
+                    }
+                    "FileRef" => {
+                        current_uid = attribute(&attributes, "uid").map(str::to_string);
+                    }
+                    "SequencePoint" | "BranchPoint" => {
+                        let uid = attribute(&attributes, "fileid")
+                            .map(str::to_string)
+                            .or_else(|| current_uid.clone());
+
+                        let Some(path) = uid.and_then(|uid| paths.get(&uid)) else {
+                            continue;
+                        };
+
+                        let visits = numeric_attribute(&attributes, "vc")?.unwrap_or(0);
+                        let Some(start_line) = numeric_attribute(&attributes, "sl")? else {
+                            continue;
+                        };
+                        let start_line = start_line as u32;
+
+                        let file = files.entry(path.clone()).or_default();
+
+                        if name.local_name == "SequencePoint" {
+                            let end_line = numeric_attribute(&attributes, "el")?
+                                .map(|line| line as u32)
+                                .unwrap_or(start_line)
+                                .max(start_line);
+
+                            // A statement spanning several lines covers all of them,
+                            // and a line shared by several statements is executed as
+                            // often as the most executed one
+                            for line in start_line..=end_line {
+                                let hits = file.lines.entry(line).or_insert(0);
+                                *hits = (*hits).max(visits);
+                            }
+                        } else {
+                            let branch = file.branches.entry(start_line).or_default();
+                            branch.line = start_line;
+                            branch.total += 1;
+                            branch.covered += (visits > 0) as i64;
+                        }
+                    }
+                    _ => {}
+                },
+                Ok(XmlEvent::EndElement { name }) => {
+                    if name.local_name == "Method" {
+                        current_uid = None;
+                    }
+                }
+                Err(e) => {
+                    return Err(anyhow::anyhow!("XML parsing error: {}", e));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+impl Parser for Opencover {
+    fn parse_text(&self, text: &str) -> Result<Vec<FileCoverage>> {
+        let files = self
+            .parse_xml(text)
+            .with_context(|| "Failed to parse XML text")?;
+
+        let mut file_coverages = vec![];
+
+        for (path, file) in files {
+            let maximum_line_number = file.lines.keys().max().copied().unwrap_or(0);
+            let mut hits = vec![-1; maximum_line_number as usize];
+
+            for (line, count) in file.lines {
+                if line > 0 {
+                    hits[line as usize - 1] = count;
+                }
+            }
+
+            file_coverages.push(FileCoverage {
+                path,
+                hits,
+                branches: file.branches.into_values().collect(),
+                ..Default::default()
+            });
+        }
+
+        Ok(file_coverages)
+    }
+}
+
+fn attribute<'a>(attributes: &'a [OwnedAttribute], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|attr| attr.name.local_name == name)
+        .map(|attr| attr.value.as_str())
+}
+
+fn numeric_attribute(attributes: &[OwnedAttribute], name: &str) -> Result<Option<i64>> {
+    attribute(attributes, name)
+        .map(|value| {
+            value
+                .parse::<i64>()
+                .with_context(|| format!("Failed to parse '{}' attribute value: {}", name, value))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opencover_results() {
+        let input = include_str!("../../tests/fixtures/opencover/sample.xml");
+
+        let parsed_results = Opencover::new().parse_text(input).unwrap();
+        insta::assert_yaml_snapshot!(parsed_results, @r#"
+        - path: /home/runner/work/app/src/Calculator.cs
+          hits:
+            - "-1"
+            - "-1"
+            - "-1"
+            - "-1"
+            - "2"
+            - "2"
+            - "2"
+            - "-1"
+            - "-1"
+            - "1"
+            - "1"
+            - "1"
+            - "0"
+          branches:
+            - line: 11
+              total: "2"
+              covered: "1"
+        - path: /home/runner/work/app/src/Program.cs
+          hits:
+            - "-1"
+            - "-1"
+            - "0"
+        "#);
+    }
+}
@@ -7,6 +7,7 @@ mod report;
 mod results;
 mod settings;
 mod summing;
+mod target;
 mod upload;
 
 pub use metrics::CoverageMetrics;
@@ -17,4 +18,6 @@ pub use reader::Reader;
 pub use report::Report;
 pub use results::Results;
 pub use settings::Settings;
+pub(crate) use summing::merge_branches;
+pub use target::Target;
 pub use upload::Upload;
@@ -6,6 +6,7 @@ use crate::transformer::AddPrefix;
 use crate::transformer::AppendMetadata;
 use crate::transformer::ComputeSummary;
 use crate::transformer::DefaultPathFixer;
+use crate::transformer::ExcludePragmas;
 use crate::transformer::IgnorePaths;
 use crate::transformer::PrependSrcDir;
 use crate::transformer::StripDotSlashPrefix;
@@ -94,8 +95,6 @@ impl Planner {
     ) -> Result<Vec<Box<dyn Transformer>>> {
         let mut transformers: Vec<Box<dyn Transformer>> = vec![];
 
-        transformers.push(Box::new(ComputeSummary::new()));
-
         // Check if user provided any manual path fixing options
         let has_manual_path_fixing =
             self.settings.strip_prefix.is_some() || self.settings.add_prefix.is_some();
@@ -134,6 +133,16 @@ impl Planner {
 
         transformers.push(Box::new(StripDotSlashPrefix));
 
+        // Pragmas are read from the source files, so this runs once paths are final,
+        // and before the summary so that excluded lines are counted as omitted
+        if self.config.coverage.pragmas {
+            transformers.push(Box::new(ExcludePragmas::new(
+                self.settings.root.clone(),
+                self.config.coverage.ignore_marker.clone(),
+            )));
+        }
+        transformers.push(Box::new(ComputeSummary::new()));
+
         transformers.push(Box::new(AppendMetadata::new(metadata)));
         Ok(transformers)
     }
@@ -846,4 +855,27 @@ mod tests {
         let result = transformed.unwrap();
         assert_eq!(result.path, "project/src/main.rs");
     }
+
+    #[test]
+    fn test_exclude_pragmas_only_added_when_enabled() {
+        let settings = Settings {
+            strip_prefix: Some("/home/user/project".to_string()),
+            ..Default::default()
+        };
+        let metadata = CoverageMetadata::default();
+
+        let has_exclude_pragmas = |config: &QltyConfig| {
+            Planner::new(config, &settings)
+                .compute_transformers(&metadata)
+                .unwrap()
+                .iter()
+                .any(|t| format!("{:?}", t).contains("ExcludePragmas"))
+        };
+
+        let mut config = QltyConfig::default();
+        assert!(!has_exclude_pragmas(&config));
+
+        config.coverage.pragmas = true;
+        assert!(has_exclude_pragmas(&config));
+    }
 }
@@ -1,5 +1,5 @@
-use qlty_types::tests::v1::{CoverageSummary, FileCoverage};
-use std::collections::HashMap;
+use qlty_types::tests::v1::{BranchCoverage, CoverageSummary, FileCoverage};
+use std::collections::{BTreeMap, HashMap};
 
 pub struct DeduplicatedCoverages(Vec<FileCoverage>);
 
@@ -30,7 +30,24 @@ fn merge_hits(existing: &mut Vec<i64>, other: &[i64]) {
     }
 }
 
-fn compute_summary(hits: &[i64]) -> CoverageSummary {
+/// Branch counts cannot be summed without knowing which branches were taken,
+/// so the more complete of the two reports wins for each line.
+pub(crate) fn merge_branches(existing: &mut Vec<BranchCoverage>, other: &[BranchCoverage]) {
+    let mut by_line: BTreeMap<u32, BranchCoverage> = existing
+        .iter()
+        .map(|branch| (branch.line, *branch))
+        .collect();
+
+    for branch in other {
+        let merged = by_line.entry(branch.line).or_insert(*branch);
+        merged.total = merged.total.max(branch.total);
+        merged.covered = merged.covered.max(branch.covered);
+    }
+
+    *existing = by_line.into_values().collect();
+}
+
+fn compute_summary(hits: &[i64], branches: &[BranchCoverage]) -> CoverageSummary {
     let mut covered: i64 = 0;
     let mut missed: i64 = 0;
     let mut omit: i64 = 0;
@@ -43,11 +60,16 @@ fn compute_summary(hits: &[i64]) -> CoverageSummary {
         }
     }
 
+    let branches_covered = branches.iter().map(|branch| branch.covered).sum::<i64>();
+    let branches_total = branches.iter().map(|branch| branch.total).sum::<i64>();
+
     CoverageSummary {
         covered,
         missed,
         omit,
         total: covered + missed + omit,
+        branches_covered,
+        branches_missed: branches_total - branches_covered,
     }
 }
 
@@ -58,6 +80,7 @@ pub fn sum_file_coverages(file_coverages: Vec<FileCoverage>) -> DeduplicatedCove
         match map.get_mut(&fc.path) {
             Some(existing) => {
                 merge_hits(&mut existing.hits, &fc.hits);
+                merge_branches(&mut existing.branches, &fc.branches);
             }
             None => {
                 map.insert(fc.path.clone(), fc);
@@ -68,7 +91,7 @@ pub fn sum_file_coverages(file_coverages: Vec<FileCoverage>) -> DeduplicatedCove
     DeduplicatedCoverages(
         map.into_values()
             .map(|mut fc| {
-                fc.summary = Some(compute_summary(&fc.hits));
+                fc.summary = Some(compute_summary(&fc.hits, &fc.branches));
                 fc
             })
             .collect(),
@@ -186,6 +209,38 @@ mod tests {
             assert_eq!(summary.total, 4);
         }
 
+        #[test]
+        fn branches_merged() {
+            let mut fc1 = make_fc("a.rs", vec![1, 1]);
+            fc1.branches = vec![BranchCoverage {
+                line: 2,
+                total: 2,
+                covered: 1,
+            }];
+
+            let mut fc2 = make_fc("a.rs", vec![1, 1]);
+            fc2.branches = vec![
+                BranchCoverage {
+                    line: 1,
+                    total: 2,
+                    covered: 0,
+                },
+                BranchCoverage {
+                    line: 2,
+                    total: 2,
+                    covered: 2,
+                },
+            ];
+
+            let result = sum_file_coverages(vec![fc1, fc2]).into_inner();
+            assert_eq!(result[0].branches.len(), 2);
+            assert_eq!(result[0].branches[1].covered, 2);
+
+            let summary = result[0].summary.unwrap();
+            assert_eq!(summary.branches_covered, 2);
+            assert_eq!(summary.branches_missed, 2);
+        }
+
         #[test]
         fn metadata_preserved_from_first_entry() {
             let mut fc1 = make_fc("a.rs", vec![1]);
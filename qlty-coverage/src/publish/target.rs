@@ -0,0 +1,157 @@
+use crate::export::CoverageExport;
+use crate::publish::upload::put_data;
+use anyhow::{bail, Context, Result};
+use std::path::PathBuf;
+
+const BUNDLE_FILES: [&str; 2] = ["coverage.zip", "metadata.json"];
+
+/// Where to publish a coverage export other than the Qlty Cloud, such as a
+/// self-hosted dashboard or a local mock receiving the same payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// Copies the export bundle into a directory
+    Directory(PathBuf),
+
+    /// PUTs the export bundle to a URL, with additional request headers
+    Http {
+        url: String,
+        headers: Vec<(String, String)>,
+    },
+}
+
+impl Target {
+    /// Parses a header given as `Name: value`.
+    pub fn parse_header(header: &str) -> Result<(String, String)> {
+        match header.split_once(':') {
+            Some((name, value)) if !name.trim().is_empty() => {
+                Ok((name.trim().to_string(), value.trim().to_string()))
+            }
+            _ => bail!("Invalid header, expected 'Name: value': {}", header),
+        }
+    }
+
+    pub fn publish(&self, export: &CoverageExport) -> Result<()> {
+        match self {
+            Self::Directory(directory) => {
+                std::fs::create_dir_all(directory).with_context(|| {
+                    format!("Failed to create publish directory: {:?}", directory)
+                })?;
+
+                for filename in BUNDLE_FILES {
+                    let path = directory.join(filename);
+                    std::fs::write(&path, export.read_file(filename)?)
+                        .with_context(|| format!("Failed to write file: {:?}", path))?;
+                }
+
+                Ok(())
+            }
+            Self::Http { url, headers } => put_data(
+                url,
+                "application/zip",
+                headers,
+                export.read_file("coverage.zip")?,
+            ),
+        }
+    }
+
+    pub fn location(&self) -> String {
+        match self {
+            Self::Directory(directory) => directory.to_string_lossy().to_string(),
+            Self::Http { url, .. } => url.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qlty_types::tests::v1::CoverageMetadata;
+    use std::sync::mpsc;
+    use std::thread;
+    use tempfile::tempdir;
+
+    fn export(directory: PathBuf) -> CoverageExport {
+        let mut export = CoverageExport {
+            metadata: CoverageMetadata {
+                commit_sha: "abc123".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        export.export_to(Some(directory)).unwrap();
+        export
+    }
+
+    #[test]
+    fn test_parse_header() {
+        assert_eq!(
+            Target::parse_header("Authorization: Bearer a:b").unwrap(),
+            ("Authorization".to_string(), "Bearer a:b".to_string())
+        );
+        assert!(Target::parse_header("Authorization").is_err());
+        assert!(Target::parse_header(": value").is_err());
+    }
+
+    #[test]
+    fn test_publish_to_directory() {
+        let export_dir = tempdir().unwrap();
+        let export = export(export_dir.path().to_path_buf());
+
+        let publish_dir = tempdir().unwrap();
+        let destination = publish_dir.path().join("nested");
+        Target::Directory(destination.clone())
+            .publish(&export)
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read(destination.join("coverage.zip")).unwrap(),
+            export.read_file("coverage.zip").unwrap()
+        );
+
+        let metadata = std::fs::read_to_string(destination.join("metadata.json")).unwrap();
+        assert!(metadata.contains("abc123"));
+    }
+
+    #[test]
+    fn test_publish_to_http() {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        std::env::set_var("QLTY_INSECURE_ALLOW_HTTP", "true");
+
+        let export_dir = tempdir().unwrap();
+        let export = export(export_dir.path().to_path_buf());
+
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/coverage", server.server_addr().to_ip().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        let handle = thread::spawn(move || {
+            let mut request = server.recv().unwrap();
+            let header = request
+                .headers()
+                .iter()
+                .find(|header| header.field.equiv("X-Build"))
+                .map(|header| header.value.to_string());
+            let mut body = vec![];
+            request.as_reader().read_to_end(&mut body).unwrap();
+            sender
+                .send((request.method().to_string(), header, body))
+                .unwrap();
+            request
+                .respond(tiny_http::Response::from_string("ok"))
+                .unwrap();
+        });
+
+        Target::Http {
+            url,
+            headers: vec![("X-Build".to_string(), "42".to_string())],
+        }
+        .publish(&export)
+        .unwrap();
+        handle.join().unwrap();
+
+        let (method, header, body) = receiver.recv().unwrap();
+        assert_eq!(method, "PUT");
+        assert_eq!(header, Some("42".to_string()));
+        assert_eq!(body, export.read_file("coverage.zip").unwrap());
+    }
+}
@@ -79,58 +79,72 @@ impl Upload {
         content_type: &str,
         data: Vec<u8>,
     ) -> Result<(), anyhow::Error> {
-        let response_result = http::put(url)?
-            .set("Content-Type", content_type)
-            .send_bytes(&data);
-
-        let response = match response_result {
-            Ok(resp) => resp,
-            Err(ureq::Error::Status(code, resp)) => match resp.into_string() {
-                Ok(body) => {
-                    bail!(
-                        "HTTP Error {}: PUT {}: Upload request failed with response body: {}",
-                        code,
-                        url,
-                        body
-                    );
-                }
-                Err(err) => {
-                    bail!(
-                            "HTTP Error {}: PUT {}: Upload request failed, error reading response body: {:?}",
-                            code,
-                            url,
-                            err
-                        );
-                }
-            },
-            Err(ureq::Error::Transport(transport_error)) => {
+        put_data(url, content_type, &[], data)
+    }
+
+    fn request_api(metadata: &CoverageMetadata, token: &str) -> Result<Value> {
+        let legacy_api_url = get_legacy_api_url();
+        let client = QltyClient::new(Some(&legacy_api_url), Some(token.into()));
+        client.post_coverage_metadata("/coverage", metadata)
+    }
+}
+
+/// PUTs the data to the URL, failing on any response outside of the 2xx range.
+pub(crate) fn put_data(
+    url: &str,
+    content_type: &str,
+    headers: &[(String, String)],
+    data: Vec<u8>,
+) -> Result<()> {
+    let mut request = http::put(url)?.set("Content-Type", content_type);
+
+    for (name, value) in headers {
+        request = request.set(name, value);
+    }
+
+    let response_result = request.send_bytes(&data);
+
+    let response = match response_result {
+        Ok(resp) => resp,
+        Err(ureq::Error::Status(code, resp)) => match resp.into_string() {
+            Ok(body) => {
                 bail!(
-                    "Transport Error: PUT {}: Error sending upload bytes: {:?}",
+                    "HTTP Error {}: PUT {}: Upload request failed with response body: {}",
+                    code,
                     url,
-                    transport_error
+                    body
                 );
             }
-        };
-
-        if response.status() < 200 || response.status() >= 300 {
+            Err(err) => {
+                bail!(
+                        "HTTP Error {}: PUT {}: Upload request failed, error reading response body: {:?}",
+                        code,
+                        url,
+                        err
+                    );
+            }
+        },
+        Err(ureq::Error::Transport(transport_error)) => {
             bail!(
-                "HTTP Error {}: PUT {}: Upload request returned an error: {:?}",
-                response.status(),
+                "Transport Error: PUT {}: Error sending upload bytes: {:?}",
                 url,
-                response
-                    .into_string()
-                    .map_err(|err| anyhow!("Error reading response body: {:?}", err))?,
+                transport_error
             );
         }
-
-        Ok(())
+    };
+
+    if response.status() < 200 || response.status() >= 300 {
+        bail!(
+            "HTTP Error {}: PUT {}: Upload request returned an error: {:?}",
+            response.status(),
+            url,
+            response
+                .into_string()
+                .map_err(|err| anyhow!("Error reading response body: {:?}", err))?,
+        );
     }
 
-    fn request_api(metadata: &CoverageMetadata, token: &str) -> Result<Value> {
-        let legacy_api_url = get_legacy_api_url();
-        let client = QltyClient::new(Some(&legacy_api_url), Some(token.into()));
-        client.post_coverage_metadata("/coverage", metadata)
-    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -1,13 +1,18 @@
 use crate::transform::{Plan, Settings};
 use crate::transformer::AddPrefix;
+use crate::transformer::ComputeSummary;
 use crate::transformer::DefaultPathFixer;
+use crate::transformer::ExcludePragmas;
+use crate::transformer::IgnorePaths;
 use crate::transformer::StripDotSlashPrefix;
 use crate::transformer::StripPrefix;
 use crate::utils::extract_path_and_format;
 use crate::Transformer;
 use anyhow::Result;
 use qlty_analysis::utils::fs::path_to_string;
+use qlty_config::Workspace;
 use qlty_types::tests::v1::ReportFile;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
 pub struct Planner {
@@ -59,10 +64,26 @@ impl Planner {
 
         transformers.push(Box::new(StripDotSlashPrefix));
 
+        if !self.settings.ignores.is_empty() {
+            transformers.push(Box::new(IgnorePaths::new(&self.settings.ignores)?));
+        }
+
         if let Some(prefix) = self.settings.add_prefix.clone() {
             transformers.push(Box::new(AddPrefix::new(&prefix)));
         }
 
+        // Paths are relative to the root of the repository once transformed
+        if self.settings.pragmas {
+            let root =
+                Workspace::assert_within_git_directory().unwrap_or_else(|_| PathBuf::from("."));
+            transformers.push(Box::new(ExcludePragmas::new(
+                root,
+                self.settings.ignore_marker.clone(),
+            )));
+        }
+
+        transformers.push(Box::new(ComputeSummary::new()));
+
         Ok(transformers)
     }
 }
@@ -78,6 +99,9 @@ mod tests {
             report_format: None,
             strip_prefix: None,
             add_prefix: None,
+            ignores: vec![],
+            pragmas: false,
+            ignore_marker: None,
         };
 
         let planner = Planner::new(&settings);
@@ -100,6 +124,9 @@ mod tests {
             report_format: None,
             strip_prefix: Some("/home/user/project".to_string()),
             add_prefix: None,
+            ignores: vec![],
+            pragmas: false,
+            ignore_marker: None,
         };
 
         let planner = Planner::new(&settings);
@@ -122,6 +149,9 @@ mod tests {
             report_format: None,
             strip_prefix: None,
             add_prefix: Some("src/".to_string()),
+            ignores: vec![],
+            pragmas: false,
+            ignore_marker: None,
         };
 
         let planner = Planner::new(&settings);
@@ -144,6 +174,9 @@ mod tests {
             report_format: None,
             strip_prefix: Some("/home/user/project".to_string()),
             add_prefix: Some("src/".to_string()),
+            ignores: vec![],
+            pragmas: false,
+            ignore_marker: None,
         };
 
         let planner = Planner::new(&settings);
@@ -155,4 +188,30 @@ mod tests {
             .any(|t| format!("{:?}", t).contains("DefaultPathFixer"));
         assert!(!has_default_fixer, "DefaultPathFixer should not be added when both strip_prefix and add_prefix are provided");
     }
+
+    #[test]
+    fn test_exclude_pragmas_only_added_when_enabled() {
+        let mut settings = Settings {
+            path: "coverage.json".to_string(),
+            report_format: None,
+            strip_prefix: Some("/home/user/project".to_string()),
+            add_prefix: None,
+            ignores: vec![],
+            pragmas: false,
+            ignore_marker: None,
+        };
+
+        let has_exclude_pragmas = |settings: &Settings| {
+            Planner::new(settings)
+                .compute_transformers()
+                .unwrap()
+                .iter()
+                .any(|t| format!("{:?}", t).contains("ExcludePragmas"))
+        };
+
+        assert!(!has_exclude_pragmas(&settings));
+
+        settings.pragmas = true;
+        assert!(has_exclude_pragmas(&settings));
+    }
 }
@@ -7,4 +7,10 @@ pub struct Settings {
     pub report_format: Option<Formats>,
     pub add_prefix: Option<String>,
     pub strip_prefix: Option<String>,
+    #[serde(default)]
+    pub ignores: Vec<String>,
+    #[serde(default)]
+    pub pragmas: bool,
+    #[serde(default)]
+    pub ignore_marker: Option<String>,
 }
@@ -4,6 +4,8 @@ use qlty_analysis::utils::fs::path_to_string;
 use qlty_config::Workspace;
 use qlty_types::tests::v1::{CoverageMetadata, CoverageSummary, FileCoverage};
 use regex::Regex;
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
 use std::path::Path;
 use std::{fmt::Debug, path::PathBuf};
 
@@ -45,6 +47,17 @@ impl Transformer for ComputeSummary {
             }
         }
 
+        let branches_covered = file_coverage
+            .branches
+            .iter()
+            .map(|branch| branch.covered)
+            .sum::<i64>();
+        let branches_total = file_coverage
+            .branches
+            .iter()
+            .map(|branch| branch.total)
+            .sum::<i64>();
+
         let mut file_coverage = file_coverage;
 
         file_coverage.summary = Some(CoverageSummary {
@@ -52,6 +65,8 @@ impl Transformer for ComputeSummary {
             missed,
             omit,
             total: covered + missed + omit,
+            branches_covered,
+            branches_missed: branches_total - branches_covered,
         });
 
         Some(file_coverage)
@@ -328,9 +343,244 @@ impl Transformer for PrependSrcDir {
     }
 }
 
+/// Marks lines excluded by coverage pragmas in the source files as omitted,
+/// for reports produced by tools that did not honor them.
+#[derive(Debug, Clone)]
+pub struct ExcludePragmas {
+    root: PathBuf,
+    marker: String,
+}
+
+/// Pragmas excluding the line they are on
+const LINE_PRAGMAS: [&str; 2] = ["LCOV_EXCL_LINE", "GCOVR_EXCL_LINE"];
+
+/// Pragmas excluding the statement they are on, including the block it opens
+const STATEMENT_PRAGMAS: [&str; 2] = ["pragma: no cover", "pragma: nocover"];
+
+/// Pragmas excluding the statement that follows them
+const NEXT_STATEMENT_PRAGMAS: [&str; 3] =
+    ["istanbul ignore next", "c8 ignore next", "v8 ignore next"];
+
+const BLOCK_START_PRAGMAS: [&str; 4] = [
+    "LCOV_EXCL_START",
+    "GCOVR_EXCL_START",
+    "c8 ignore start",
+    "v8 ignore start",
+];
+
+const BLOCK_STOP_PRAGMAS: [&str; 4] = [
+    "LCOV_EXCL_STOP",
+    "GCOVR_EXCL_STOP",
+    "c8 ignore stop",
+    "v8 ignore stop",
+];
+
+const DEFAULT_IGNORE_MARKER: &str = "qlty-ignore-coverage";
+
+impl ExcludePragmas {
+    /// The marker works like `pragma: no cover`, and `<marker>-start` and
+    /// `<marker>-end` exclude the lines between them.
+    pub fn new(root: PathBuf, marker: Option<String>) -> Self {
+        Self {
+            root,
+            marker: marker.unwrap_or_else(|| DEFAULT_IGNORE_MARKER.to_string()),
+        }
+    }
+
+    /// Zero-based indexes of the excluded lines
+    fn excluded_lines(&self, source: &str) -> HashSet<usize> {
+        let lines = source.lines().collect::<Vec<_>>();
+        let blocks = self.excluded_blocks(&lines);
+
+        let mut excluded = HashSet::new();
+
+        for (index, line) in lines.iter().enumerate() {
+            if blocks.iter().any(|block| block.contains(&index))
+                || contains_any(line, &LINE_PRAGMAS)
+            {
+                excluded.insert(index);
+            } else if self.is_statement_marker(line) || contains_any(line, &STATEMENT_PRAGMAS) {
+                excluded.extend(statement_lines(&lines, index));
+            } else if let Some(position) = NEXT_STATEMENT_PRAGMAS
+                .iter()
+                .find_map(|pragma| line.find(pragma).map(|start| start + pragma.len()))
+            {
+                excluded.insert(index);
+
+                // The pragma applies to code following it on the same line, if any
+                let rest = &line[position..];
+                let rest = rest.split_once("*/").map_or(rest, |(_, after)| after);
+                let target = if rest.trim().is_empty() {
+                    (index + 1..lines.len()).find(|next| !lines[*next].trim().is_empty())
+                } else {
+                    Some(index)
+                };
+
+                if let Some(target) = target {
+                    excluded.extend(statement_lines(&lines, target));
+                }
+            }
+        }
+
+        excluded
+    }
+
+    /// Line ranges between start and stop pragmas, including the pragmas. A
+    /// start without a stop is ignored rather than excluding the rest of the
+    /// file, since it is more likely a mistake or inside a string.
+    fn excluded_blocks(&self, lines: &[&str]) -> Vec<RangeInclusive<usize>> {
+        let block_start = format!("{}-start", self.marker);
+        let block_end = format!("{}-end", self.marker);
+
+        let mut blocks = vec![];
+        let mut start = None;
+
+        for (index, line) in lines.iter().enumerate() {
+            match start {
+                Some(first)
+                    if line.contains(&block_end) || contains_any(line, &BLOCK_STOP_PRAGMAS) =>
+                {
+                    blocks.push(first..=index);
+                    start = None;
+                }
+                None if line.contains(&block_start) || contains_any(line, &BLOCK_START_PRAGMAS) => {
+                    start = Some(index);
+                }
+                _ => {}
+            }
+        }
+
+        blocks
+    }
+
+    fn is_statement_marker(&self, line: &str) -> bool {
+        line.split(&self.marker)
+            .skip(1)
+            .any(|after| !after.starts_with("-start") && !after.starts_with("-end"))
+    }
+}
+
+fn contains_any(line: &str, pragmas: &[&str]) -> bool {
+    pragmas.iter().any(|pragma| line.contains(pragma))
+}
+
+/// The lines of the statement starting at `start`, extended to the end of the
+/// block it opens, either with braces or with a colon and indentation.
+fn statement_lines(lines: &[&str], start: usize) -> Vec<usize> {
+    let line = lines[start];
+    let mut depth = line.matches('{').count() as i64 - line.matches('}').count() as i64;
+
+    if depth > 0 {
+        let mut end = start;
+
+        while depth > 0 && end + 1 < lines.len() {
+            end += 1;
+            depth += lines[end].matches('{').count() as i64;
+            depth -= lines[end].matches('}').count() as i64;
+        }
+
+        return (start..=end).collect();
+    }
+
+    let code = line.split_once('#').map_or(line, |(code, _)| code);
+
+    if code.trim_end().ends_with(':') {
+        let block_indentation = indentation(line);
+        let mut end = start;
+
+        for (index, next) in lines.iter().enumerate().skip(start + 1) {
+            if next.trim().is_empty() {
+                continue;
+            }
+
+            if indentation(next) <= block_indentation {
+                break;
+            }
+
+            end = index;
+        }
+
+        return (start..=end).collect();
+    }
+
+    vec![start]
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+impl Transformer for ExcludePragmas {
+    fn transform(&self, file_coverage: FileCoverage) -> Option<FileCoverage> {
+        let Ok(source) = std::fs::read_to_string(self.root.join(&file_coverage.path)) else {
+            return Some(file_coverage);
+        };
+
+        let excluded = self.excluded_lines(&source);
+
+        if excluded.is_empty() {
+            return Some(file_coverage);
+        }
+
+        let mut file_coverage = file_coverage;
+
+        for index in &excluded {
+            if let Some(hit) = file_coverage.hits.get_mut(*index) {
+                *hit = -1;
+            }
+        }
+
+        file_coverage
+            .branches
+            .retain(|branch| branch.line == 0 || !excluded.contains(&(branch.line as usize - 1)));
+
+        Some(file_coverage)
+    }
+
+    fn clone_box(&self) -> Box<dyn Transformer> {
+        Box::new(self.clone())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use qlty_types::tests::v1::BranchCoverage;
+
+    #[test]
+    fn test_compute_summary() {
+        let file_coverage = FileCoverage {
+            path: "src/lib.rs".to_string(),
+            hits: vec![1, 0, -1, 3],
+            branches: vec![
+                BranchCoverage {
+                    line: 1,
+                    total: 2,
+                    covered: 1,
+                },
+                BranchCoverage {
+                    line: 4,
+                    total: 4,
+                    covered: 4,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let summary = ComputeSummary::new()
+            .transform(file_coverage)
+            .unwrap()
+            .summary
+            .unwrap();
+
+        assert_eq!(summary.covered, 2);
+        assert_eq!(summary.missed, 1);
+        assert_eq!(summary.omit, 1);
+        assert_eq!(summary.total, 4);
+        assert_eq!(summary.branches_covered, 5);
+        assert_eq!(summary.branches_missed, 1);
+        assert_eq!(summary.branch_percent(), Some(5.0 / 6.0 * 100.0));
+    }
 
     #[test]
     fn test_default_path_fixer_circleci() {
@@ -753,4 +1003,141 @@ mod tests {
             );
         }
     }
+
+    mod exclude_pragmas_tests {
+        use super::*;
+        use std::fs;
+        use tempfile::TempDir;
+
+        fn excluded_lines(source: &str, marker: Option<&str>) -> Vec<usize> {
+            let transformer = ExcludePragmas::new(PathBuf::new(), marker.map(str::to_string));
+            let mut lines = transformer
+                .excluded_lines(source)
+                .into_iter()
+                .map(|index| index + 1)
+                .collect::<Vec<_>>();
+            lines.sort();
+            lines
+        }
+
+        #[test]
+        fn python_no_cover_block() {
+            let source = "\
+def covered():
+    return 1
+
+def debug():  # pragma: no cover
+    print('a')
+
+    print('b')
+x = 1  # pragma: no cover
+y = 2
+";
+            assert_eq!(excluded_lines(source, None), vec![4, 5, 6, 7, 8]);
+        }
+
+        #[test]
+        fn istanbul_ignore_next() {
+            let source = "\
+/* istanbul ignore next */
+function debug() {
+  console.log('a');
+}
+const a = 1;
+const b = cond ? /* istanbul ignore next */ x : y;
+";
+            assert_eq!(excluded_lines(source, None), vec![1, 2, 3, 4, 6]);
+        }
+
+        #[test]
+        fn lcov_excl_block_and_line() {
+            let source = "\
+int main() {
+  // LCOV_EXCL_START
+  abort();
+  // LCOV_EXCL_STOP
+  return 0; // LCOV_EXCL_LINE
+}
+";
+            assert_eq!(excluded_lines(source, None), vec![2, 3, 4, 5]);
+        }
+
+        #[test]
+        fn unterminated_block() {
+            let source = "a\n// c8 ignore start\nb\nc // LCOV_EXCL_LINE\n";
+            assert_eq!(excluded_lines(source, None), vec![4]);
+
+            let source = "a\nb // no-cov-start\nc\n";
+            assert_eq!(excluded_lines(source, Some("no-cov")), Vec::<usize>::new());
+        }
+
+        #[test]
+        fn default_and_custom_markers() {
+            let source = "\
+a // qlty-ignore-coverage
+b // no-cov
+// no-cov-start
+c
+// no-cov-end
+d
+";
+            assert_eq!(excluded_lines(source, None), vec![1]);
+            assert_eq!(excluded_lines(source, Some("no-cov")), vec![2, 3, 4, 5]);
+        }
+
+        #[test]
+        fn transform_marks_lines_omitted() {
+            let temp = TempDir::new().unwrap();
+            fs::write(
+                temp.path().join("app.py"),
+                "def debug():  # pragma: no cover\n    if x:\n        pass\nrun()\n",
+            )
+            .unwrap();
+
+            let transformer = ExcludePragmas::new(temp.path().to_path_buf(), None);
+            let file_coverage = FileCoverage {
+                path: "app.py".to_string(),
+                hits: vec![1, 0, 0, 1],
+                branches: vec![
+                    BranchCoverage {
+                        line: 2,
+                        total: 2,
+                        covered: 0,
+                    },
+                    BranchCoverage {
+                        line: 4,
+                        total: 2,
+                        covered: 1,
+                    },
+                ],
+                ..Default::default()
+            };
+
+            let result = transformer.transform(file_coverage).unwrap();
+            assert_eq!(result.hits, vec![-1, -1, -1, 1]);
+            assert_eq!(result.branches.len(), 1);
+            assert_eq!(result.branches[0].line, 4);
+
+            let summary = ComputeSummary::new()
+                .transform(result)
+                .unwrap()
+                .summary
+                .unwrap();
+            assert_eq!(summary.omit, 3);
+            assert_eq!(summary.covered, 1);
+        }
+
+        #[test]
+        fn transform_missing_source_file() {
+            let transformer = ExcludePragmas::new(PathBuf::from("/nonexistent"), None);
+            let file_coverage = FileCoverage {
+                path: "app.py".to_string(),
+                hits: vec![1, 0],
+                ..Default::default()
+            };
+
+            let result = transformer.transform(file_coverage.clone()).unwrap();
+            assert_eq!(result, file_coverage);
+        }
+    }
 }
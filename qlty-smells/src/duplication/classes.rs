@@ -0,0 +1,262 @@
+use super::code::context_start_line;
+use qlty_analysis::Report;
+use qlty_types::analysis::v1::Issue;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// A family of clones: every occurrence of the same duplicated structure,
+/// regardless of the language it was found in. Near-miss pairs form a class
+/// of their own.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CloneClass {
+    pub rule_key: String,
+    pub node_kind: String,
+    pub mass: usize,
+    pub similarity: Option<f64>,
+    pub occurrences: Vec<CloneOccurrence>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CloneOccurrence {
+    pub path: String,
+    pub language: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub differing_lines: String,
+    pub snippet_with_context: String,
+    pub context_start_line: usize,
+}
+
+/// Lines covered by at least one clone in the files directly inside a directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DirectoryDuplication {
+    pub directory: String,
+    pub files: usize,
+    pub duplicated_lines: usize,
+}
+
+impl CloneClass {
+    fn from_issues(issues: &[Issue]) -> Self {
+        let first = &issues[0];
+
+        let mut occurrences = issues
+            .iter()
+            .filter_map(CloneOccurrence::from_issue)
+            .collect::<Vec<_>>();
+        occurrences.sort_by(|a, b| {
+            a.path
+                .cmp(&b.path)
+                .then_with(|| a.start_line.cmp(&b.start_line))
+        });
+
+        Self {
+            rule_key: first.rule_key.clone(),
+            node_kind: first.get_property_string("node_kind"),
+            mass: first.get_property_number("mass") as usize,
+            similarity: if first.rule_key == "near-miss-code" {
+                Some(first.get_property_number("similarity"))
+            } else {
+                None
+            },
+            occurrences,
+        }
+    }
+
+    /// Total lines across all occurrences
+    pub fn duplicated_lines(&self) -> usize {
+        self.occurrences
+            .iter()
+            .map(CloneOccurrence::lines_count)
+            .sum()
+    }
+
+    pub fn languages(&self) -> Vec<String> {
+        self.occurrences
+            .iter()
+            .map(|occurrence| occurrence.language.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+impl CloneOccurrence {
+    fn from_issue(issue: &Issue) -> Option<Self> {
+        let path = issue.path()?;
+        let range = issue.range()?;
+        let start_line = range.start_line as usize;
+
+        Some(Self {
+            path,
+            language: issue
+                .language()
+                .as_str_name()
+                .trim_start_matches("LANGUAGE_")
+                .to_lowercase(),
+            start_line,
+            end_line: range.end_line as usize,
+            differing_lines: if issue.rule_key == "near-miss-code" {
+                issue.get_property_string("differing_lines")
+            } else {
+                String::new()
+            },
+            snippet_with_context: issue.snippet_with_context.clone(),
+            context_start_line: context_start_line(start_line),
+        })
+    }
+
+    pub fn lines_count(&self) -> usize {
+        self.end_line - self.start_line + 1
+    }
+}
+
+/// Groups the duplication issues of a report into clone classes, largest first.
+pub fn clone_classes(report: &Report) -> Vec<CloneClass> {
+    let mut classes = report
+        .duplication_issues_by_duplication()
+        .into_values()
+        .map(|issues| CloneClass::from_issues(&issues))
+        .filter(|class| !class.occurrences.is_empty())
+        .collect::<Vec<_>>();
+
+    classes.sort_by(|a, b| {
+        b.duplicated_lines()
+            .cmp(&a.duplicated_lines())
+            .then_with(|| a.occurrences[0].path.cmp(&b.occurrences[0].path))
+            .then_with(|| {
+                a.occurrences[0]
+                    .start_line
+                    .cmp(&b.occurrences[0].start_line)
+            })
+    });
+
+    classes
+}
+
+/// Totals the duplicated lines of each directory, most duplicated first.
+/// Lines shared by overlapping clones are only counted once.
+pub fn duplication_by_directory(classes: &[CloneClass]) -> Vec<DirectoryDuplication> {
+    let mut lines_by_path: BTreeMap<&str, BTreeSet<usize>> = BTreeMap::new();
+
+    for occurrence in classes.iter().flat_map(|class| &class.occurrences) {
+        lines_by_path
+            .entry(&occurrence.path)
+            .or_default()
+            .extend(occurrence.start_line..=occurrence.end_line);
+    }
+
+    let mut directories: BTreeMap<String, DirectoryDuplication> = BTreeMap::new();
+
+    for (path, lines) in lines_by_path {
+        let directory = match Path::new(path).parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.display().to_string(),
+            _ => ".".to_string(),
+        };
+
+        let entry = directories
+            .entry(directory.clone())
+            .or_insert(DirectoryDuplication {
+                directory,
+                files: 0,
+                duplicated_lines: 0,
+            });
+        entry.files += 1;
+        entry.duplicated_lines += lines.len();
+    }
+
+    let mut directories = directories.into_values().collect::<Vec<_>>();
+    directories.sort_by(|a, b| b.duplicated_lines.cmp(&a.duplicated_lines));
+    directories
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_types::analysis::v1::{Language, Location, Range};
+
+    fn issue(hash: &str, path: &str, lines: (u32, u32), language: Language) -> Issue {
+        let mut issue = Issue {
+            tool: "qlty".to_string(),
+            driver: "duplication".to_string(),
+            rule_key: "identical-code".to_string(),
+            language: language.into(),
+            location: Some(Location {
+                path: path.to_string(),
+                range: Some(Range {
+                    start_line: lines.0,
+                    end_line: lines.1,
+                    ..Default::default()
+                }),
+            }),
+            ..Default::default()
+        };
+
+        issue.set_property_string("structural_hash", hash.to_string());
+        issue.set_property_string("node_kind", "function".to_string());
+        issue.set_property_number("mass", 40.0);
+        issue
+    }
+
+    fn report() -> Report {
+        Report {
+            issues: vec![
+                issue("a", "src/b.js", (20, 29), Language::Javascript),
+                issue("a", "src/a.ts", (1, 10), Language::Typescript),
+                issue("a", "lib/c.js", (5, 14), Language::Javascript),
+                issue("b", "src/a.ts", (5, 12), Language::Typescript),
+                issue("b", "main.ts", (1, 8), Language::Typescript),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn classes() {
+        let classes = clone_classes(&report());
+
+        assert_eq!(2, classes.len());
+        assert_eq!(30, classes[0].duplicated_lines());
+        assert_eq!(
+            vec!["javascript".to_string(), "typescript".to_string()],
+            classes[0].languages()
+        );
+        assert_eq!(
+            vec!["lib/c.js", "src/a.ts", "src/b.js"],
+            classes[0]
+                .occurrences
+                .iter()
+                .map(|occurrence| occurrence.path.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(11, classes[0].occurrences[2].context_start_line);
+        assert_eq!(16, classes[1].duplicated_lines());
+        assert_eq!(None, classes[1].similarity);
+    }
+
+    #[test]
+    fn directories() {
+        let directories = duplication_by_directory(&clone_classes(&report()));
+
+        assert_eq!(
+            vec![
+                DirectoryDuplication {
+                    directory: "src".to_string(),
+                    files: 2,
+                    duplicated_lines: 22,
+                },
+                DirectoryDuplication {
+                    directory: "lib".to_string(),
+                    files: 1,
+                    duplicated_lines: 10,
+                },
+                DirectoryDuplication {
+                    directory: ".".to_string(),
+                    files: 1,
+                    duplicated_lines: 8,
+                },
+            ],
+            directories
+        );
+    }
+}
@@ -23,7 +23,7 @@ pub struct Node {
     pub source_hash: md5::Digest,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct NodeWithFile {
     pub source_file: Arc<File>,
     pub node: Arc<Node>,
@@ -49,7 +49,7 @@ impl NodeWithFile {
         let start_line = self.node.start_line;
         let end_line = self.node.end_line;
 
-        let start_context_index = start_line.saturating_sub(CONTEXT_LINES);
+        let start_context_index = context_start_line(start_line) - 1;
         let end_context_index = if end_line + CONTEXT_LINES < lines.len() {
             end_line + CONTEXT_LINES
         } else {
@@ -60,6 +60,12 @@ impl NodeWithFile {
     }
 }
 
+/// The first line shown by `NodeWithFile::snippet_with_context` for a node
+/// starting at `start_line`
+pub fn context_start_line(start_line: usize) -> usize {
+    start_line.saturating_sub(CONTEXT_LINES) + 1
+}
+
 impl Node {
     pub fn lines_count(&self) -> usize {
         self.end_line - self.start_line + 1
@@ -1,3 +1,5 @@
+use super::near_miss;
+use super::NearMiss;
 use super::Node;
 use super::NodeVisitor;
 use super::NodeWithFile;
@@ -5,7 +7,7 @@ use super::Plan;
 use qlty_analysis::snippet::truncate_snippet;
 use qlty_analysis::utils::fs::path_to_string;
 use qlty_analysis::{
-    code::{File, NodeFilterBuilder, Visitor},
+    code::{capture_by_name, File, NodeFilterBuilder, Visitor, QUERY_MATCH_LIMIT},
     Report,
 };
 use qlty_types::analysis::v1::{Category, Issue, Level, Location, Range};
@@ -13,6 +15,7 @@ use qlty_types::calculate_effort_minutes;
 use qlty_types::language_enum_from_name;
 use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
 use std::sync::Arc;
 
 const BASE_EFFORT_MINUTES: u32 = 10;
@@ -23,6 +26,8 @@ const MAX_OTHER_LOCATIONS: usize = 21;
 pub struct Executor {
     plan: Plan,
     pub nodes_by_hash: HashMap<md5::Digest, Vec<NodeWithFile>>,
+    pub near_misses: Vec<NearMiss>,
+    near_miss_candidates: Vec<NodeWithFile>,
 }
 
 impl Executor {
@@ -30,12 +35,15 @@ impl Executor {
         Self {
             plan: plan.clone(),
             nodes_by_hash: HashMap::new(),
+            near_misses: vec![],
+            near_miss_candidates: vec![],
         }
     }
 
     pub fn execute(&mut self) {
         self.index();
         self.prune();
+        self.detect_near_misses();
     }
 
     pub fn index(&mut self) {
@@ -66,24 +74,50 @@ impl Executor {
 
                 visitor.process_node(&mut root.walk());
 
-                (source_file.clone(), visitor.nodes)
+                let definitions = if self
+                    .plan
+                    .get_language(&source_file.language_name)
+                    .near_miss_lines_threshold
+                    .is_some()
+                {
+                    definition_ranges(&source_file, &tree)
+                } else {
+                    HashSet::new()
+                };
+
+                (source_file.clone(), visitor.nodes, definitions)
             })
             .collect();
 
-        for (source_file, nodes) in files_to_results {
-            self.index_file(source_file, &nodes);
+        for (source_file, nodes, definitions) in files_to_results {
+            self.index_file(source_file, &nodes, &definitions);
         }
     }
 
-    pub fn index_file(&mut self, source_file: Arc<File>, nodes: &[Arc<Node>]) {
-        let nodes_threshold = self
-            .plan
-            .get_language(&source_file.language_name)
-            .nodes_threshold;
+    pub fn index_file(
+        &mut self,
+        source_file: Arc<File>,
+        nodes: &[Arc<Node>],
+        definitions: &HashSet<std::ops::Range<usize>>,
+    ) {
+        let language_plan = self.plan.get_language(&source_file.language_name);
 
         for node in &mut nodes.iter() {
-            if node.mass >= nodes_threshold {
+            if node.mass >= language_plan.nodes_threshold {
                 self.insert(source_file.clone(), node.clone());
+
+                if let Some(near_miss_lines_threshold) = language_plan.near_miss_lines_threshold {
+                    // Only whole functions and classes are compared, because pairing
+                    // every nested block and statement grows quadratically
+                    if node.lines_count() >= near_miss_lines_threshold
+                        && definitions.contains(&(node.start_byte..node.end_byte))
+                    {
+                        self.near_miss_candidates.push(NodeWithFile {
+                            source_file: source_file.clone(),
+                            node: node.clone(),
+                        });
+                    }
+                }
             }
         }
     }
@@ -131,6 +165,17 @@ impl Executor {
         }
     }
 
+    fn detect_near_misses(&mut self) {
+        if self.near_miss_candidates.is_empty() {
+            return;
+        }
+
+        let plan = &self.plan;
+        self.near_misses = near_miss::detect(&self.near_miss_candidates, |language| {
+            plan.get_language(language).near_miss_similarity
+        });
+    }
+
     pub fn report(&self) -> Report {
         Report {
             issues: self.issues(),
@@ -246,9 +291,117 @@ impl Executor {
             }
         }
 
+        issues.extend(self.near_miss_issues());
+
         self.apply_issues_transfomers(issues)
     }
 
+    fn near_miss_issues(&self) -> Vec<Issue> {
+        let mut issues = vec![];
+
+        for near_miss in &self.near_misses {
+            let language_plan = self
+                .plan
+                .get_language(&near_miss.left.source_file.language_name);
+
+            let lines_threshold = match language_plan.near_miss_lines_threshold {
+                Some(lines_threshold) => lines_threshold as u32,
+                None => continue,
+            };
+
+            // Near-miss pairs have no shared structure, so both sides are
+            // grouped by a digest of their locations instead
+            let clone_hash = md5::compute(format!(
+                "{}:{}:{}:{}",
+                path_to_string(&near_miss.left.source_file.path),
+                near_miss.left.node.start_byte,
+                path_to_string(&near_miss.right.source_file.path),
+                near_miss.right.node.start_byte,
+            ));
+
+            let sides = [
+                (
+                    &near_miss.left,
+                    &near_miss.left_differences,
+                    &near_miss.right,
+                    &near_miss.right_differences,
+                ),
+                (
+                    &near_miss.right,
+                    &near_miss.right_differences,
+                    &near_miss.left,
+                    &near_miss.left_differences,
+                ),
+            ];
+
+            for (node, differences, other, other_differences) in sides {
+                let lines_count = node.node.lines_count() as u32;
+                let value_delta = lines_count.saturating_sub(lines_threshold);
+
+                let message = format!(
+                    "Found {} lines of near-miss code ({:.0}% similar) in 2 locations (mass = {})",
+                    lines_count,
+                    near_miss.similarity * 100.0,
+                    node.node.mass
+                );
+
+                let mut issue = Issue {
+                    tool: "qlty".to_string(),
+                    driver: "duplication".to_string(),
+                    rule_key: "near-miss-code".to_string(),
+                    category: Category::Duplication.into(),
+                    language: language_enum_from_name(node.source_file.language().name()).into(),
+                    location: Some(Location {
+                        path: path_to_string(&node.source_file.path),
+                        range: Some(Range {
+                            start_line: node.node.start_line as u32,
+                            end_line: node.node.end_line as u32,
+                            start_byte: Some(node.node.start_byte as u32),
+                            end_byte: Some(node.node.end_byte as u32),
+                            ..Default::default()
+                        }),
+                    }),
+                    snippet: truncate_snippet(&node.snippet()),
+                    snippet_with_context: truncate_snippet(&node.snippet_with_context()),
+                    level: Level::Medium.into(),
+                    message,
+                    value: lines_count,
+                    value_delta,
+                    effort_minutes: calculate_effort_minutes(
+                        value_delta,
+                        BASE_EFFORT_MINUTES,
+                        EFFORT_MINUTES_PER_VALUE_DELTA,
+                    ),
+                    other_locations: vec![Location {
+                        path: path_to_string(&other.source_file.path),
+                        range: Some(Range {
+                            start_line: other.node.start_line as u32,
+                            end_line: other.node.end_line as u32,
+                            ..Default::default()
+                        }),
+                    }],
+                    mode: language_plan.issue_mode as i32,
+                    ..Default::default()
+                };
+
+                issue.set_property_string("node_kind", node.node.kind.clone());
+                issue.set_property_string("structural_hash", format!("{:x}", clone_hash));
+                issue.set_property_number("mass", node.node.mass as f64);
+                issue.set_property_bool("identical", false);
+                issue.set_property_number("similarity", near_miss.similarity);
+                issue.set_property_string("differing_lines", format_line_ranges(differences));
+                issue.set_property_string(
+                    "other_differing_lines",
+                    format_line_ranges(other_differences),
+                );
+
+                issues.push(issue);
+            }
+        }
+
+        issues
+    }
+
     fn apply_issues_transfomers(&self, issues: Vec<Issue>) -> Vec<Issue> {
         let transformers = &self.plan.transformers;
         issues
@@ -268,6 +421,45 @@ impl Executor {
     }
 }
 
+fn format_line_ranges(ranges: &[RangeInclusive<usize>]) -> String {
+    ranges
+        .iter()
+        .map(|range| {
+            if range.start() == range.end() {
+                range.start().to_string()
+            } else {
+                format!("{}-{}", range.start(), range.end())
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn definition_ranges(
+    source_file: &File,
+    tree: &tree_sitter::Tree,
+) -> HashSet<std::ops::Range<usize>> {
+    let language = source_file.language();
+    let mut ranges = HashSet::new();
+
+    for (query, capture_name) in [
+        (language.function_declaration_query(), "definition.function"),
+        (language.class_query(), "definition.class"),
+    ] {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        cursor.set_match_limit(QUERY_MATCH_LIMIT as u32);
+
+        let all_matches = cursor.matches(query, tree.root_node(), source_file.contents.as_bytes());
+
+        for query_match in all_matches {
+            let capture = capture_by_name(query, capture_name, &query_match);
+            ranges.insert(capture.node.byte_range());
+        }
+    }
+
+    ranges
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -10,13 +10,20 @@ pub fn report_duplications(report: &Report, diff: bool) {
 
     for (_, issues) in report.duplication_issues_by_duplication() {
         let identical = issues[0].get_property_bool("identical");
+        let near_miss = issues[0].rule_key == "near-miss-code";
         let node_kind = issues[0].get_property_string("node_kind");
         let mass = issues[0].get_property_number("mass");
         i += 1;
 
-        let description = match identical {
-            false => "Similar",
-            true => "IDENTICAL",
+        let description = if near_miss {
+            format!(
+                "Near-miss ({:.0}% similar)",
+                issues[0].get_property_number("similarity") * 100.0
+            )
+        } else if identical {
+            "IDENTICAL".to_string()
+        } else {
+            "Similar".to_string()
         };
 
         let bonus = match identical {
@@ -45,9 +52,21 @@ pub fn report_duplications(report: &Report, diff: bool) {
             };
 
             let range = issue.range().unwrap();
+            let differing_lines = if near_miss {
+                issue.get_property_string("differing_lines")
+            } else {
+                String::from("")
+            };
+
+            let differences = if differing_lines.is_empty() {
+                String::from("")
+            } else {
+                format!(" (differs at {})", differing_lines)
+            };
+
             println!(
-                "        {}: {}:{}-{}",
-                letter, source_file_relative_path, range.start_line, range.end_line,
+                "        {}: {}:{}-{}{}",
+                letter, source_file_relative_path, range.start_line, range.end_line, differences,
             );
 
             letter = std::char::from_u32(letter as u32 + 1).unwrap_or('A');
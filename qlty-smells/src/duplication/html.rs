@@ -0,0 +1,246 @@
+use super::classes::{duplication_by_directory, CloneClass, CloneOccurrence};
+use anyhow::Result;
+use std::collections::HashSet;
+use std::io::Write;
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2em; color: #1f2328; }
+table { border-collapse: collapse; }
+.summary th, .summary td { padding: 4px 12px; border-bottom: 1px solid #d0d7de; text-align: right; }
+.summary th:first-child, .summary td:first-child { text-align: left; }
+.occurrences { display: flex; gap: 1em; overflow-x: auto; }
+.occurrence { flex: 1 0 32em; min-width: 0; border: 1px solid #d0d7de; }
+.occurrence h3 { margin: 0; padding: 4px 8px; font-size: 13px; background: #f6f8fa; border-bottom: 1px solid #d0d7de; }
+.source { width: 100%; font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: 12px; }
+.source td { padding: 0 8px; vertical-align: top; }
+.source .number { text-align: right; color: #656d76; white-space: nowrap; width: 1%; }
+.source pre { margin: 0; }
+.clone { background: #fff8c5; }
+.differs { background: #ffebe9; }
+"#;
+
+/// Renders clone classes as a self-contained static HTML page, with a table
+/// of duplicated lines per directory and the occurrences of each class side
+/// by side. Cloned lines are highlighted, and lines that differ between
+/// near-miss clones are marked.
+#[derive(Debug)]
+pub struct HtmlReport {
+    classes: Vec<CloneClass>,
+}
+
+impl HtmlReport {
+    pub fn new(classes: Vec<CloneClass>) -> Self {
+        Self { classes }
+    }
+
+    pub fn write_to(&self, writer: &mut dyn Write) -> Result<()> {
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, r#"<html lang="en">"#)?;
+        writeln!(writer, r#"<head><meta charset="utf-8">"#)?;
+        writeln!(writer, "<title>Duplication report</title>")?;
+        writeln!(writer, "<style>{}</style>", STYLE)?;
+        writeln!(writer, "</head>")?;
+        writeln!(writer, "<body>")?;
+        writeln!(writer, "<h1>Duplication report</h1>")?;
+
+        self.write_directories(writer)?;
+
+        for (index, class) in self.classes.iter().enumerate() {
+            write_class(writer, index, class)?;
+        }
+
+        writeln!(writer, "</body>")?;
+        writeln!(writer, "</html>")?;
+        Ok(())
+    }
+
+    fn write_directories(&self, writer: &mut dyn Write) -> Result<()> {
+        writeln!(
+            writer,
+            "<p>{} clone classes, {} duplicated lines.</p>",
+            self.classes.len(),
+            self.classes
+                .iter()
+                .map(CloneClass::duplicated_lines)
+                .sum::<usize>()
+        )?;
+
+        writeln!(writer, r#"<table class="summary">"#)?;
+        writeln!(
+            writer,
+            "<tr><th>Directory</th><th>Files</th><th>Duplicated lines</th></tr>"
+        )?;
+
+        for directory in duplication_by_directory(&self.classes) {
+            writeln!(
+                writer,
+                "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&directory.directory),
+                directory.files,
+                directory.duplicated_lines
+            )?;
+        }
+
+        writeln!(writer, "</table>")?;
+        Ok(())
+    }
+}
+
+fn write_class(writer: &mut dyn Write, index: usize, class: &CloneClass) -> Result<()> {
+    let description = match class.similarity {
+        Some(similarity) => format!("Near-miss code ({:.0}% similar)", similarity * 100.0),
+        None if class.rule_key == "identical-code" => "Identical code".to_string(),
+        None => "Similar code".to_string(),
+    };
+
+    writeln!(
+        writer,
+        r#"<section id="clone-{}"><h2>{}. {} in {} locations</h2>"#,
+        index + 1,
+        index + 1,
+        description,
+        class.occurrences.len()
+    )?;
+    writeln!(
+        writer,
+        "<p>{} :{} (mass = {}), {} duplicated lines</p>",
+        escape(&class.languages().join(", ")),
+        escape(&class.node_kind),
+        class.mass,
+        class.duplicated_lines()
+    )?;
+
+    writeln!(writer, r#"<div class="occurrences">"#)?;
+
+    for occurrence in &class.occurrences {
+        write_occurrence(writer, occurrence)?;
+    }
+
+    writeln!(writer, "</div></section>")?;
+    Ok(())
+}
+
+fn write_occurrence(writer: &mut dyn Write, occurrence: &CloneOccurrence) -> Result<()> {
+    writeln!(
+        writer,
+        r#"<div class="occurrence"><h3>{}:{}-{}</h3>"#,
+        escape(&occurrence.path),
+        occurrence.start_line,
+        occurrence.end_line
+    )?;
+
+    let differing_lines = parse_line_ranges(&occurrence.differing_lines);
+
+    writeln!(writer, r#"<table class="source">"#)?;
+
+    for (index, code) in occurrence.snippet_with_context.lines().enumerate() {
+        let line = occurrence.context_start_line + index;
+
+        let class = if differing_lines.contains(&line) {
+            "differs"
+        } else if (occurrence.start_line..=occurrence.end_line).contains(&line) {
+            "clone"
+        } else {
+            ""
+        };
+
+        writeln!(
+            writer,
+            r#"<tr class="{}"><td class="number">{}</td><td><pre>{}</pre></td></tr>"#,
+            class,
+            line,
+            escape(code)
+        )?;
+    }
+
+    writeln!(writer, "</table></div>")?;
+    Ok(())
+}
+
+/// Parses line ranges formatted like "3, 5-7"
+fn parse_line_ranges(ranges: &str) -> HashSet<usize> {
+    let mut lines = HashSet::new();
+
+    for range in ranges.split(',').map(str::trim) {
+        let (start, end) = range.split_once('-').unwrap_or((range, range));
+
+        if let (Ok(start), Ok(end)) = (start.parse::<usize>(), end.parse::<usize>()) {
+            lines.extend(start..=end);
+        }
+    }
+
+    lines
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn occurrence(path: &str, start_line: usize, differing_lines: &str) -> CloneOccurrence {
+        CloneOccurrence {
+            path: path.to_string(),
+            language: "ruby".to_string(),
+            start_line,
+            end_line: start_line + 1,
+            differing_lines: differing_lines.to_string(),
+            snippet_with_context: "# before\ndef a\n  x < y\nend".to_string(),
+            context_start_line: start_line - 1,
+        }
+    }
+
+    #[test]
+    fn write() {
+        let report = HtmlReport::new(vec![CloneClass {
+            rule_key: "near-miss-code".to_string(),
+            node_kind: "method".to_string(),
+            mass: 30,
+            similarity: Some(0.85),
+            occurrences: vec![
+                occurrence("lib/a.rb", 2, "3"),
+                occurrence("lib/<b>.rb", 12, ""),
+            ],
+        }]);
+
+        let mut output = vec![];
+        report.write_to(&mut output).unwrap();
+        let html = String::from_utf8(output).unwrap();
+
+        assert!(html.contains("<p>1 clone classes, 4 duplicated lines.</p>"));
+        assert!(html.contains("<tr><td>lib</td><td>2</td><td>4</td></tr>"));
+        assert!(html.contains(
+            r#"<section id="clone-1"><h2>1. Near-miss code (85% similar) in 2 locations</h2>"#
+        ));
+        assert!(html.contains(r#"<div class="occurrence"><h3>lib/&lt;b&gt;.rb:12-13</h3>"#));
+        assert!(html.contains(
+            r#"<tr class=""><td class="number">1</td><td><pre># before</pre></td></tr>"#
+        ));
+        assert!(html.contains(
+            r#"<tr class="differs"><td class="number">3</td><td><pre>  x &lt; y</pre></td></tr>"#
+        ));
+        assert!(html.contains(
+            r#"<tr class="clone"><td class="number">13</td><td><pre>  x &lt; y</pre></td></tr>"#
+        ));
+    }
+
+    #[test]
+    fn line_ranges() {
+        assert_eq!(HashSet::from([3, 5, 6, 7]), parse_line_ranges("3, 5-7"));
+        assert!(parse_line_ranges("").is_empty());
+    }
+}
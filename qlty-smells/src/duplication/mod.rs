@@ -1,15 +1,23 @@
+mod classes;
 mod code;
 mod executor;
 mod format;
+mod html;
+mod near_miss;
 mod plan;
 mod planner;
 mod settings;
 mod transformers;
 mod visitor;
 
+pub use classes::{
+    clone_classes, duplication_by_directory, CloneClass, CloneOccurrence, DirectoryDuplication,
+};
 pub use code::{Node, NodeWithFile};
 pub use executor::Executor;
 pub use format::report_duplications;
+pub use html::HtmlReport;
+pub use near_miss::NearMiss;
 pub use plan::{LanguagePlan, Plan};
 pub use planner::Planner;
 pub use settings::Settings;
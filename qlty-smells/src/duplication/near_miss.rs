@@ -0,0 +1,524 @@
+use super::{Node, NodeWithFile};
+use rayon::prelude::*;
+use std::collections::{BTreeSet, HashMap};
+use std::ops::RangeInclusive;
+
+// Aligning two candidates is quadratic in their number of tokens, so very
+// large nodes (which are usually whole files or classes) are not compared
+const MAX_TOKENS: usize = 2_000;
+
+/// A pair of nodes whose syntax mostly matches, such as a copy with a
+/// statement inserted or removed.
+#[derive(Debug, Clone)]
+pub struct NearMiss {
+    pub left: NodeWithFile,
+    pub right: NodeWithFile,
+
+    /// Share of the syntax tokens of both nodes which align with each other,
+    /// from 0.0 to 1.0
+    pub similarity: f64,
+
+    /// Lines of the left node which differ from the right node
+    pub left_differences: Vec<RangeInclusive<usize>>,
+
+    /// Lines of the right node which differ from the left node
+    pub right_differences: Vec<RangeInclusive<usize>>,
+}
+
+impl NearMiss {
+    fn covers(&self, other: &NearMiss) -> bool {
+        (contains(&self.left, &other.left) && contains(&self.right, &other.right))
+            || (contains(&self.left, &other.right) && contains(&self.right, &other.left))
+    }
+
+    fn mass(&self) -> usize {
+        self.left.node.mass + self.right.node.mass
+    }
+}
+
+struct Candidate<'a> {
+    node: &'a NodeWithFile,
+    tokens: Vec<&'a Node>,
+    histogram: HashMap<&'a str, usize>,
+}
+
+impl<'a> Candidate<'a> {
+    fn new(node: &'a NodeWithFile) -> Self {
+        // Pre-order traversal, so that tokens follow the order of the source
+        let mut tokens = vec![];
+        let mut stack = vec![node.node.as_ref()];
+
+        while let Some(current) = stack.pop() {
+            tokens.push(current);
+            stack.extend(current.children.iter().rev().map(|child| child.as_ref()));
+        }
+
+        let mut histogram = HashMap::new();
+
+        for token in &tokens {
+            *histogram.entry(token.kind.as_str()).or_default() += 1;
+        }
+
+        Self {
+            node,
+            tokens,
+            histogram,
+        }
+    }
+
+    fn text(&self, token: &Node) -> &'a str {
+        self.node
+            .source_file
+            .contents
+            .get(token.start_byte..token.end_byte)
+            .unwrap_or_default()
+    }
+}
+
+/// Finds pairs of candidate nodes of the same language and kind whose token
+/// sequences are at least `similarity_for(language)` similar. Pairs nested
+/// within a larger reported pair are left out.
+pub fn detect<F>(candidates: &[NodeWithFile], similarity_for: F) -> Vec<NearMiss>
+where
+    F: Fn(&str) -> f64 + Sync,
+{
+    let mut groups: HashMap<(&str, &str), Vec<Candidate>> = HashMap::new();
+
+    for node in candidates {
+        let candidate = Candidate::new(node);
+
+        if candidate.tokens.len() <= MAX_TOKENS {
+            groups
+                .entry((
+                    node.source_file.language_name.as_str(),
+                    node.node.kind.as_str(),
+                ))
+                .or_default()
+                .push(candidate);
+        }
+    }
+
+    let mut near_misses = groups
+        .into_iter()
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .flat_map(|((language, _), group)| compare_group(group, similarity_for(language)))
+        .collect::<Vec<_>>();
+
+    // Largest pairs first, so that pairs nested within them can be skipped
+    near_misses.sort_by(|a, b| {
+        b.mass().cmp(&a.mass()).then_with(|| {
+            (
+                &a.left.source_file.path,
+                a.left.node.start_byte,
+                &a.right.source_file.path,
+                a.right.node.start_byte,
+            )
+                .cmp(&(
+                    &b.left.source_file.path,
+                    b.left.node.start_byte,
+                    &b.right.source_file.path,
+                    b.right.node.start_byte,
+                ))
+        })
+    });
+
+    let mut results: Vec<NearMiss> = vec![];
+
+    for near_miss in near_misses {
+        if !results.iter().any(|result| result.covers(&near_miss)) {
+            results.push(near_miss);
+        }
+    }
+
+    results
+}
+
+fn compare_group(mut group: Vec<Candidate>, similarity: f64) -> Vec<NearMiss> {
+    group.sort_by_key(|candidate| {
+        (
+            candidate.tokens.len(),
+            candidate.node.source_file.path.clone(),
+            candidate.node.node.start_byte,
+        )
+    });
+
+    let mut near_misses = vec![];
+
+    for (i, left) in group.iter().enumerate() {
+        for right in &group[i + 1..] {
+            // Candidates are sorted by size, so once the smaller one can no
+            // longer reach the threshold no later candidate can either
+            if similarity_bound(left.tokens.len(), left.tokens.len(), right.tokens.len())
+                < similarity
+            {
+                break;
+            }
+
+            if let Some(near_miss) = compare(left, right, similarity) {
+                near_misses.push(near_miss);
+            }
+        }
+    }
+
+    near_misses
+}
+
+fn compare(left: &Candidate, right: &Candidate, similarity: f64) -> Option<NearMiss> {
+    // Structurally equal nodes are reported as identical or similar code
+    if left.node.node.structural_hash == right.node.node.structural_hash
+        || overlaps(left.node, right.node)
+    {
+        return None;
+    }
+
+    // Tokens can only align with tokens of the same kind, which bounds the
+    // similarity cheaply before aligning the sequences
+    let shared_kinds = left
+        .histogram
+        .iter()
+        .map(|(kind, count)| *count.min(right.histogram.get(kind).unwrap_or(&0)))
+        .sum();
+
+    if similarity_bound(shared_kinds, left.tokens.len(), right.tokens.len()) < similarity {
+        return None;
+    }
+
+    let matches = align(left, right);
+    let actual = similarity_bound(matches.len(), left.tokens.len(), right.tokens.len());
+
+    if actual < similarity {
+        return None;
+    }
+
+    let (left_differences, right_differences) = differences(left, right, &matches);
+
+    Some(NearMiss {
+        left: left.node.clone(),
+        right: right.node.clone(),
+        similarity: actual,
+        left_differences,
+        right_differences,
+    })
+}
+
+fn similarity_bound(matches: usize, left_tokens: usize, right_tokens: usize) -> f64 {
+    2.0 * matches as f64 / (left_tokens + right_tokens) as f64
+}
+
+/// Aligns two token sequences, returning the indexes of the aligned tokens.
+/// Only tokens of the same kind align, and those which also match exactly
+/// (leaves with the same source, or subtrees with the same structure) are
+/// preferred, so that an inserted statement does not shift the alignment.
+fn align(left: &Candidate, right: &Candidate) -> Vec<(usize, usize)> {
+    let (left_tokens, right_tokens) = (&left.tokens, &right.tokens);
+    let width = right_tokens.len() + 1;
+
+    let weight = |i: usize, j: usize| -> u16 {
+        let (left_token, right_token) = (left_tokens[i], right_tokens[j]);
+
+        if left_token.kind != right_token.kind {
+            0
+        } else if left_token.children.is_empty() {
+            if left.text(left_token) == right.text(right_token) {
+                2
+            } else {
+                1
+            }
+        } else if left_token.structural_hash == right_token.structural_hash {
+            2
+        } else {
+            1
+        }
+    };
+
+    // Best total weight of the suffixes starting at each pair of positions.
+    // Both sides are at most MAX_TOKENS long, so this fits in a u16.
+    let mut scores = vec![0u16; (left_tokens.len() + 1) * width];
+
+    for i in (0..left_tokens.len()).rev() {
+        for j in (0..right_tokens.len()).rev() {
+            let skip = scores[(i + 1) * width + j].max(scores[i * width + j + 1]);
+
+            scores[i * width + j] = match weight(i, j) {
+                0 => skip,
+                matched => skip.max(scores[(i + 1) * width + j + 1] + matched),
+            };
+        }
+    }
+
+    let mut matches = vec![];
+    let (mut i, mut j) = (0, 0);
+
+    while i < left_tokens.len() && j < right_tokens.len() {
+        let matched = weight(i, j);
+
+        if matched > 0 && scores[i * width + j] == scores[(i + 1) * width + j + 1] + matched {
+            matches.push((i, j));
+            i += 1;
+            j += 1;
+        } else if scores[(i + 1) * width + j] >= scores[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    matches
+}
+
+/// Lines of each side with tokens that have no counterpart on the other
+/// side, or leaves (such as renamed identifiers) whose source differs.
+fn differences(
+    left: &Candidate,
+    right: &Candidate,
+    matches: &[(usize, usize)],
+) -> (Vec<RangeInclusive<usize>>, Vec<RangeInclusive<usize>>) {
+    let mut left_lines = BTreeSet::new();
+    let mut right_lines = BTreeSet::new();
+    let mut left_matched = vec![false; left.tokens.len()];
+    let mut right_matched = vec![false; right.tokens.len()];
+
+    for &(i, j) in matches {
+        left_matched[i] = true;
+        right_matched[j] = true;
+
+        let (left_token, right_token) = (left.tokens[i], right.tokens[j]);
+
+        if left_token.children.is_empty() && left.text(left_token) != right.text(right_token) {
+            left_lines.extend(left_token.start_line..=left_token.end_line);
+            right_lines.extend(right_token.start_line..=right_token.end_line);
+        }
+    }
+
+    for (token, matched) in left.tokens.iter().zip(left_matched) {
+        if !matched {
+            left_lines.insert(token.start_line);
+        }
+    }
+
+    for (token, matched) in right.tokens.iter().zip(right_matched) {
+        if !matched {
+            right_lines.insert(token.start_line);
+        }
+    }
+
+    (line_ranges(left_lines), line_ranges(right_lines))
+}
+
+fn line_ranges(lines: BTreeSet<usize>) -> Vec<RangeInclusive<usize>> {
+    let mut ranges: Vec<RangeInclusive<usize>> = vec![];
+
+    for line in lines {
+        match ranges.last_mut() {
+            Some(range) if *range.end() + 1 == line => *range = *range.start()..=line,
+            _ => ranges.push(line..=line),
+        }
+    }
+
+    ranges
+}
+
+fn contains(outer: &NodeWithFile, inner: &NodeWithFile) -> bool {
+    outer.source_file.path == inner.source_file.path
+        && outer.node.start_byte <= inner.node.start_byte
+        && inner.node.end_byte <= outer.node.end_byte
+}
+
+fn overlaps(a: &NodeWithFile, b: &NodeWithFile) -> bool {
+    a.source_file.path == b.source_file.path
+        && a.node.start_byte < b.node.end_byte
+        && b.node.start_byte < a.node.end_byte
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::duplication::{Executor, Planner, Settings};
+    use qlty_analysis::code::File;
+    use qlty_config::config::smells::{
+        Duplication, IdenticalCode, NearMissCode, SimilarCode, Smells,
+    };
+    use qlty_config::QltyConfig;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    const ORIGINAL: &str = r#"
+function total(items) {
+    let sum = 0;
+    for (const item of items) {
+        if (item.price > 0) {
+            sum += item.price * item.quantity;
+        }
+    }
+    return sum;
+}
+"#;
+
+    const INSERTED_STATEMENT: &str = r#"
+function total(items) {
+    let sum = 0;
+    for (const item of items) {
+        if (item.price > 0) {
+            console.log(item);
+            sum += item.price * item.quantity;
+        }
+    }
+    return sum;
+}
+"#;
+
+    const ADDED_CALL: &str = r#"
+function total(items) {
+    let sum = 0;
+    for (const item of items) {
+        if (item.price > 0) {
+            sum += item.price * item.quantity;
+        }
+    }
+    return round(sum);
+}
+"#;
+
+    const UNRELATED: &str = r#"
+class Widget {
+    constructor(name) {
+        this.name = name;
+    }
+}
+"#;
+
+    const LOOP_IN_SUMMARY: &str = r#"
+function summary(orders, out) {
+    out.open("summary");
+    let sum = 0;
+    for (const item of items) {
+        if (item.price > 0) {
+            sum += item.price * item.quantity;
+            count += 1;
+        }
+    }
+    return out.close(sum);
+}
+"#;
+
+    const LOOP_IN_RENDER: &str = r#"
+function render(rows, out, theme) {
+    const style = theme.current();
+    style.apply(out);
+    out.header(rows.length, style.width);
+    if (rows.length === 0) {
+        out.empty(style.placeholder);
+        return out.flush();
+    }
+    for (const item of items) {
+        if (item.price > 0) {
+            console.log(item);
+            sum += item.price * item.quantity;
+            count += 1;
+        }
+    }
+    out.footer(style.border, rows);
+    theme.reset(style);
+    return out.flush();
+}
+"#;
+
+    fn near_misses(sources: &[&str]) -> Vec<NearMiss> {
+        let files = sources
+            .iter()
+            .enumerate()
+            .map(|(i, source)| {
+                Arc::new(File {
+                    language_name: "javascript".to_string(),
+                    path: PathBuf::from(format!("file{}.js", i)),
+                    contents: source.to_string(),
+                    digest: md5::compute(source),
+                })
+            })
+            .collect();
+
+        let mut config = QltyConfig::default();
+        config
+            .language
+            .insert("javascript".to_string(), Default::default());
+        config.smells = Some(Smells {
+            duplication: Some(Duplication {
+                nodes_threshold: Some(10),
+                ..Default::default()
+            }),
+            identical_code: Some(IdenticalCode {
+                enabled: true,
+                threshold: Some(5),
+            }),
+            similar_code: Some(SimilarCode {
+                enabled: true,
+                threshold: Some(5),
+            }),
+            near_miss_code: Some(NearMissCode {
+                enabled: true,
+                threshold: Some(6),
+                similarity: Some(0.8),
+            }),
+            ..Default::default()
+        });
+
+        let settings = Settings {
+            include_tests: true,
+            ..Default::default()
+        };
+
+        let plan = Planner::new(&config, &settings, files)
+            .unwrap()
+            .compute()
+            .unwrap();
+
+        let mut executor = Executor::new(&plan);
+        executor.execute();
+        executor.near_misses
+    }
+
+    #[test]
+    fn inserted_statement() {
+        let near_misses = near_misses(&[ORIGINAL, INSERTED_STATEMENT]);
+
+        assert_eq!(near_misses.len(), 1);
+        assert!(near_misses[0].similarity >= 0.8 && near_misses[0].similarity < 1.0);
+        assert_eq!(near_misses[0].left_differences, vec![]);
+        assert_eq!(near_misses[0].right_differences, vec![6..=6]);
+    }
+
+    #[test]
+    fn added_call() {
+        let near_misses = near_misses(&[ORIGINAL, ADDED_CALL]);
+
+        assert_eq!(near_misses.len(), 1);
+        assert_eq!(near_misses[0].left_differences, vec![]);
+        assert_eq!(near_misses[0].right_differences, vec![9..=9]);
+    }
+
+    #[test]
+    fn unrelated() {
+        assert!(near_misses(&[ORIGINAL, UNRELATED]).is_empty());
+    }
+
+    #[test]
+    fn nested_blocks_are_not_compared() {
+        // Only the enclosing functions are candidates, and they are not similar
+        assert!(near_misses(&[LOOP_IN_SUMMARY, LOOP_IN_RENDER]).is_empty());
+    }
+
+    #[test]
+    fn identical() {
+        // Exact copies are reported as identical code instead
+        assert!(near_misses(&[ORIGINAL, ORIGINAL]).is_empty());
+    }
+
+    #[test]
+    fn line_ranges_merge_consecutive_lines() {
+        assert_eq!(
+            line_ranges(BTreeSet::from([1, 2, 3, 7, 9, 10])),
+            vec![1..=3, 7..=7, 9..=10]
+        );
+    }
+}
@@ -25,5 +25,7 @@ pub struct LanguagePlan {
     pub nodes_threshold: usize,
     pub identical_lines_threshold: Option<usize>,
     pub similar_lines_threshold: Option<usize>,
+    pub near_miss_lines_threshold: Option<usize>,
+    pub near_miss_similarity: f64,
     pub issue_mode: IssueMode,
 }
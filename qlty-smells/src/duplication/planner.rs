@@ -4,7 +4,7 @@ use anyhow::Result;
 use qlty_analysis::code::File;
 use qlty_analysis::utils::fs::path_to_string;
 use qlty_config::config::issue_transformer::IssueTransformer;
-use qlty_config::config::smells::{Duplication, IdenticalCode, SimilarCode};
+use qlty_config::config::smells::{Duplication, IdenticalCode, NearMissCode, SimilarCode};
 use qlty_config::config::Exclude;
 use qlty_config::{
     config::{IssueMode, Language},
@@ -12,6 +12,9 @@ use qlty_config::{
 };
 use std::{collections::HashMap, sync::Arc};
 
+const DEFAULT_NEAR_MISS_LINES_THRESHOLD: usize = 15;
+const DEFAULT_NEAR_MISS_SIMILARITY: f64 = 0.8;
+
 #[derive(Debug, Clone)]
 pub struct Planner {
     config: QltyConfig,
@@ -127,6 +130,34 @@ impl Planner {
         Some(SimilarCode::default().threshold.unwrap())
     }
 
+    fn extract_near_miss_code(&self, language: &Language) -> Option<NearMissCode> {
+        let language_near_miss_code = language
+            .smells
+            .as_ref()
+            .and_then(|smells| smells.near_miss_code.clone());
+        let global_near_miss_code = self
+            .config
+            .smells
+            .as_ref()
+            .and_then(|smells| smells.near_miss_code.clone());
+
+        // Near-miss detection is opt-in, so it only runs when enabled
+        let near_miss_code = language_near_miss_code.or_else(|| global_near_miss_code.clone())?;
+
+        if !near_miss_code.enabled {
+            return None;
+        }
+
+        // Settings the language leaves unset fall back to the global ones
+        let global = global_near_miss_code.unwrap_or_default();
+
+        Some(NearMissCode {
+            enabled: true,
+            threshold: near_miss_code.threshold.or(global.threshold),
+            similarity: near_miss_code.similarity.or(global.similarity),
+        })
+    }
+
     pub fn compute(&self) -> Result<Plan> {
         let mut languages = HashMap::new();
 
@@ -137,12 +168,22 @@ impl Planner {
                 filter_patterns.extend(language_settings.test_syntax_patterns.clone());
             }
 
+            let near_miss_code = self.extract_near_miss_code(language_settings);
+
             let language_plan = LanguagePlan {
                 filters: filter_patterns,
                 nodes_threshold: self.extract_nodes_threshold(language_settings),
                 similar_lines_threshold: self.extract_similar_lines_threshold(language_settings),
                 identical_lines_threshold: self
                     .extract_identical_lines_threshold(language_settings),
+                near_miss_lines_threshold: near_miss_code.as_ref().map(|near_miss_code| {
+                    near_miss_code
+                        .threshold
+                        .unwrap_or(DEFAULT_NEAR_MISS_LINES_THRESHOLD)
+                }),
+                near_miss_similarity: near_miss_code
+                    .and_then(|near_miss_code| near_miss_code.similarity)
+                    .unwrap_or(DEFAULT_NEAR_MISS_SIMILARITY),
 
                 issue_mode: IssueMode::extract_issue_mode_from_smells(
                     language_settings,
@@ -150,10 +191,11 @@ impl Planner {
                 ),
             };
 
-            // Skip language if issue mode is disabled or both thresholds are None
+            // Skip language if issue mode is disabled or all thresholds are None
             if language_plan.issue_mode == IssueMode::Disabled
                 || (language_plan.similar_lines_threshold.is_none()
-                    && language_plan.identical_lines_threshold.is_none())
+                    && language_plan.identical_lines_threshold.is_none()
+                    && language_plan.near_miss_lines_threshold.is_none())
             {
                 continue;
             }
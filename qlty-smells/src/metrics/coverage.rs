@@ -0,0 +1,77 @@
+use serde::Serialize;
+use tree_sitter::Node;
+
+/// Executable lines of a node that were or were not executed according to
+/// the line hits of a coverage report, where negative hits mark lines that
+/// are not executable.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Coverage {
+    pub covered: usize,
+    pub uncovered: usize,
+}
+
+impl Coverage {
+    pub fn for_node(hits: &[i64], node: &Node) -> Self {
+        let range = node.range();
+        Self::for_lines(hits, range.start_point.row + 1, range.end_point.row + 1)
+    }
+
+    pub fn for_file(hits: &[i64]) -> Self {
+        Self::for_lines(hits, 1, hits.len())
+    }
+
+    fn for_lines(hits: &[i64], first_line: usize, last_line: usize) -> Self {
+        let mut coverage = Self::default();
+
+        for hit in hits
+            .iter()
+            .skip(first_line.saturating_sub(1))
+            .take((last_line + 1).saturating_sub(first_line))
+        {
+            match hit {
+                0 => coverage.uncovered += 1,
+                hit if *hit > 0 => coverage.covered += 1,
+                _ => {}
+            }
+        }
+
+        coverage
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn for_lines() {
+        let hits = [-1, 2, 0, -1, 1, 0];
+
+        assert_eq!(
+            Coverage::for_lines(&hits, 2, 5),
+            Coverage {
+                covered: 2,
+                uncovered: 1
+            }
+        );
+        assert_eq!(
+            Coverage::for_lines(&hits, 6, 10),
+            Coverage {
+                covered: 0,
+                uncovered: 1
+            }
+        );
+        assert_eq!(Coverage::for_lines(&hits, 8, 10), Coverage::default());
+    }
+
+    #[test]
+    fn for_file() {
+        assert_eq!(
+            Coverage::for_file(&[1, -1, 0, 3]),
+            Coverage {
+                covered: 2,
+                uncovered: 1
+            }
+        );
+    }
+}
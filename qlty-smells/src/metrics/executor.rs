@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use super::{metrics, MetricsMode, Plan, Results};
+use super::{metrics, Coverage, MetricsMode, Plan, Results};
 use qlty_analysis::{
     code::{capture_by_name, capture_by_name_option, capture_source, node_source, File},
     utils::fs::path_to_string,
@@ -60,6 +60,9 @@ impl Executor {
         let test_filter = self.plan.node_filter_for(source_file, &tree);
         let lines = super::Lines::for_node(source_file, &tree.root_node(), &test_filter);
         let name = path_to_string(source_file.path.file_name().unwrap_or_default());
+        let coverage = self.plan.coverage_for(source_file).map(Coverage::for_file);
+        let cyclomatic = metrics::cyclomatic(source_file, &tree.root_node(), &test_filter);
+        let halstead = metrics::halstead(source_file, &tree.root_node(), &test_filter);
 
         Stats {
             kind: ComponentType::File.into(),
@@ -73,9 +76,7 @@ impl Executor {
                 metrics::functions(source_file, &tree.root_node(), &test_filter) as u32,
             ),
             fields: Some(metrics::fields(source_file, &tree.root_node(), &test_filter) as u32),
-            cyclomatic: Some(
-                metrics::cyclomatic(source_file, &tree.root_node(), &test_filter) as u32,
-            ),
+            cyclomatic: Some(cyclomatic as u32),
             complexity: Some(
                 metrics::complexity(source_file, &tree.root_node(), &test_filter) as u32,
             ),
@@ -84,6 +85,16 @@ impl Executor {
             code_lines: Some(lines.code_lines as u32),
             comment_lines: Some(lines.comment_lines as u32),
             blank_lines: Some(lines.blank_lines as u32),
+            covered_lines: coverage.map(|coverage| coverage.covered as u32),
+            uncovered_lines: coverage.map(|coverage| coverage.uncovered as u32),
+            halstead_volume: Some(halstead.volume()),
+            halstead_difficulty: Some(halstead.difficulty()),
+            halstead_effort: Some(halstead.effort()),
+            maintainability_index: Some(metrics::maintainability_index(
+                halstead.volume(),
+                cyclomatic,
+                lines.code_lines,
+            )),
             ..Default::default()
         }
     }
@@ -180,6 +191,12 @@ impl Executor {
 
             let test_filter = self.plan.node_filter_for(source_file, &tree);
             let lines = super::Lines::for_node(source_file, &node, &test_filter);
+            let coverage = self
+                .plan
+                .coverage_for(source_file)
+                .map(|hits| Coverage::for_node(hits, &node));
+            let cyclomatic = metrics::cyclomatic(source_file, &node, &test_filter);
+            let halstead = metrics::halstead(source_file, &node, &test_filter);
 
             stats.push(Stats {
                 kind: ComponentType::Function.into(),
@@ -188,12 +205,22 @@ impl Executor {
                 path: path_to_string(&source_file.path),
                 language: language_enum_from_name(source_file.language().name()).into(),
                 fields: Some(metrics::fields(source_file, &node, &test_filter) as u32),
-                cyclomatic: Some(metrics::cyclomatic(source_file, &node, &test_filter) as u32),
+                cyclomatic: Some(cyclomatic as u32),
                 complexity: Some(metrics::complexity(source_file, &node, &test_filter) as u32),
                 lines: Some(lines.total as u32),
                 code_lines: Some(lines.code_lines as u32),
                 comment_lines: Some(lines.comment_lines as u32),
                 blank_lines: Some(lines.blank_lines as u32),
+                covered_lines: coverage.map(|coverage| coverage.covered as u32),
+                uncovered_lines: coverage.map(|coverage| coverage.uncovered as u32),
+                halstead_volume: Some(halstead.volume()),
+                halstead_difficulty: Some(halstead.difficulty()),
+                halstead_effort: Some(halstead.effort()),
+                maintainability_index: Some(metrics::maintainability_index(
+                    halstead.volume(),
+                    cyclomatic,
+                    lines.code_lines,
+                )),
                 ..Default::default()
             });
         }
@@ -3,11 +3,15 @@ mod cognitive;
 mod cyclomatic;
 mod fields;
 mod functions;
+mod halstead;
 mod lcom;
+mod maintainability;
 
 pub use classes::count as classes;
 pub use cognitive::count as complexity;
 pub use cyclomatic::count as cyclomatic;
 pub use fields::count as fields;
 pub use functions::count as functions;
+pub use halstead::{count as halstead, Halstead};
 pub use lcom::count as lcom4;
+pub use maintainability::index as maintainability_index;
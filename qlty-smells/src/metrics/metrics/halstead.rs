@@ -0,0 +1,304 @@
+use qlty_analysis::code::{node_source, File, NodeFilter};
+use std::collections::HashSet;
+use tree_sitter::Node;
+
+pub fn count<'a>(source_file: &'a File, node: &Node<'a>, filter: &NodeFilter) -> Halstead {
+    let mut processor = HalsteadProcessor::new(source_file, filter);
+    processor.process(node);
+    processor.halstead()
+}
+
+/// Operator and operand counts of a node, from which the Halstead measures
+/// are derived.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Halstead {
+    /// Number of distinct operators (n1)
+    pub distinct_operators: usize,
+    /// Number of distinct operands (n2)
+    pub distinct_operands: usize,
+    /// Total number of operators (N1)
+    pub total_operators: usize,
+    /// Total number of operands (N2)
+    pub total_operands: usize,
+}
+
+impl Halstead {
+    pub fn vocabulary(&self) -> usize {
+        self.distinct_operators + self.distinct_operands
+    }
+
+    pub fn length(&self) -> usize {
+        self.total_operators + self.total_operands
+    }
+
+    /// `N * log2(n)`
+    pub fn volume(&self) -> f64 {
+        if self.vocabulary() == 0 {
+            return 0.0;
+        }
+
+        self.length() as f64 * (self.vocabulary() as f64).log2()
+    }
+
+    /// `(n1 / 2) * (N2 / n2)`
+    pub fn difficulty(&self) -> f64 {
+        if self.distinct_operands == 0 {
+            return 0.0;
+        }
+
+        (self.distinct_operators as f64 / 2.0)
+            * (self.total_operands as f64 / self.distinct_operands as f64)
+    }
+
+    /// `D * V`
+    pub fn effort(&self) -> f64 {
+        self.difficulty() * self.volume()
+    }
+}
+
+struct HalsteadProcessor<'a> {
+    source_file: &'a File,
+    filter: &'a NodeFilter,
+    comment_kinds: Vec<&'a str>,
+    string_kinds: Vec<&'a str>,
+    operators: HashSet<&'static str>,
+    operands: HashSet<String>,
+    total_operators: usize,
+    total_operands: usize,
+}
+
+impl<'a> HalsteadProcessor<'a> {
+    fn new(source_file: &'a File, filter: &'a NodeFilter) -> Self {
+        let language = source_file.language();
+
+        Self {
+            source_file,
+            filter,
+            comment_kinds: language.comment_nodes(),
+            string_kinds: language.string_nodes(),
+            operators: HashSet::new(),
+            operands: HashSet::new(),
+            total_operators: 0,
+            total_operands: 0,
+        }
+    }
+
+    fn halstead(&self) -> Halstead {
+        Halstead {
+            distinct_operators: self.operators.len(),
+            distinct_operands: self.operands.len(),
+            total_operators: self.total_operators,
+            total_operands: self.total_operands,
+        }
+    }
+
+    // Walks the tree with an explicit stack, since only tokens matter and
+    // deeply nested trees would otherwise overflow the stack
+    fn process(&mut self, node: &Node<'a>) {
+        let mut stack = vec![*node];
+
+        while let Some(node) = stack.pop() {
+            let kind = node.kind();
+
+            if node.is_missing() || self.filter.exclude(&node) || self.comment_kinds.contains(&kind)
+            {
+                continue;
+            }
+
+            // Strings are a single operand, regardless of how the grammar
+            // splits their contents
+            if self.string_kinds.contains(&kind) {
+                self.record_operand(&node);
+                continue;
+            }
+
+            if node.child_count() == 0 {
+                self.process_token(&node);
+                continue;
+            }
+
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+    }
+
+    // Named tokens (identifiers and literals) are operands and anonymous
+    // tokens (keywords and punctuation) are operators, which classifies
+    // every grammar the same way
+    fn process_token(&mut self, node: &Node) {
+        if node.is_named() {
+            self.record_operand(node);
+        } else {
+            self.total_operators += 1;
+            self.operators.insert(node.kind());
+        }
+    }
+
+    fn record_operand(&mut self, node: &Node) {
+        self.total_operands += 1;
+        self.operands.insert(node_source(node, self.source_file));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn measures() {
+        let halstead = Halstead {
+            distinct_operators: 3,
+            distinct_operands: 2,
+            total_operators: 3,
+            total_operands: 3,
+        };
+
+        assert_eq!(5, halstead.vocabulary());
+        assert_eq!(6, halstead.length());
+        assert!((halstead.volume() - 6.0 * 5f64.log2()).abs() < 1e-9);
+        assert!((halstead.difficulty() - 2.25).abs() < 1e-9);
+        assert!((halstead.effort() - 2.25 * 6.0 * 5f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn empty() {
+        let halstead = Halstead::default();
+
+        assert_eq!(0.0, halstead.volume());
+        assert_eq!(0.0, halstead.difficulty());
+        assert_eq!(0.0, halstead.effort());
+    }
+
+    mod javascript {
+        use super::*;
+
+        #[test]
+        fn halstead_basic() {
+            let source_file = File::from_string("javascript", "a = b + b;");
+            assert_eq!(
+                Halstead {
+                    distinct_operators: 3,
+                    distinct_operands: 2,
+                    total_operators: 3,
+                    total_operands: 3,
+                },
+                count(
+                    &source_file,
+                    &source_file.parse().root_node(),
+                    &NodeFilter::empty()
+                )
+            );
+        }
+
+        #[test]
+        fn halstead_strings() {
+            let source_file = File::from_string("javascript", r#"a = "x" + "x";"#);
+            assert_eq!(
+                Halstead {
+                    distinct_operators: 3,
+                    distinct_operands: 2,
+                    total_operators: 3,
+                    total_operands: 3,
+                },
+                count(
+                    &source_file,
+                    &source_file.parse().root_node(),
+                    &NodeFilter::empty()
+                )
+            );
+        }
+
+        #[test]
+        fn halstead_comments() {
+            let source_file = File::from_string(
+                "javascript",
+                r#"
+                // a = a + a;
+                a = b;
+            "#,
+            );
+            assert_eq!(
+                Halstead {
+                    distinct_operators: 2,
+                    distinct_operands: 2,
+                    total_operators: 2,
+                    total_operands: 2,
+                },
+                count(
+                    &source_file,
+                    &source_file.parse().root_node(),
+                    &NodeFilter::empty()
+                )
+            );
+        }
+    }
+
+    mod python {
+        use super::*;
+
+        #[test]
+        fn halstead_basic() {
+            let source_file = File::from_string("python", "x = a + b * a");
+            assert_eq!(
+                Halstead {
+                    distinct_operators: 3,
+                    distinct_operands: 3,
+                    total_operators: 3,
+                    total_operands: 4,
+                },
+                count(
+                    &source_file,
+                    &source_file.parse().root_node(),
+                    &NodeFilter::empty()
+                )
+            );
+        }
+
+        #[test]
+        fn halstead_keywords() {
+            let source_file = File::from_string(
+                "python",
+                r#"
+def foo(x):
+    return x + 1
+            "#,
+            );
+            // Operators: def, (, ), :, return, +. Operands: foo, x, x, 1.
+            assert_eq!(
+                Halstead {
+                    distinct_operators: 6,
+                    distinct_operands: 3,
+                    total_operators: 6,
+                    total_operands: 4,
+                },
+                count(
+                    &source_file,
+                    &source_file.parse().root_node(),
+                    &NodeFilter::empty()
+                )
+            );
+        }
+    }
+
+    mod ruby {
+        use super::*;
+
+        #[test]
+        fn halstead_basic() {
+            let source_file = File::from_string("ruby", "x = y - 1");
+            assert_eq!(
+                Halstead {
+                    distinct_operators: 2,
+                    distinct_operands: 3,
+                    total_operators: 2,
+                    total_operands: 3,
+                },
+                count(
+                    &source_file,
+                    &source_file.parse().root_node(),
+                    &NodeFilter::empty()
+                )
+            );
+        }
+    }
+}
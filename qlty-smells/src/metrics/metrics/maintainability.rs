@@ -0,0 +1,32 @@
+/// Maintainability index normalized to a 0-100 scale, where higher is more
+/// maintainable: `(171 - 5.2 * ln(V) - 0.23 * G - 16.2 * ln(LOC)) * 100 / 171`
+/// with Halstead volume `V`, cyclomatic complexity `G` and lines of code `LOC`.
+pub fn index(volume: f64, cyclomatic: usize, code_lines: usize) -> f64 {
+    // Empty code would otherwise take the logarithm of zero
+    let volume = volume.max(1.0);
+    let code_lines = (code_lines as f64).max(1.0);
+
+    let index = 171.0 - 5.2 * volume.ln() - 0.23 * cyclomatic as f64 - 16.2 * code_lines.ln();
+
+    (index * 100.0 / 171.0).clamp(0.0, 100.0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn index_typical() {
+        assert!((index(1000.0, 10, 100) - 34.02).abs() < 0.01);
+    }
+
+    #[test]
+    fn index_empty() {
+        assert!((index(0.0, 1, 0) - 99.87).abs() < 0.01);
+    }
+
+    #[test]
+    fn index_clamped() {
+        assert_eq!(0.0, index(1e12, 500, 100_000));
+    }
+}
@@ -1,3 +1,4 @@
+mod coverage;
 mod executor;
 mod lines;
 pub mod metrics;
@@ -7,6 +8,7 @@ mod processor;
 mod results;
 mod settings;
 
+pub use coverage::Coverage;
 pub use executor::Executor;
 pub use lines::Lines;
 pub use plan::Plan;
@@ -1,6 +1,6 @@
 use qlty_analysis::code::{File, NodeFilter, NodeFilterBuilder};
 use qlty_analysis::workspace_entries::TargetMode;
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use super::MetricsMode;
 
@@ -10,6 +10,7 @@ pub struct Plan {
     pub target_mode: TargetMode,
     pub source_files: Vec<Arc<File>>,
     pub node_filter_builders: HashMap<String, NodeFilterBuilder>,
+    pub coverage: Arc<HashMap<PathBuf, Vec<i64>>>,
 }
 
 impl Plan {
@@ -21,6 +22,12 @@ impl Plan {
         }
     }
 
+    pub fn coverage_for(&self, source_file: &File) -> Option<&[i64]> {
+        self.coverage
+            .get(&source_file.path)
+            .map(|hits| hits.as_slice())
+    }
+
     pub fn description(&self) -> String {
         match self.target_mode {
             TargetMode::All => format!("{} over all targets", self.mode),
@@ -44,6 +44,7 @@ impl Planner {
             target_mode: self.settings.target_mode.clone(),
             node_filter_builders,
             source_files: self.files.clone(),
+            coverage: Arc::new(self.settings.coverage.clone()),
         })
     }
 
@@ -1,11 +1,15 @@
 use qlty_analysis::workspace_entries::TargetMode;
+use std::collections::HashMap;
 use std::fmt;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, Default)]
 pub struct Settings {
     pub functions: bool,
     pub target_mode: TargetMode,
     pub exclude_tests: bool,
+    /// Line hits from coverage reports by absolute path of the source file
+    pub coverage: HashMap<PathBuf, Vec<i64>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -1,8 +1,10 @@
 pub mod boolean_logic;
 pub mod file_complexity;
 pub mod function_complexity;
+pub mod large_class;
 pub mod nested_control;
 pub mod parameters;
+pub mod query;
 pub mod returns;
 
 use qlty_analysis::{code::File, snippet::truncate_snippet, utils::fs::path_to_string};
@@ -0,0 +1,344 @@
+use crate::metrics::metrics::{fields, functions, lcom4};
+use crate::structure::LargeClassThresholds;
+use qlty_analysis::code::{capture_by_name, capture_source, File, NodeFilter};
+use qlty_types::analysis::v1::{Issue, Level};
+use qlty_types::calculate_effort_minutes;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tree_sitter::Tree;
+
+use super::issue_for;
+
+pub const CHECK_NAME: &str = "large-class";
+
+const BASE_EFFORT_MINUTES: u32 = 20;
+const EFFORT_MINUTES_PER_VALUE_DELTA: u32 = 5;
+
+pub fn check(thresholds: LargeClassThresholds, source_file: Arc<File>, tree: &Tree) -> Vec<Issue> {
+    let mut issues: Vec<Issue> = Vec::new();
+
+    let language = source_file.language();
+    let filter = NodeFilter::empty();
+
+    // Like LCOM4, languages that implement methods outside of the type
+    // declaration are measured on their implementation blocks
+    let (query, capture_name) = match language.implementation_query() {
+        Some(implementation_query) => (implementation_query, "reference.implementation"),
+        None => (language.class_query(), "definition.class"),
+    };
+
+    let mut query_cursor = tree_sitter::QueryCursor::new();
+    query_cursor.set_match_limit(qlty_analysis::code::QUERY_MATCH_LIMIT as u32);
+
+    let all_matches =
+        query_cursor.matches(query, tree.root_node(), source_file.contents.as_bytes());
+
+    for class_match in all_matches {
+        let class_node = capture_by_name(query, capture_name, &class_match).node;
+        let class_name = capture_source(query, "name", &class_match, &source_file);
+
+        let measures = [
+            (
+                "methods",
+                thresholds.methods,
+                functions(&source_file, &class_node, &filter),
+            ),
+            (
+                "fields",
+                thresholds.fields,
+                fields(&source_file, &class_node, &filter),
+            ),
+            (
+                "LCOM4",
+                thresholds.lcom4,
+                lcom4(&source_file, &class_node, &filter),
+            ),
+        ];
+
+        let exceeded = measures
+            .iter()
+            .filter_map(|(label, threshold, count)| match threshold {
+                Some(threshold) if count >= threshold => Some((*label, *count, *threshold)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // Report the measure furthest over its threshold
+        let Some((_, count, threshold)) = exceeded
+            .iter()
+            .max_by_key(|(_, count, threshold)| count - threshold)
+        else {
+            continue;
+        };
+
+        let value_delta = (count - threshold) as u32;
+
+        let message = format!(
+            "Large class ({}): {}",
+            exceeded
+                .iter()
+                .map(|(label, count, _)| format!("{} = {}", label, count))
+                .collect::<Vec<_>>()
+                .join(", "),
+            class_name
+        );
+
+        let mut partial_fingerprints = HashMap::new();
+        partial_fingerprints.insert("class.name".to_string(), class_name);
+
+        issues.push(Issue {
+            rule_key: CHECK_NAME.to_string(),
+            message,
+            level: Level::Medium.into(),
+            value: *count as u32,
+            value_delta,
+            effort_minutes: calculate_effort_minutes(
+                value_delta,
+                BASE_EFFORT_MINUTES,
+                EFFORT_MINUTES_PER_VALUE_DELTA,
+            ),
+            partial_fingerprints,
+            ..issue_for(&source_file, &class_node)
+        });
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const METHODS: LargeClassThresholds = LargeClassThresholds {
+        methods: Some(2),
+        fields: None,
+        lcom4: None,
+    };
+
+    const FIELDS: LargeClassThresholds = LargeClassThresholds {
+        methods: None,
+        fields: Some(2),
+        lcom4: None,
+    };
+
+    fn summarize(thresholds: LargeClassThresholds, language: &str, source: &str) -> String {
+        let source_file = Arc::new(File::from_string(language, source));
+
+        check(thresholds, source_file.clone(), &source_file.parse())
+            .iter()
+            .map(|issue| format!("{}: {}", issue.range().unwrap().start_line, issue.message))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn large_class_c() {
+        let source = r#"
+struct Point {
+    int x;
+    int y;
+};
+"#;
+        insta::assert_snapshot!(summarize(FIELDS, "c", source), @"2: Large class (fields = 2): Point");
+    }
+
+    #[test]
+    fn large_class_cpp() {
+        let source = r#"
+class Foo {
+public:
+    void a() {}
+    void b() {}
+};
+"#;
+        insta::assert_snapshot!(summarize(METHODS, "cpp", source), @"2: Large class (methods = 2): Foo");
+    }
+
+    #[test]
+    fn large_class_csharp() {
+        let source = r#"
+class Foo
+{
+    void A() {}
+    void B() {}
+}
+"#;
+        insta::assert_snapshot!(summarize(METHODS, "csharp", source), @"2: Large class (methods = 2): Foo");
+    }
+
+    #[test]
+    fn large_class_go() {
+        let source = r#"
+type Point struct {
+    X int
+    Y int
+}
+"#;
+        insta::assert_snapshot!(summarize(FIELDS, "go", source), @"2: Large class (fields = 2): Point");
+    }
+
+    #[test]
+    fn large_class_java() {
+        let source = r#"
+class Foo {
+    void a() {}
+    void b() {}
+}
+"#;
+        insta::assert_snapshot!(summarize(METHODS, "java", source), @"2: Large class (methods = 2): Foo");
+    }
+
+    #[test]
+    fn large_class_javascript() {
+        let source = r#"
+class Foo {
+  a() {}
+  b() {}
+}
+"#;
+        insta::assert_snapshot!(summarize(METHODS, "javascript", source), @"2: Large class (methods = 2): Foo");
+    }
+
+    #[test]
+    fn large_class_kotlin() {
+        let source = r#"
+class Foo {
+    fun a() {}
+    fun b() {}
+}
+"#;
+        insta::assert_snapshot!(summarize(METHODS, "kotlin", source), @"2: Large class (methods = 2): Foo");
+    }
+
+    #[test]
+    fn large_class_php() {
+        let source = r#"<?php
+class Foo {
+    public function a() {}
+    public function b() {}
+}
+"#;
+        insta::assert_snapshot!(summarize(METHODS, "php", source), @"2: Large class (methods = 2): Foo");
+    }
+
+    #[test]
+    fn large_class_python() {
+        let source = r#"
+class Foo:
+    def a(self):
+        return self.x
+
+    def b(self):
+        return self.y
+"#;
+        let thresholds = LargeClassThresholds {
+            methods: Some(2),
+            fields: Some(2),
+            lcom4: Some(2),
+        };
+        insta::assert_snapshot!(summarize(thresholds, "python", source), @"2: Large class (methods = 2, fields = 2, LCOM4 = 2): Foo");
+    }
+
+    #[test]
+    fn large_class_python_not_found() {
+        let source = r#"
+class Foo:
+    def a(self):
+        return self.x
+
+    def b(self):
+        return self.x
+"#;
+        let thresholds = LargeClassThresholds {
+            methods: Some(3),
+            fields: Some(2),
+            lcom4: Some(2),
+        };
+        assert_eq!("", summarize(thresholds, "python", source));
+    }
+
+    #[test]
+    fn large_class_ruby() {
+        let source = r#"
+class Foo
+  def a
+  end
+
+  def b
+  end
+end
+"#;
+        insta::assert_snapshot!(summarize(METHODS, "ruby", source), @"2: Large class (methods = 2): Foo");
+    }
+
+    #[test]
+    fn large_class_rust() {
+        let source = r#"
+struct Foo;
+
+impl Foo {
+    fn a(&self) {}
+    fn b(&self) {}
+}
+"#;
+        insta::assert_snapshot!(summarize(METHODS, "rust", source), @"4: Large class (methods = 2): Foo");
+    }
+
+    #[test]
+    fn large_class_scala() {
+        let source = r#"
+class Foo {
+  def a(): Unit = {}
+  def b(): Unit = {}
+}
+"#;
+        insta::assert_snapshot!(summarize(METHODS, "scala", source), @"2: Large class (methods = 2): Foo");
+    }
+
+    #[test]
+    fn large_class_swift() {
+        let source = r#"
+class Foo {
+    func a() {}
+    func b() {}
+}
+"#;
+        insta::assert_snapshot!(summarize(METHODS, "swift", source), @"2: Large class (methods = 2): Foo");
+    }
+
+    #[test]
+    fn large_class_tsx() {
+        let source = r#"
+class Foo {
+  a(): void {}
+  b(): void {}
+}
+"#;
+        insta::assert_snapshot!(summarize(METHODS, "tsx", source), @"2: Large class (methods = 2): Foo");
+    }
+
+    #[test]
+    fn large_class_typescript() {
+        let source = r#"
+class Foo {
+  a(): void {}
+  b(): void {}
+}
+"#;
+        insta::assert_snapshot!(summarize(METHODS, "typescript", source), @"2: Large class (methods = 2): Foo");
+    }
+
+    #[test]
+    fn large_class_vbnet() {
+        let source = r#"
+Public Class Foo
+    Public Sub A()
+    End Sub
+
+    Public Sub B()
+    End Sub
+End Class
+"#;
+        insta::assert_snapshot!(summarize(METHODS, "vbnet", source), @"2: Large class (methods = 2): Foo");
+    }
+}
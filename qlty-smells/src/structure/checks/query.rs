@@ -0,0 +1,229 @@
+use crate::structure::QueryPlan;
+use qlty_analysis::code::{capture_by_name_option, File};
+use qlty_types::analysis::v1::Issue;
+use qlty_types::calculate_effort_minutes;
+use std::path::Path;
+use std::sync::Arc;
+use tree_sitter::{Node, Tree};
+
+use super::issue_for;
+
+const BASE_EFFORT_MINUTES: u32 = 5;
+const EFFORT_MINUTES_PER_VALUE_DELTA: u32 = 1;
+
+pub fn check(
+    query_plan: &QueryPlan,
+    root: &Path,
+    source_file: Arc<File>,
+    tree: &Tree,
+) -> Vec<Issue> {
+    if !matches_file_patterns(query_plan, root, &source_file.path) {
+        return vec![];
+    }
+
+    let query = &query_plan.query;
+
+    let mut query_cursor = tree_sitter::QueryCursor::new();
+    query_cursor.set_match_limit(qlty_analysis::code::QUERY_MATCH_LIMIT as u32);
+
+    let mut nodes: Vec<Node> = vec![];
+
+    for query_match in
+        query_cursor.matches(query, tree.root_node(), source_file.contents.as_bytes())
+    {
+        let capture = capture_by_name_option(query, "match", &query_match)
+            .or_else(|| query_match.captures.first());
+
+        if let Some(capture) = capture {
+            // A node can match more than one pattern of the same query
+            if !nodes.iter().any(|node| node.id() == capture.node.id()) {
+                nodes.push(capture.node);
+            }
+        }
+    }
+
+    if nodes.is_empty() || nodes.len() < query_plan.threshold {
+        return vec![];
+    }
+
+    let count = nodes.len() as u32;
+    let value_delta = count - query_plan.threshold as u32;
+
+    nodes
+        .iter()
+        .map(|node| Issue {
+            rule_key: query_plan.name.clone(),
+            message: query_plan.message.clone(),
+            level: query_plan.level.into(),
+            category: query_plan.category.into(),
+            value: count,
+            value_delta,
+            effort_minutes: calculate_effort_minutes(
+                value_delta,
+                BASE_EFFORT_MINUTES,
+                EFFORT_MINUTES_PER_VALUE_DELTA,
+            ),
+            ..issue_for(&source_file, node)
+        })
+        .collect()
+}
+
+// File paths are absolute, so patterns are matched against the path
+// relative to the workspace root, as they are written in qlty.toml
+fn matches_file_patterns(query_plan: &QueryPlan, root: &Path, path: &Path) -> bool {
+    let Some(file_patterns) = &query_plan.file_patterns else {
+        return true;
+    };
+
+    file_patterns.is_match(path.strip_prefix(root).unwrap_or(path))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use globset::{Glob, GlobSetBuilder};
+    use qlty_types::analysis::v1::{Category, Level};
+
+    fn query_plan(language: &str, query: &str, threshold: usize) -> QueryPlan {
+        QueryPlan {
+            name: "no-fetch".to_string(),
+            message: "Use the API client instead of fetch".to_string(),
+            query: Arc::new(File::from_string(language, "").query(query)),
+            level: Level::High,
+            category: Category::AntiPattern,
+            threshold,
+            file_patterns: None,
+        }
+    }
+
+    const FETCH_QUERY: &str = r#"
+        (call_expression
+          function: (identifier) @name
+          (#eq? @name "fetch")) @match
+    "#;
+
+    #[test]
+    fn query_javascript() {
+        let source_file = Arc::new(File::from_string(
+            "javascript",
+            r#"
+            fetch("/users");
+            client.fetch("/users");
+            "#,
+        ));
+
+        insta::assert_yaml_snapshot!(check(&query_plan("javascript", FETCH_QUERY, 1), Path::new(""), source_file.clone(), &source_file.parse()), @r#"
+        - tool: qlty
+          driver: structure
+          ruleKey: no-fetch
+          message: Use the API client instead of fetch
+          level: LEVEL_HIGH
+          language: LANGUAGE_JAVASCRIPT
+          category: CATEGORY_ANTI_PATTERN
+          snippet: "fetch(\"/users\")"
+          snippetWithContext: "\n            fetch(\"/users\");\n            client.fetch(\"/users\");\n            "
+          effortMinutes: 5
+          value: 1
+          location:
+            path: STRING
+            range:
+              startLine: 2
+              startColumn: 13
+              endLine: 2
+              endColumn: 28
+              startByte: 13
+              endByte: 28
+        "#);
+    }
+
+    #[test]
+    fn query_threshold() {
+        let source_file = Arc::new(File::from_string(
+            "python",
+            r#"
+print("a")
+print("b")
+            "#,
+        ));
+        let query = r#"((call function: (identifier) @name) @match (#eq? @name "print"))"#;
+
+        assert_eq!(
+            0,
+            check(
+                &query_plan("python", query, 3),
+                Path::new(""),
+                source_file.clone(),
+                &source_file.parse()
+            )
+            .len()
+        );
+
+        let issues = check(
+            &query_plan("python", query, 2),
+            Path::new(""),
+            source_file.clone(),
+            &source_file.parse(),
+        );
+        assert_eq!(2, issues.len());
+        assert_eq!(2, issues[0].value);
+        assert_eq!(0, issues[0].value_delta);
+    }
+
+    #[test]
+    fn query_first_capture() {
+        let source_file = Arc::new(File::from_string("ruby", "puts 1\nputs 2\n"));
+
+        let issues = check(
+            &query_plan("ruby", "(integer) @number", 1),
+            Path::new(""),
+            source_file.clone(),
+            &source_file.parse(),
+        );
+        assert_eq!(2, issues.len());
+        assert_eq!("1", issues[0].snippet);
+    }
+
+    #[test]
+    fn query_file_patterns() {
+        let mut builder = GlobSetBuilder::new();
+        builder.add(Glob::new("packages/web/**").unwrap());
+
+        let query_plan = QueryPlan {
+            file_patterns: Some(builder.build().unwrap()),
+            ..query_plan("javascript", FETCH_QUERY, 1)
+        };
+
+        assert!(matches_file_patterns(
+            &query_plan,
+            Path::new("/repo"),
+            Path::new("/repo/packages/web/src/users.js")
+        ));
+        assert!(!matches_file_patterns(
+            &query_plan,
+            Path::new("/repo"),
+            Path::new("/repo/packages/api/src/users.js")
+        ));
+    }
+
+    #[test]
+    fn query_file_patterns_relative_to_root() {
+        let mut builder = GlobSetBuilder::new();
+        builder.add(Glob::new("src/**").unwrap());
+
+        let query_plan = QueryPlan {
+            file_patterns: Some(builder.build().unwrap()),
+            ..query_plan("javascript", FETCH_QUERY, 1)
+        };
+
+        assert!(matches_file_patterns(
+            &query_plan,
+            Path::new("/repo"),
+            Path::new("/repo/src/users.js")
+        ));
+        assert!(!matches_file_patterns(
+            &query_plan,
+            Path::new("/repo"),
+            Path::new("/repo/packages/api/src/users.js")
+        ));
+    }
+}
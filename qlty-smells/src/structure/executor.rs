@@ -90,6 +90,23 @@ impl Executor {
             ));
         }
 
+        if let Some(thresholds) = language.large_class {
+            issues.extend(checks::large_class::check(
+                thresholds,
+                source_file.clone(),
+                &tree,
+            ));
+        }
+
+        for query_plan in &language.queries {
+            issues.extend(checks::query::check(
+                query_plan,
+                &self.plan.root,
+                source_file.clone(),
+                &tree,
+            ));
+        }
+
         for issue in &mut issues {
             issue.mode = language.issue_mode as i32;
         }
@@ -4,5 +4,5 @@ mod plan;
 mod planner;
 
 pub use executor::Executor;
-pub use plan::{LanguagePlan, Plan};
+pub use plan::{LanguagePlan, LargeClassThresholds, Plan, QueryPlan};
 pub use planner::Planner;
@@ -1,9 +1,13 @@
+use globset::GlobSet;
 use qlty_analysis::code::File;
 use qlty_config::config::IssueMode;
-use std::{collections::HashMap, sync::Arc};
+use qlty_types::analysis::v1::{Category, Level};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+use tree_sitter::Query;
 
 #[derive(Clone, Debug)]
 pub struct Plan {
+    pub root: PathBuf,
     pub languages: HashMap<String, LanguagePlan>,
     pub source_files: Vec<Arc<File>>,
 }
@@ -25,5 +29,26 @@ pub struct LanguagePlan {
     pub nested_control: Option<usize>,
     pub parameters: Option<usize>,
     pub returns: Option<usize>,
+    pub large_class: Option<LargeClassThresholds>,
+    pub queries: Vec<QueryPlan>,
     pub issue_mode: IssueMode,
 }
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LargeClassThresholds {
+    pub methods: Option<usize>,
+    pub fields: Option<usize>,
+    pub lcom4: Option<usize>,
+}
+
+/// A user-defined query check, compiled for one language
+#[derive(Clone, Debug)]
+pub struct QueryPlan {
+    pub name: String,
+    pub message: String,
+    pub query: Arc<Query>,
+    pub level: Level,
+    pub category: Category,
+    pub threshold: usize,
+    pub file_patterns: Option<GlobSet>,
+}
@@ -1,28 +1,40 @@
-use super::{LanguagePlan, Plan};
-use anyhow::Result;
+use super::{LanguagePlan, LargeClassThresholds, Plan, QueryPlan};
+use anyhow::{bail, Context, Result};
+use globset::{Glob, GlobSetBuilder};
 use qlty_analysis::code::File;
 use qlty_config::{
     config::{
         smells::{
-            BooleanLogic, FileComplexity, FunctionComplexity, FunctionParameters,
-            NestedControlFlow, ReturnStatements,
+            BooleanLogic, FileComplexity, FunctionComplexity, FunctionParameters, LargeClass,
+            NestedControlFlow, QueryCheck, ReturnStatements,
         },
         IssueMode, Language,
     },
     QltyConfig,
 };
-use std::{collections::HashMap, sync::Arc};
+use qlty_types::analysis::v1::{Category, Level};
+use qlty_types::{category_from_str, level_from_str};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tree_sitter::{Query, QueryError};
+
+const DEFAULT_QUERY_THRESHOLD: usize = 1;
 
 #[derive(Debug, Clone)]
 pub struct Planner {
     config: QltyConfig,
+    root: PathBuf,
     files: Vec<Arc<File>>,
 }
 
 impl Planner {
-    pub fn new(config: &QltyConfig, files: Vec<Arc<File>>) -> Result<Self> {
+    pub fn new(config: &QltyConfig, root: &Path, files: Vec<Arc<File>>) -> Result<Self> {
         Ok(Self {
             config: config.clone(),
+            root: root.to_path_buf(),
             files,
         })
     }
@@ -197,6 +209,80 @@ impl Planner {
         ReturnStatements::default().threshold
     }
 
+    // Each threshold falls back to the global setting separately, so a
+    // language can override just one of them
+    fn extract_large_class(&self, language: &Language) -> Option<LargeClassThresholds> {
+        let language_large_class = language
+            .smells
+            .as_ref()
+            .and_then(|smells| smells.large_class);
+        let global_large_class = self
+            .config
+            .smells
+            .as_ref()
+            .and_then(|smells| smells.large_class);
+
+        if let Some(large_class) = language_large_class {
+            if !large_class.enabled {
+                return None;
+            }
+        } else if let Some(large_class) = global_large_class {
+            if !large_class.enabled {
+                return None;
+            }
+        }
+
+        let default = LargeClass::default();
+        let threshold = |select: fn(&LargeClass) -> Option<usize>| {
+            language_large_class
+                .as_ref()
+                .and_then(select)
+                .or_else(|| global_large_class.as_ref().and_then(select))
+                .or_else(|| select(&default))
+        };
+
+        let thresholds = LargeClassThresholds {
+            methods: threshold(|large_class| large_class.methods_threshold),
+            fields: threshold(|large_class| large_class.fields_threshold),
+            lcom4: threshold(|large_class| large_class.lcom4_threshold),
+        };
+
+        if thresholds == LargeClassThresholds::default() {
+            None
+        } else {
+            Some(thresholds)
+        }
+    }
+
+    // Global queries apply to the languages they list, or to every language
+    // they compile for when they list none. Queries configured under a
+    // language apply to that language only.
+    fn extract_queries(&self, name: &str, language: &Language) -> Result<Vec<QueryPlan>> {
+        let mut queries = vec![];
+
+        if let Some(smells) = &self.config.smells {
+            for check in &smells.queries {
+                if check.languages.is_empty() {
+                    match query_plan(check, name) {
+                        Ok(query_plan) => queries.extend(query_plan),
+                        Err(error) if error.is::<QueryError>() => {}
+                        Err(error) => return Err(error),
+                    }
+                } else if check.languages.iter().any(|language| language == name) {
+                    queries.extend(query_plan(check, name)?);
+                }
+            }
+        }
+
+        if let Some(smells) = &language.smells {
+            for check in &smells.queries {
+                queries.extend(query_plan(check, name)?);
+            }
+        }
+
+        Ok(queries)
+    }
+
     pub fn compute(&self) -> Result<Plan> {
         let mut languages = HashMap::new();
 
@@ -208,6 +294,8 @@ impl Planner {
                 nested_control: self.extract_nested_control_flow(language_settings),
                 parameters: self.extract_function_parameters(language_settings),
                 returns: self.extract_return_statements(language_settings),
+                large_class: self.extract_large_class(language_settings),
+                queries: self.extract_queries(name, language_settings)?,
                 issue_mode: IssueMode::extract_issue_mode_from_smells(
                     language_settings,
                     &self.config,
@@ -218,8 +306,177 @@ impl Planner {
         }
 
         Ok(Plan {
+            root: self.root.clone(),
             languages,
             source_files: self.files.clone(),
         })
     }
 }
+
+fn query_plan(check: &QueryCheck, language_name: &str) -> Result<Option<QueryPlan>> {
+    if !check.enabled {
+        return Ok(None);
+    }
+
+    let Some(language) = qlty_analysis::lang::from_str(language_name) else {
+        return Ok(None);
+    };
+
+    let query = Query::new(&language.tree_sitter_language(), &check.query).with_context(|| {
+        format!(
+            "Invalid query for smells check {} in {}",
+            check.name, language_name
+        )
+    })?;
+
+    if query.capture_names().is_empty() {
+        bail!(
+            "Query for smells check {} must capture the node to report, e.g. @match",
+            check.name
+        );
+    }
+
+    let level = match &check.level {
+        Some(level) => match level_from_str(level) {
+            Level::Unspecified => bail!("Unknown level {} for smells check {}", level, check.name),
+            level => level,
+        },
+        None => Level::Medium,
+    };
+
+    let category = match &check.category {
+        Some(category) => match category_from_str(category) {
+            Category::Unspecified => bail!(
+                "Unknown category {} for smells check {}",
+                category,
+                check.name
+            ),
+            category => category,
+        },
+        None => Category::Structure,
+    };
+
+    let file_patterns = if check.file_patterns.is_empty() {
+        None
+    } else {
+        let mut builder = GlobSetBuilder::new();
+
+        for pattern in &check.file_patterns {
+            builder.add(Glob::new(pattern)?);
+        }
+
+        Some(builder.build()?)
+    };
+
+    Ok(Some(QueryPlan {
+        name: check.name.clone(),
+        message: check.message.clone(),
+        query: Arc::new(query),
+        level,
+        category,
+        threshold: check
+            .thresholds
+            .get(language_name)
+            .copied()
+            .or(check.threshold)
+            .unwrap_or(DEFAULT_QUERY_THRESHOLD),
+        file_patterns,
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use qlty_config::config::smells::Smells;
+
+    fn config(queries: Vec<QueryCheck>) -> QltyConfig {
+        let mut config = QltyConfig::default();
+
+        for language in ["javascript", "python"] {
+            config
+                .language
+                .insert(language.to_string(), Default::default());
+        }
+
+        config.smells = Some(Smells {
+            queries,
+            ..Default::default()
+        });
+        config
+    }
+
+    fn fetch_check() -> QueryCheck {
+        QueryCheck {
+            enabled: true,
+            name: "no-fetch".to_string(),
+            query:
+                r#"((call_expression function: (identifier) @name) @match (#eq? @name "fetch"))"#
+                    .to_string(),
+            message: "Use the API client".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn queries_for_compatible_languages() {
+        let plan = Planner::new(&config(vec![fetch_check()]), Path::new("/repo"), vec![])
+            .unwrap()
+            .compute()
+            .unwrap();
+
+        let queries = plan.get_language("javascript").queries;
+        assert_eq!(1, queries.len());
+        assert_eq!(Level::Medium, queries[0].level);
+        assert_eq!(Category::Structure, queries[0].category);
+        assert_eq!(1, queries[0].threshold);
+
+        assert!(plan.get_language("python").queries.is_empty());
+    }
+
+    #[test]
+    fn queries_thresholds() {
+        let check = QueryCheck {
+            languages: vec!["javascript".to_string()],
+            level: Some("high".to_string()),
+            category: Some("anti_pattern".to_string()),
+            threshold: Some(2),
+            thresholds: HashMap::from([("javascript".to_string(), 3)]),
+            ..fetch_check()
+        };
+
+        let plan = Planner::new(&config(vec![check]), Path::new("/repo"), vec![])
+            .unwrap()
+            .compute()
+            .unwrap();
+
+        let queries = plan.get_language("javascript").queries;
+        assert_eq!(Level::High, queries[0].level);
+        assert_eq!(Category::AntiPattern, queries[0].category);
+        assert_eq!(3, queries[0].threshold);
+    }
+
+    #[test]
+    fn queries_invalid() {
+        let check = QueryCheck {
+            languages: vec!["python".to_string()],
+            ..fetch_check()
+        };
+        assert!(
+            Planner::new(&config(vec![check]), Path::new("/repo"), vec![])
+                .unwrap()
+                .compute()
+                .is_err()
+        );
+
+        let check = QueryCheck {
+            level: Some("severe".to_string()),
+            ..fetch_check()
+        };
+        assert!(
+            Planner::new(&config(vec![check]), Path::new("/repo"), vec![])
+                .unwrap()
+                .compute()
+                .is_err()
+        );
+    }
+}
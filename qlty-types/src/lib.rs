@@ -357,6 +357,32 @@ impl Add for analysis::v1::Stats {
             complexity: Some(self.complexity.unwrap_or(0) + other.complexity.unwrap_or(0)),
             cyclomatic: Some(self.cyclomatic.unwrap_or(0) + other.cyclomatic.unwrap_or(0)),
             lcom4: Some(self.lcom4.unwrap_or(0) + other.lcom4.unwrap_or(0)),
+            covered_lines: add_optional(self.covered_lines, other.covered_lines),
+            uncovered_lines: add_optional(self.uncovered_lines, other.uncovered_lines),
+            halstead_volume: add_optional_f64(self.halstead_volume, other.halstead_volume),
+            halstead_effort: add_optional_f64(self.halstead_effort, other.halstead_effort),
+            // Effort is difficulty times volume, so the combined difficulty is
+            // the volume-weighted mean of both sides
+            halstead_difficulty: weighted_mean(
+                (
+                    self.halstead_difficulty,
+                    self.halstead_volume.unwrap_or(0.0),
+                ),
+                (
+                    other.halstead_difficulty,
+                    other.halstead_volume.unwrap_or(0.0),
+                ),
+            ),
+            maintainability_index: weighted_mean(
+                (
+                    self.maintainability_index,
+                    self.code_lines.unwrap_or(0) as f64,
+                ),
+                (
+                    other.maintainability_index,
+                    other.code_lines.unwrap_or(0) as f64,
+                ),
+            ),
             reference: self.reference,
             build_id: self.build_id,
             commit_sha: self.commit_sha,
@@ -366,10 +392,75 @@ impl Add for analysis::v1::Stats {
     }
 }
 
+// Coverage is only known when a coverage report was provided, so it stays
+// unset unless one of the sides has it
+fn add_optional(a: Option<u32>, b: Option<u32>) -> Option<u32> {
+    match (a, b) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+    }
+}
+
+fn add_optional_f64(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(0.0) + b.unwrap_or(0.0)),
+    }
+}
+
+fn weighted_mean(a: (Option<f64>, f64), b: (Option<f64>, f64)) -> Option<f64> {
+    match (a, b) {
+        ((None, _), (None, _)) => None,
+        ((Some(value), _), (None, _)) | ((None, _), (Some(value), _)) => Some(value),
+        ((Some(a), a_weight), (Some(b), b_weight)) => {
+            if a_weight + b_weight == 0.0 {
+                Some((a + b) / 2.0)
+            } else {
+                Some((a * a_weight + b * b_weight) / (a_weight + b_weight))
+            }
+        }
+    }
+}
+
+impl analysis::v1::Stats {
+    /// Percentage of executable lines covered by tests, or `None` when no
+    /// coverage is known or the component has no executable lines.
+    pub fn coverage_percent(&self) -> Option<f64> {
+        let covered = self.covered_lines?;
+        let executable = covered + self.uncovered_lines.unwrap_or(0);
+
+        if executable == 0 {
+            None
+        } else {
+            Some(covered as f64 / executable as f64 * 100.0)
+        }
+    }
+
+    /// Change Risk Anti-Patterns score, which combines cyclomatic complexity
+    /// with coverage: `complexity^2 * (1 - coverage)^3 + complexity`.
+    pub fn crap(&self) -> Option<f64> {
+        let complexity = self.cyclomatic? as f64;
+        let uncovered = 1.0 - self.coverage_percent()? / 100.0;
+
+        Some(complexity.powi(2) * uncovered.powi(3) + complexity)
+    }
+}
+
 impl tests::v1::CoverageSummary {
     pub fn percent(&self) -> f64 {
         self.covered as f64 / (self.covered + self.missed) as f64 * 100.0
     }
+
+    /// Percentage of branches taken, or `None` when the report has no branch data.
+    pub fn branch_percent(&self) -> Option<f64> {
+        let branches = self.branches_covered + self.branches_missed;
+
+        if branches == 0 {
+            None
+        } else {
+            Some(self.branches_covered as f64 / branches as f64 * 100.0)
+        }
+    }
 }
 
 impl Add for tests::v1::CoverageSummary {
@@ -381,6 +472,8 @@ impl Add for tests::v1::CoverageSummary {
             missed: self.missed + other.missed,
             omit: self.omit + other.omit,
             total: self.total + other.total,
+            branches_covered: self.branches_covered + other.branches_covered,
+            branches_missed: self.branches_missed + other.branches_missed,
         }
     }
 }
@@ -139,6 +139,18 @@ pub struct Stats {
     pub cyclomatic: ::core::option::Option<u32>,
     #[prost(uint32, optional, tag="110")]
     pub lcom4: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="111")]
+    pub covered_lines: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="112")]
+    pub uncovered_lines: ::core::option::Option<u32>,
+    #[prost(double, optional, tag="113")]
+    pub halstead_volume: ::core::option::Option<f64>,
+    #[prost(double, optional, tag="114")]
+    pub halstead_difficulty: ::core::option::Option<f64>,
+    #[prost(double, optional, tag="115")]
+    pub halstead_effort: ::core::option::Option<f64>,
+    #[prost(double, optional, tag="116")]
+    pub maintainability_index: ::core::option::Option<f64>,
     #[prost(message, optional, tag="17")]
     pub generated_at: ::core::option::Option<::pbjson_types::Timestamp>,
     #[prost(message, optional, tag="18")]
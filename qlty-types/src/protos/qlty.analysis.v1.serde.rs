@@ -4060,6 +4060,24 @@ impl serde::Serialize for Stats {
         if self.lcom4.is_some() {
             len += 1;
         }
+        if self.covered_lines.is_some() {
+            len += 1;
+        }
+        if self.uncovered_lines.is_some() {
+            len += 1;
+        }
+        if self.halstead_volume.is_some() {
+            len += 1;
+        }
+        if self.halstead_difficulty.is_some() {
+            len += 1;
+        }
+        if self.halstead_effort.is_some() {
+            len += 1;
+        }
+        if self.maintainability_index.is_some() {
+            len += 1;
+        }
         if self.generated_at.is_some() {
             len += 1;
         }
@@ -4146,6 +4164,24 @@ impl serde::Serialize for Stats {
         if let Some(v) = self.lcom4.as_ref() {
             struct_ser.serialize_field("lcom4", v)?;
         }
+        if let Some(v) = self.covered_lines.as_ref() {
+            struct_ser.serialize_field("coveredLines", v)?;
+        }
+        if let Some(v) = self.uncovered_lines.as_ref() {
+            struct_ser.serialize_field("uncoveredLines", v)?;
+        }
+        if let Some(v) = self.halstead_volume.as_ref() {
+            struct_ser.serialize_field("halsteadVolume", v)?;
+        }
+        if let Some(v) = self.halstead_difficulty.as_ref() {
+            struct_ser.serialize_field("halsteadDifficulty", v)?;
+        }
+        if let Some(v) = self.halstead_effort.as_ref() {
+            struct_ser.serialize_field("halsteadEffort", v)?;
+        }
+        if let Some(v) = self.maintainability_index.as_ref() {
+            struct_ser.serialize_field("maintainabilityIndex", v)?;
+        }
         if let Some(v) = self.generated_at.as_ref() {
             struct_ser.serialize_field("generatedAt", v)?;
         }
@@ -4199,6 +4235,18 @@ impl<'de> serde::Deserialize<'de> for Stats {
             "complexity",
             "cyclomatic",
             "lcom4",
+            "covered_lines",
+            "coveredLines",
+            "uncovered_lines",
+            "uncoveredLines",
+            "halstead_volume",
+            "halsteadVolume",
+            "halstead_difficulty",
+            "halsteadDifficulty",
+            "halstead_effort",
+            "halsteadEffort",
+            "maintainability_index",
+            "maintainabilityIndex",
             "generated_at",
             "generatedAt",
             "time",
@@ -4231,6 +4279,12 @@ impl<'de> serde::Deserialize<'de> for Stats {
             Complexity,
             Cyclomatic,
             Lcom4,
+            CoveredLines,
+            UncoveredLines,
+            HalsteadVolume,
+            HalsteadDifficulty,
+            HalsteadEffort,
+            MaintainabilityIndex,
             GeneratedAt,
             Time,
         }
@@ -4279,6 +4333,12 @@ impl<'de> serde::Deserialize<'de> for Stats {
                             "complexity" => Ok(GeneratedField::Complexity),
                             "cyclomatic" => Ok(GeneratedField::Cyclomatic),
                             "lcom4" => Ok(GeneratedField::Lcom4),
+                            "coveredLines" | "covered_lines" => Ok(GeneratedField::CoveredLines),
+                            "uncoveredLines" | "uncovered_lines" => Ok(GeneratedField::UncoveredLines),
+                            "halsteadVolume" | "halstead_volume" => Ok(GeneratedField::HalsteadVolume),
+                            "halsteadDifficulty" | "halstead_difficulty" => Ok(GeneratedField::HalsteadDifficulty),
+                            "halsteadEffort" | "halstead_effort" => Ok(GeneratedField::HalsteadEffort),
+                            "maintainabilityIndex" | "maintainability_index" => Ok(GeneratedField::MaintainabilityIndex),
                             "generatedAt" | "generated_at" => Ok(GeneratedField::GeneratedAt),
                             "time" => Ok(GeneratedField::Time),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
@@ -4325,6 +4385,12 @@ impl<'de> serde::Deserialize<'de> for Stats {
                 let mut complexity__ = None;
                 let mut cyclomatic__ = None;
                 let mut lcom4__ = None;
+                let mut covered_lines__ = None;
+                let mut uncovered_lines__ = None;
+                let mut halstead_volume__ = None;
+                let mut halstead_difficulty__ = None;
+                let mut halstead_effort__ = None;
+                let mut maintainability_index__ = None;
                 let mut generated_at__ = None;
                 let mut time__ = None;
                 while let Some(k) = map_.next_key()? {
@@ -4501,6 +4567,54 @@ impl<'de> serde::Deserialize<'de> for Stats {
                                 map_.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
                             ;
                         }
+                        GeneratedField::CoveredLines => {
+                            if covered_lines__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("coveredLines"));
+                            }
+                            covered_lines__ = 
+                                map_.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                        GeneratedField::UncoveredLines => {
+                            if uncovered_lines__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("uncoveredLines"));
+                            }
+                            uncovered_lines__ = 
+                                map_.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                        GeneratedField::HalsteadVolume => {
+                            if halstead_volume__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("halsteadVolume"));
+                            }
+                            halstead_volume__ = 
+                                map_.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                        GeneratedField::HalsteadDifficulty => {
+                            if halstead_difficulty__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("halsteadDifficulty"));
+                            }
+                            halstead_difficulty__ = 
+                                map_.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                        GeneratedField::HalsteadEffort => {
+                            if halstead_effort__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("halsteadEffort"));
+                            }
+                            halstead_effort__ = 
+                                map_.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                        GeneratedField::MaintainabilityIndex => {
+                            if maintainability_index__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("maintainabilityIndex"));
+                            }
+                            maintainability_index__ = 
+                                map_.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
                         GeneratedField::GeneratedAt => {
                             if generated_at__.is_some() {
                                 return Err(serde::de::Error::duplicate_field("generatedAt"));
@@ -4541,6 +4655,12 @@ impl<'de> serde::Deserialize<'de> for Stats {
                     complexity: complexity__,
                     cyclomatic: cyclomatic__,
                     lcom4: lcom4__,
+                    covered_lines: covered_lines__,
+                    uncovered_lines: uncovered_lines__,
+                    halstead_volume: halstead_volume__,
+                    halstead_difficulty: halstead_difficulty__,
+                    halstead_effort: halstead_effort__,
+                    maintainability_index: maintainability_index__,
                     generated_at: generated_at__,
                     time: time__,
                 })
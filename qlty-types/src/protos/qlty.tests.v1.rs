@@ -148,6 +148,17 @@ pub struct FileCoverage {
     pub branch: ::prost::alloc::string::String,
     #[prost(string, optional, tag="14")]
     pub pull_request_number: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag="16")]
+    pub branches: ::prost::alloc::vec::Vec<BranchCoverage>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct BranchCoverage {
+    #[prost(uint32, tag="1")]
+    pub line: u32,
+    #[prost(int64, tag="2")]
+    pub total: i64,
+    #[prost(int64, tag="3")]
+    pub covered: i64,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct CoverageSummary {
@@ -159,6 +170,10 @@ pub struct CoverageSummary {
     pub omit: i64,
     #[prost(int64, tag="4")]
     pub total: i64,
+    #[prost(int64, tag="5")]
+    pub branches_covered: i64,
+    #[prost(int64, tag="6")]
+    pub branches_missed: i64,
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -1,4 +1,139 @@
 // @generated
+impl serde::Serialize for BranchCoverage {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.line != 0 {
+            len += 1;
+        }
+        if self.total != 0 {
+            len += 1;
+        }
+        if self.covered != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("qlty.tests.v1.BranchCoverage", len)?;
+        if self.line != 0 {
+            struct_ser.serialize_field("line", &self.line)?;
+        }
+        if self.total != 0 {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("total", ToString::to_string(&self.total).as_str())?;
+        }
+        if self.covered != 0 {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("covered", ToString::to_string(&self.covered).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for BranchCoverage {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "line",
+            "total",
+            "covered",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Line,
+            Total,
+            Covered,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "line" => Ok(GeneratedField::Line),
+                            "total" => Ok(GeneratedField::Total),
+                            "covered" => Ok(GeneratedField::Covered),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = BranchCoverage;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct qlty.tests.v1.BranchCoverage")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<BranchCoverage, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut line__ = None;
+                let mut total__ = None;
+                let mut covered__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::Line => {
+                            if line__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("line"));
+                            }
+                            line__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Total => {
+                            if total__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("total"));
+                            }
+                            total__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Covered => {
+                            if covered__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("covered"));
+                            }
+                            covered__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(BranchCoverage {
+                    line: line__.unwrap_or_default(),
+                    total: total__.unwrap_or_default(),
+                    covered: covered__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("qlty.tests.v1.BranchCoverage", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for CoverageMetadata {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -879,6 +1014,12 @@ impl serde::Serialize for CoverageSummary {
         if self.total != 0 {
             len += 1;
         }
+        if self.branches_covered != 0 {
+            len += 1;
+        }
+        if self.branches_missed != 0 {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("qlty.tests.v1.CoverageSummary", len)?;
         if self.covered != 0 {
             #[allow(clippy::needless_borrow)]
@@ -900,6 +1041,16 @@ impl serde::Serialize for CoverageSummary {
             #[allow(clippy::needless_borrows_for_generic_args)]
             struct_ser.serialize_field("total", ToString::to_string(&self.total).as_str())?;
         }
+        if self.branches_covered != 0 {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("branchesCovered", ToString::to_string(&self.branches_covered).as_str())?;
+        }
+        if self.branches_missed != 0 {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("branchesMissed", ToString::to_string(&self.branches_missed).as_str())?;
+        }
         struct_ser.end()
     }
 }
@@ -914,6 +1065,10 @@ impl<'de> serde::Deserialize<'de> for CoverageSummary {
             "missed",
             "omit",
             "total",
+            "branches_covered",
+            "branchesCovered",
+            "branches_missed",
+            "branchesMissed",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -922,6 +1077,8 @@ impl<'de> serde::Deserialize<'de> for CoverageSummary {
             Missed,
             Omit,
             Total,
+            BranchesCovered,
+            BranchesMissed,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -947,6 +1104,8 @@ impl<'de> serde::Deserialize<'de> for CoverageSummary {
                             "missed" => Ok(GeneratedField::Missed),
                             "omit" => Ok(GeneratedField::Omit),
                             "total" => Ok(GeneratedField::Total),
+                            "branchesCovered" | "branches_covered" => Ok(GeneratedField::BranchesCovered),
+                            "branchesMissed" | "branches_missed" => Ok(GeneratedField::BranchesMissed),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -970,6 +1129,8 @@ impl<'de> serde::Deserialize<'de> for CoverageSummary {
                 let mut missed__ = None;
                 let mut omit__ = None;
                 let mut total__ = None;
+                let mut branches_covered__ = None;
+                let mut branches_missed__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::Covered => {
@@ -1004,6 +1165,22 @@ impl<'de> serde::Deserialize<'de> for CoverageSummary {
                                 Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
                             ;
                         }
+                        GeneratedField::BranchesCovered => {
+                            if branches_covered__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("branchesCovered"));
+                            }
+                            branches_covered__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::BranchesMissed => {
+                            if branches_missed__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("branchesMissed"));
+                            }
+                            branches_missed__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
                     }
                 }
                 Ok(CoverageSummary {
@@ -1011,6 +1188,8 @@ impl<'de> serde::Deserialize<'de> for CoverageSummary {
                     missed: missed__.unwrap_or_default(),
                     omit: omit__.unwrap_or_default(),
                     total: total__.unwrap_or_default(),
+                    branches_covered: branches_covered__.unwrap_or_default(),
+                    branches_missed: branches_missed__.unwrap_or_default(),
                 })
             }
         }
@@ -1067,6 +1246,9 @@ impl serde::Serialize for FileCoverage {
         if self.pull_request_number.is_some() {
             len += 1;
         }
+        if !self.branches.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("qlty.tests.v1.FileCoverage", len)?;
         if !self.upload_id.is_empty() {
             struct_ser.serialize_field("uploadId", &self.upload_id)?;
@@ -1110,6 +1292,9 @@ impl serde::Serialize for FileCoverage {
         if let Some(v) = self.pull_request_number.as_ref() {
             struct_ser.serialize_field("pullRequestNumber", v)?;
         }
+        if !self.branches.is_empty() {
+            struct_ser.serialize_field("branches", &self.branches)?;
+        }
         struct_ser.end()
     }
 }
@@ -1143,6 +1328,7 @@ impl<'de> serde::Deserialize<'de> for FileCoverage {
             "branch",
             "pull_request_number",
             "pullRequestNumber",
+            "branches",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -1161,6 +1347,7 @@ impl<'de> serde::Deserialize<'de> for FileCoverage {
             UploadedAt,
             Branch,
             PullRequestNumber,
+            Branches,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1196,6 +1383,7 @@ impl<'de> serde::Deserialize<'de> for FileCoverage {
                             "uploadedAt" | "uploaded_at" => Ok(GeneratedField::UploadedAt),
                             "branch" => Ok(GeneratedField::Branch),
                             "pullRequestNumber" | "pull_request_number" => Ok(GeneratedField::PullRequestNumber),
+                            "branches" => Ok(GeneratedField::Branches),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1229,6 +1417,7 @@ impl<'de> serde::Deserialize<'de> for FileCoverage {
                 let mut uploaded_at__ = None;
                 let mut branch__ = None;
                 let mut pull_request_number__ = None;
+                let mut branches__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::UploadId => {
@@ -1318,6 +1507,12 @@ impl<'de> serde::Deserialize<'de> for FileCoverage {
                             }
                             pull_request_number__ = map_.next_value()?;
                         }
+                        GeneratedField::Branches => {
+                            if branches__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("branches"));
+                            }
+                            branches__ = Some(map_.next_value()?);
+                        }
                     }
                 }
                 Ok(FileCoverage {
@@ -1335,6 +1530,7 @@ impl<'de> serde::Deserialize<'de> for FileCoverage {
                     uploaded_at: uploaded_at__,
                     branch: branch__.unwrap_or_default(),
                     pull_request_number: pull_request_number__,
+                    branches: branches__.unwrap_or_default(),
                 })
             }
         }